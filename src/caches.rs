@@ -0,0 +1,178 @@
+use std::env;
+use std::path::PathBuf;
+
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+
+use crate::cleaner::{CleanError, CleanResult, remove_read_only_dir_all};
+use crate::scanner::walk::dir_size;
+use crate::scanner::{CleanTarget, TargetCategory, TargetKind};
+
+/// A toolchain cache shared by every project on the machine, rather than one
+/// that lives inside a project (e.g. Go's build and module caches, or a
+/// global `CARGO_TARGET_DIR`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GlobalCache {
+    /// Display name (e.g. "Go build cache").
+    pub name: String,
+    pub path: PathBuf,
+    pub size_bytes: u64,
+    /// Build caches are regenerated; module caches need the network.
+    pub category: TargetCategory,
+}
+
+/// Every known global cache that exists and isn't empty, resolved from the
+/// process environment.
+pub fn global_caches() -> Vec<GlobalCache> {
+    let env = |var: &str| env::var(var).ok();
+    caches_from(&env, dirs::cache_dir(), dirs::home_dir())
+}
+
+/// Like [`global_caches`], with `env` looking up environment variables (see
+/// [`go_cache_dirs`]).
+pub fn caches_from(
+    env: &dyn Fn(&str) -> Option<String>,
+    cache_dir: Option<PathBuf>,
+    home_dir: Option<PathBuf>,
+) -> Vec<GlobalCache> {
+    go_cache_dirs(env, cache_dir, home_dir)
+        .into_iter()
+        .chain(shared_cargo_target(env))
+        .filter_map(|(name, path, category)| sized_cache(name, path, category))
+        .collect()
+}
+
+/// The shared `CARGO_TARGET_DIR` from the process environment, if it is set,
+/// exists, and isn't empty.
+pub fn cargo_target_cache() -> Option<GlobalCache> {
+    let env = |var: &str| env::var(var).ok();
+    let (name, path, category) = shared_cargo_target(&env)?;
+    sized_cache(name, path, category)
+}
+
+/// The directory every Rust project builds into when `CARGO_TARGET_DIR` is
+/// set to an absolute path. Projects then have no `target/` of their own, so
+/// their build output only shows up here. A relative value is resolved
+/// against wherever cargo runs, so it can't be located.
+pub fn shared_cargo_target(
+    env: &dyn Fn(&str) -> Option<String>,
+) -> Option<(String, PathBuf, TargetCategory)> {
+    let path = PathBuf::from(env("CARGO_TARGET_DIR")?);
+    path.is_absolute().then(|| {
+        (
+            "Cargo target dir".to_string(),
+            path,
+            TargetCategory::BuildOutput,
+        )
+    })
+}
+
+/// Where Go keeps its caches, the way `go env` resolves them:
+///
+/// - build cache: `$GOCACHE`, else `go-build` under the user cache dir
+///   (`~/.cache`, `~/Library/Caches`, `%LocalAppData%`); `GOCACHE=off` has none
+/// - module cache: `$GOMODCACHE`, else `pkg/mod` under the first `$GOPATH`
+///   entry, else `~/go/pkg/mod`
+///
+/// `env` looks up an environment variable, so callers (and tests) can supply
+/// their own.
+pub fn go_cache_dirs(
+    env: &dyn Fn(&str) -> Option<String>,
+    cache_dir: Option<PathBuf>,
+    home_dir: Option<PathBuf>,
+) -> Vec<(String, PathBuf, TargetCategory)> {
+    let set = |var: &str| env(var).filter(|v| !v.is_empty());
+    let mut dirs = Vec::new();
+
+    let build = match set("GOCACHE") {
+        Some(v) if v == "off" => None,
+        Some(v) => Some(PathBuf::from(v)),
+        None => cache_dir.map(|d| d.join("go-build")),
+    };
+    if let Some(path) = build {
+        dirs.push((
+            "Go build cache".to_string(),
+            path,
+            TargetCategory::ToolCache,
+        ));
+    }
+
+    let gopath = set("GOPATH")
+        .and_then(|v| env::split_paths(&v).next())
+        .or_else(|| home_dir.map(|h| h.join("go")));
+    let modules = set("GOMODCACHE")
+        .map(PathBuf::from)
+        .or_else(|| gopath.map(|p| p.join("pkg").join("mod")));
+    if let Some(path) = modules {
+        dirs.push((
+            "Go module cache".to_string(),
+            path,
+            TargetCategory::DependencyCache,
+        ));
+    }
+
+    dirs
+}
+
+/// Size the cache at `path`, or `None` if it doesn't exist or is empty.
+fn sized_cache(name: String, path: PathBuf, category: TargetCategory) -> Option<GlobalCache> {
+    if !path.is_dir() {
+        return None;
+    }
+    let size_bytes = dir_size(&path).ok().filter(|&s| s > 0)?;
+    Some(GlobalCache {
+        name,
+        path,
+        size_bytes,
+        category,
+    })
+}
+
+/// Remove each of `caches` (or, with `dry_run`, only report what would be
+/// freed). The Go module cache is read-only on disk, which
+/// [`remove_dir_all`] works around.
+pub fn clean_caches(caches: &[GlobalCache], dry_run: bool) -> Vec<CleanResult> {
+    caches
+        .iter()
+        .map(|cache| {
+            let target = CleanTarget {
+                path: cache.path.clone(),
+                name: cache.name.clone(),
+                size_bytes: cache.size_bytes,
+                kind: TargetKind::Dir,
+                category: cache.category,
+                estimated: false,
+                at_least: false,
+                last_modified: Local::now(),
+                active: false,
+                ambiguous: false,
+            };
+            let mut result = CleanResult {
+                project_name: cache.name.clone(),
+                targets_cleaned: 0,
+                bytes_freed: 0,
+                errors: Vec::new(),
+                cleaned: Vec::new(),
+                protected: Vec::new(),
+            };
+            let removed = if dry_run {
+                Ok(())
+            } else {
+                remove_read_only_dir_all(&cache.path)
+            };
+            match removed {
+                Ok(()) => {
+                    result.targets_cleaned = 1;
+                    result.bytes_freed = cache.size_bytes;
+                    result.cleaned.push(target);
+                }
+                Err(e) => result.errors.push(CleanError {
+                    project: cache.name.clone(),
+                    path: cache.path.clone(),
+                    message: e.to_string(),
+                }),
+            }
+            result
+        })
+        .collect()
+}
@@ -0,0 +1,69 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+
+use super::CleanResult;
+use crate::scanner::ScannedProject;
+
+/// Progress of a clean, written by `clean --manifest` as each project
+/// finishes and read back by `clean --resume` to skip what is already done.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CleanManifest {
+    /// When the clean was first started.
+    pub started: DateTime<Local>,
+    /// Roots of the projects cleaned without errors, in completion order.
+    pub completed: Vec<PathBuf>,
+}
+
+impl Default for CleanManifest {
+    fn default() -> Self {
+        Self {
+            started: Local::now(),
+            completed: Vec::new(),
+        }
+    }
+}
+
+impl CleanManifest {
+    /// Write the manifest as pretty-printed JSON.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let json = serde_json::to_string_pretty(self)?;
+        fs::write(path, json)
+            .with_context(|| format!("Failed to write manifest {}", path.display()))
+    }
+
+    /// Read a manifest written by [`save`](Self::save).
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read manifest {}", path.display()))?;
+        serde_json::from_str(&contents)
+            .with_context(|| format!("Invalid manifest file {}", path.display()))
+    }
+
+    /// Whether the project rooted at `path` was already cleaned.
+    pub fn is_completed(&self, path: &Path) -> bool {
+        self.completed.iter().any(|p| p == path)
+    }
+
+    /// Remove every already-cleaned project from `projects`. Returns how many
+    /// were removed.
+    pub fn retain_pending(&self, projects: &mut Vec<ScannedProject>) -> usize {
+        let before = projects.len();
+        projects.retain(|p| !self.is_completed(&p.path));
+        before - projects.len()
+    }
+
+    /// Record `project` as done if `result` had no errors; a project that
+    /// failed partway stays pending so a resume retries it. Returns whether
+    /// anything was recorded.
+    pub fn record(&mut self, project: &ScannedProject, result: &CleanResult) -> bool {
+        if !result.errors.is_empty() || self.is_completed(&project.path) {
+            return false;
+        }
+        self.completed.push(project.path.clone());
+        true
+    }
+}
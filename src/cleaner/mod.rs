@@ -1,9 +1,23 @@
-use std::fs;
-use std::path::Path;
+pub mod manifest;
+pub mod plan;
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::fs::{self, File};
+use std::iter;
+use std::path::{Component, Path, PathBuf};
+use std::time::SystemTime;
 
 use anyhow::{Context, Result};
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+
+use crate::scanner::walk::is_ambiguous_build_dir;
+use crate::scanner::{CleanTarget, ProjectKind, ScannedProject, TargetCategory, TargetKind};
+use crate::util::glob_match;
 
-use crate::scanner::ScannedProject;
+pub use manifest::CleanManifest;
+pub use plan::{CleanPlan, PlanCheck};
 
 /// Result of a clean operation on a single project.
 #[derive(Debug)]
@@ -11,38 +25,288 @@ pub struct CleanResult {
     pub project_name: String,
     pub targets_cleaned: usize,
     pub bytes_freed: u64,
-    pub errors: Vec<String>,
+    pub errors: Vec<CleanError>,
+    /// The targets that were removed (or would be, in a dry run).
+    pub cleaned: Vec<CleanTarget>,
+    /// Targets left alone because they match a `protect` pattern.
+    pub protected: Vec<PathBuf>,
+}
+
+/// A target that could not be removed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CleanError {
+    pub project: String,
+    pub path: PathBuf,
+    pub message: String,
+}
+
+impl fmt::Display for CleanError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Failed to remove {}: {}",
+            self.path.display(),
+            self.message
+        )
+    }
+}
+
+/// Machine-readable report of a clean run, as emitted by `clean --json`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CleanReport {
+    pub dry_run: bool,
+    pub projects_cleaned: usize,
+    pub total_bytes_freed: u64,
+    pub errors: Vec<CleanError>,
+    /// Every target that was removed (or would be, in a dry run).
+    pub targets: Vec<TargetReport>,
+    /// Selected targets skipped because they match a `protect` pattern.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub protected: Vec<PathBuf>,
+}
+
+/// One cleaned (or would-be-cleaned) target within a [`CleanReport`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TargetReport {
+    pub project: String,
+    pub path: PathBuf,
+    pub size_bytes: u64,
+    pub category: TargetCategory,
+}
+
+impl CleanReport {
+    /// Build a report from the results of a clean run.
+    pub fn new(results: &[CleanResult], dry_run: bool) -> Self {
+        Self {
+            dry_run,
+            projects_cleaned: results.len(),
+            total_bytes_freed: results.iter().map(|r| r.bytes_freed).sum(),
+            errors: results.iter().flat_map(|r| r.errors.clone()).collect(),
+            targets: results
+                .iter()
+                .flat_map(|r| {
+                    r.cleaned.iter().map(|t| TargetReport {
+                        project: r.project_name.clone(),
+                        path: t.path.clone(),
+                        size_bytes: t.size_bytes,
+                        category: t.category,
+                    })
+                })
+                .collect(),
+            protected: results.iter().flat_map(|r| r.protected.clone()).collect(),
+        }
+    }
+}
+
+/// Options controlling how a clean is carried out.
+#[derive(Debug, Clone, Default)]
+pub struct CleanOptions {
+    /// Only report what *would* be cleaned without deleting anything.
+    pub dry_run: bool,
+    /// After removing a target, also remove ancestor directories it left empty,
+    /// stopping at the project root.
+    pub prune_empty: bool,
+    /// Glob patterns (e.g. `**/release/**`) for paths that must never be
+    /// removed, whatever was selected. See [`is_protected`].
+    pub protect: Vec<String>,
+    /// Delete targets even when they lie inside a `.git` directory
+    /// (`--confirm-delete-git`). See [`is_inside_git_dir`].
+    pub allow_git_dir: bool,
+    /// In a dry run, probe each target with [`check_deletable`] and report
+    /// the ones a real clean couldn't remove (`--check-perms`).
+    pub check_perms: bool,
+}
+
+/// Check, without deleting anything, that `target` could be removed: its
+/// parent directory must accept changes, which is probed by creating and
+/// removing a scratch file there. Read-only entries *inside* a directory are
+/// fine, since [`remove_dir_all`] makes them writable first.
+pub fn check_deletable(target: &Path) -> Result<()> {
+    let parent = target
+        .parent()
+        .with_context(|| format!("No parent directory: {}", target.display()))?;
+    let probe = parent.join(format!(".dev-sweep-probe-{}", std::process::id()));
+    fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&probe)
+        .with_context(|| format!("Cannot delete {}: parent is not writable", target.display()))?;
+    let _ = fs::remove_file(&probe);
+    Ok(())
+}
+
+/// The lock file Cargo holds in each profile directory while it builds.
+const CARGO_LOCK_FILE: &str = ".cargo-lock";
+
+/// Profile directories Cargo writes to, directly under `target/` or under
+/// `target/<triple>/` when cross-compiling.
+const CARGO_PROFILES: &[&str] = &["debug", "release"];
+
+/// Whether `target` looks like it's in use by a running build: the
+/// `.cargo-lock` of one of its profile directories is locked by another
+/// process. Returns the held lock file.
+///
+/// Only Rust `target/` directories are checked, along with the pieces
+/// `--granular` splits them into: `target/<profile>`, `target/<triple>`, and
+/// `target/<triple>/<profile>`. Lock files stay behind after a build
+/// finishes, so only a lock that can't be taken counts.
+pub fn build_lock_held(target: &Path) -> Option<PathBuf> {
+    cargo_profile_dirs(target)
+        .into_iter()
+        .map(|dir| dir.join(CARGO_LOCK_FILE))
+        .find(|lock| File::open(lock).is_ok_and(|f| lock_is_held(&f)))
+}
+
+/// Whether another process holds `file`'s lock. Cargo locks with `flock`, so
+/// probe the same way without blocking; a lock we do get is released when
+/// `file` closes.
+#[cfg(unix)]
+fn lock_is_held(file: &File) -> bool {
+    use std::os::fd::AsRawFd;
+    // SAFETY: `file` keeps the descriptor open for the duration of the call.
+    let taken = unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_EX | libc::LOCK_NB) } == 0;
+    !taken && std::io::Error::last_os_error().raw_os_error() == Some(libc::EWOULDBLOCK)
+}
+
+/// Elsewhere there's no `flock` to probe with, so no lock counts as held.
+#[cfg(not(unix))]
+fn lock_is_held(_file: &File) -> bool {
+    false
+}
+
+/// The Cargo profile directories in or at `target` (see [`build_lock_held`]);
+/// empty if `target` isn't part of a Rust `target/` directory.
+fn cargo_profile_dirs(target: &Path) -> Vec<PathBuf> {
+    let is_target = |path: Option<&Path>| path.is_some_and(|p| p.ends_with("target"));
+    let profiles_in = |dir: &Path| CARGO_PROFILES.iter().map(|p| dir.join(p)).collect::<Vec<_>>();
+
+    let parent = target.parent();
+    if is_target(Some(target)) {
+        let triples = fs::read_dir(target)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .map(|e| e.path())
+            .filter(|p| p.is_dir() && !is_cargo_profile(p));
+        iter::once(target.to_path_buf())
+            .chain(triples)
+            .flat_map(|dir| profiles_in(&dir))
+            .collect()
+    } else if is_target(parent) {
+        if is_cargo_profile(target) {
+            vec![target.to_path_buf()]
+        } else {
+            profiles_in(target)
+        }
+    } else if is_target(parent.and_then(Path::parent)) && is_cargo_profile(target) {
+        vec![target.to_path_buf()]
+    } else {
+        Vec::new()
+    }
+}
+
+/// Whether `dir` is named like one of the [`CARGO_PROFILES`].
+fn is_cargo_profile(dir: &Path) -> bool {
+    CARGO_PROFILES.iter().any(|p| dir.ends_with(p))
+}
+
+/// Whether `path` is a `.git` directory or lies inside one. Nothing dev-sweep
+/// detects belongs there, so such a target means a misconfigured pattern, and
+/// deleting it would corrupt the repository.
+pub fn is_inside_git_dir(path: &Path) -> bool {
+    path.components()
+        .any(|c| matches!(c, Component::Normal(name) if name == ".git"))
+}
+
+/// Whether `path` matches one of the `protect` globs. A pattern ending in
+/// `/**` also covers the directory itself, so `**/release/**` protects
+/// `target/release` as well as everything inside it.
+pub fn is_protected(path: &Path, protect: &[String]) -> bool {
+    let path = path.to_string_lossy().replace('\\', "/");
+    let dir = format!("{path}/");
+    protect
+        .iter()
+        .any(|pattern| glob_match(pattern, &path) || glob_match(pattern, &dir))
 }
 
 /// Clean the specified targets from a project.
 ///
 /// If `dry_run` is true, only reports what *would* be cleaned without deleting anything.
 pub fn clean_project(project: &ScannedProject, dry_run: bool) -> Result<CleanResult> {
+    clean_project_with(
+        project,
+        &CleanOptions {
+            dry_run,
+            ..Default::default()
+        },
+    )
+}
+
+/// Clean the specified targets from a project using the given options.
+pub fn clean_project_with(project: &ScannedProject, opts: &CleanOptions) -> Result<CleanResult> {
     let mut result = CleanResult {
         project_name: project.name.clone(),
         targets_cleaned: 0,
         bytes_freed: 0,
         errors: Vec::new(),
+        cleaned: Vec::new(),
+        protected: Vec::new(),
     };
 
     for target in &project.clean_targets {
-        if dry_run {
+        if is_protected(&target.path, &opts.protect) {
+            result.protected.push(target.path.clone());
+            continue;
+        }
+
+        if is_inside_git_dir(&target.path) && !opts.allow_git_dir {
+            result.errors.push(CleanError {
+                project: project.name.clone(),
+                path: target.path.clone(),
+                message: "refusing to delete inside a .git directory \
+                          (pass --confirm-delete-git to override)"
+                    .to_string(),
+            });
+            continue;
+        }
+
+        if opts.dry_run {
+            if opts.check_perms
+                && let Err(e) = check_deletable(&target.path)
+            {
+                result.errors.push(CleanError {
+                    project: project.name.clone(),
+                    path: target.path.clone(),
+                    message: format!("{e:#}"),
+                });
+                continue;
+            }
             result.targets_cleaned += 1;
             result.bytes_freed += target.size_bytes;
+            result.cleaned.push(target.clone());
             continue;
         }
 
-        match remove_dir_all(&target.path) {
+        let removed = match target.kind {
+            TargetKind::Dir => remove_dir_all(&target.path),
+            TargetKind::File => remove_file(&target.path),
+        };
+
+        match removed {
             Ok(()) => {
                 result.targets_cleaned += 1;
                 result.bytes_freed += target.size_bytes;
+                result.cleaned.push(target.clone());
+                if opts.prune_empty {
+                    prune_empty_ancestors(&target.path, &project.path);
+                }
             }
             Err(e) => {
-                result.errors.push(format!(
-                    "Failed to remove {}: {}",
-                    target.path.display(),
-                    e
-                ));
+                result.errors.push(CleanError {
+                    project: project.name.clone(),
+                    path: target.path.clone(),
+                    message: e.to_string(),
+                });
             }
         }
     }
@@ -50,29 +314,350 @@ pub fn clean_project(project: &ScannedProject, dry_run: bool) -> Result<CleanRes
     Ok(result)
 }
 
+/// Whether `path` is a filesystem root (`/`, `C:\`) or exactly the home directory.
+///
+/// Cleaning from one of these reaches across every project on the machine, so
+/// it needs an explicit override.
+pub fn is_sweeping_root(path: &Path) -> bool {
+    let path = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    if path.parent().is_none() {
+        return true;
+    }
+    dirs::home_dir()
+        .and_then(|home| fs::canonicalize(home).ok())
+        .is_some_and(|home| home == path)
+}
+
+/// Refuse to clean from a filesystem root or the home directory unless
+/// `allow` is set.
+pub fn ensure_safe_clean_roots(roots: &[PathBuf], allow: bool) -> Result<()> {
+    if allow {
+        return Ok(());
+    }
+    if let Some(root) = roots.iter().find(|r| is_sweeping_root(r)) {
+        anyhow::bail!(
+            "Refusing to clean from {}: it is a filesystem root or your home directory. \
+             Pass --i-know-what-im-doing to proceed anyway.",
+            root.display()
+        );
+    }
+    Ok(())
+}
+
+/// Drop the newest target of each group of glob siblings from `project`, so
+/// that only the older ones get cleaned.
+///
+/// Siblings are targets matched by the same glob pattern, e.g.
+/// `cmake-build-debug` and `cmake-build-release` for `cmake-build-*`. Targets
+/// from exact (non-glob) patterns are unaffected.
+pub fn drop_latest_siblings(project: &mut ScannedProject) {
+    let kept = latest_glob_siblings(project);
+    project.clean_targets.retain(|t| !kept.contains(&t.path));
+    project.total_cleanable_bytes = project.clean_targets.iter().map(|t| t.size_bytes).sum();
+}
+
+/// The single largest target across `projects`, so a bulk clean can be
+/// sanity-checked before confirming. Ties go to the first target seen.
+pub fn largest_target<'a>(
+    projects: impl IntoIterator<Item = &'a ScannedProject>,
+) -> Option<&'a CleanTarget> {
+    projects
+        .into_iter()
+        .flat_map(|p| &p.clean_targets)
+        .reduce(|best, t| if t.size_bytes > best.size_bytes { t } else { best })
+}
+
+/// Remove every project modified after `cutoff`, so no selection (not even
+/// `--all`) can clean it. Returns how many projects were removed.
+pub fn drop_protected(projects: &mut Vec<ScannedProject>, cutoff: DateTime<Local>) -> usize {
+    let before = projects.len();
+    projects.retain(|p| p.last_modified <= cutoff);
+    before - projects.len()
+}
+
+/// Remove every project whose artifacts are smaller than its source
+/// (`--skip-smaller-than-source`), so it isn't cleaned. Returns how many
+/// projects were removed.
+pub fn drop_smaller_than_source(projects: &mut Vec<ScannedProject>) -> usize {
+    let before = projects.len();
+    projects.retain(|p| !p.artifacts_smaller_than_source());
+    before - projects.len()
+}
+
+/// Remove every active virtualenv (see [`CleanTarget::active`]) from
+/// `projects`, dropping projects left with nothing to clean. Returns the
+/// paths that were held back.
+pub fn drop_active_venvs(projects: &mut Vec<ScannedProject>) -> Vec<PathBuf> {
+    drop_targets_where(projects, |t| t.active)
+}
+
+/// Every target of a Rust project in `projects` a running build appears to be
+/// using (see [`build_lock_held`]), with the held lock file.
+pub fn targets_in_use(projects: &[ScannedProject]) -> Vec<(PathBuf, PathBuf)> {
+    projects
+        .iter()
+        .filter(|p| p.kind == ProjectKind::Rust)
+        .flat_map(|p| &p.clean_targets)
+        .filter_map(|t| build_lock_held(&t.path).map(|lock| (t.path.clone(), lock)))
+        .collect()
+}
+
+/// Remove the given targets from `projects` (`clean --safe` uses this for
+/// [`targets_in_use`]), like [`drop_active_venvs`].
+pub fn drop_targets(projects: &mut Vec<ScannedProject>, paths: &[PathBuf]) -> Vec<PathBuf> {
+    drop_targets_where(projects, |t| paths.contains(&t.path))
+}
+
+/// Flag every `build/` in `projects` that may be committed source (see
+/// [`CleanTarget::ambiguous`]). This asks git about each one, so it's left to
+/// the commands that delete rather than done on every scan.
+pub fn mark_ambiguous_builds(projects: &mut [ScannedProject]) {
+    for target in projects.iter_mut().flat_map(|p| &mut p.clean_targets) {
+        target.ambiguous = target.name == "build" && is_ambiguous_build_dir(&target.path);
+    }
+}
+
+/// Remove every ambiguous `build/` (see [`CleanTarget::ambiguous`]) from
+/// `projects`, like [`drop_active_venvs`].
+pub fn drop_ambiguous_builds(projects: &mut Vec<ScannedProject>) -> Vec<PathBuf> {
+    drop_targets_where(projects, |t| t.ambiguous)
+}
+
+fn drop_targets_where(
+    projects: &mut Vec<ScannedProject>,
+    held: impl Fn(&CleanTarget) -> bool,
+) -> Vec<PathBuf> {
+    let mut skipped = Vec::new();
+    for project in projects.iter_mut() {
+        if !project.clean_targets.iter().any(&held) {
+            continue;
+        }
+        project.clean_targets.retain(|t| {
+            if held(t) {
+                skipped.push(t.path.clone());
+            }
+            !held(t)
+        });
+        project.total_cleanable_bytes = project.clean_targets.iter().map(|t| t.size_bytes).sum();
+    }
+    projects.retain(|p| !p.clean_targets.is_empty());
+    skipped
+}
+
+/// Keep only the targets of `project` in one of `categories`.
+pub fn retain_categories(project: &mut ScannedProject, categories: &[TargetCategory]) {
+    project
+        .clean_targets
+        .retain(|t| categories.contains(&t.category));
+    project.total_cleanable_bytes = project.clean_targets.iter().map(|t| t.size_bytes).sum();
+}
+
+/// Keep only the targets of `project` whose name matches `pattern`
+/// (e.g. `dist` or `cmake-build-*`), whichever kind produced them.
+pub fn retain_targets_matching(project: &mut ScannedProject, pattern: &str) {
+    project
+        .clean_targets
+        .retain(|t| glob_match(pattern, &t.name));
+    project.total_cleanable_bytes = project.clean_targets.iter().map(|t| t.size_bytes).sum();
+}
+
+/// Find the newest target for each glob pattern that matched more than one target.
+fn latest_glob_siblings(project: &ScannedProject) -> HashSet<PathBuf> {
+    let kind = project.kind;
+    let mut kept = HashSet::new();
+
+    for pattern in kind.cleanable_dirs().iter().chain(kind.cleanable_files()) {
+        if !pattern.contains('*') {
+            continue;
+        }
+
+        let siblings: Vec<&CleanTarget> = project
+            .clean_targets
+            .iter()
+            .filter(|t| glob_match(pattern, &t.name))
+            .collect();
+        if siblings.len() < 2 {
+            continue;
+        }
+
+        let newest = siblings.into_iter().max_by_key(|t| {
+            fs::symlink_metadata(&t.path)
+                .and_then(|m| m.modified())
+                .unwrap_or(SystemTime::UNIX_EPOCH)
+        });
+        if let Some(newest) = newest {
+            kept.insert(newest.path.clone());
+        }
+    }
+
+    kept
+}
+
+/// Remove directories between `removed` and `project_root` that are now empty.
+///
+/// Walks up from the parent of `removed`, stopping at the first directory that
+/// is non-empty (or can't be removed) and never touching `project_root` itself.
+fn prune_empty_ancestors(removed: &Path, project_root: &Path) {
+    let mut dir = removed.parent();
+    while let Some(current) = dir {
+        if current == project_root || !current.starts_with(project_root) {
+            break;
+        }
+        // `remove_dir` refuses to delete non-empty directories.
+        if fs::remove_dir(current).is_err() {
+            break;
+        }
+        dir = current.parent();
+    }
+}
+
 /// Remove a directory and all its contents.
 ///
 /// This is a wrapper around `fs::remove_dir_all` with better error context.
-fn remove_dir_all(path: &Path) -> Result<()> {
+pub fn remove_dir_all(path: &Path) -> Result<()> {
     fs::remove_dir_all(path)
         .with_context(|| format!("Failed to remove directory: {}", path.display()))?;
     Ok(())
 }
 
+/// Like [`remove_dir_all`], but for trees a tool deliberately leaves
+/// read-only (like Go's module cache): if removal is refused, everything
+/// below `path` is made writable by its owner and removal is retried.
+pub fn remove_read_only_dir_all(path: &Path) -> Result<()> {
+    match fs::remove_dir_all(path) {
+        Err(e) if e.kind() == std::io::ErrorKind::PermissionDenied => {
+            make_owner_writable(path);
+            fs::remove_dir_all(path)
+        }
+        other => other,
+    }
+    .with_context(|| format!("Failed to remove directory: {}", path.display()))?;
+    Ok(())
+}
+
+/// Give the owner write permission on `path` and everything below it, as far
+/// as permissions allow, leaving group and other bits alone. Directories are
+/// fixed before their contents are read.
+pub fn make_owner_writable(path: &Path) {
+    for entry in walkdir::WalkDir::new(path).follow_links(false) {
+        let Ok(entry) = entry else { continue };
+        if entry.path_is_symlink() {
+            continue;
+        }
+        if let Ok(meta) = entry.metadata() {
+            let mut perms = meta.permissions();
+            if perms.readonly() {
+                add_owner_write(&mut perms);
+                let _ = fs::set_permissions(entry.path(), perms);
+            }
+        }
+    }
+}
+
+#[cfg(unix)]
+fn add_owner_write(perms: &mut fs::Permissions) {
+    use std::os::unix::fs::PermissionsExt;
+    perms.set_mode(perms.mode() | 0o200);
+}
+
+/// Elsewhere there is only the one read-only flag.
+#[cfg(not(unix))]
+fn add_owner_write(perms: &mut fs::Permissions) {
+    #[allow(clippy::permissions_set_readonly_false)]
+    perms.set_readonly(false);
+}
+
+/// Remove a single file target.
+fn remove_file(path: &Path) -> Result<()> {
+    fs::remove_file(path)
+        .with_context(|| format!("Failed to remove file: {}", path.display()))?;
+    Ok(())
+}
+
 /// Clean multiple projects and return results.
 pub fn clean_projects(
     projects: &[&ScannedProject],
     dry_run: bool,
 ) -> Vec<CleanResult> {
+    clean_projects_with(
+        projects,
+        &CleanOptions {
+            dry_run,
+            ..Default::default()
+        },
+    )
+}
+
+/// Clean multiple projects using the given options and return results.
+pub fn clean_projects_with(projects: &[&ScannedProject], opts: &CleanOptions) -> Vec<CleanResult> {
+    clean_projects_each(projects, opts, &|_, _| {})
+}
+
+/// Like [`clean_projects_with`], but also hands each project and its result
+/// to `on_result` as soon as that project is done, e.g. to drive a progress
+/// bar or record progress in a [`CleanManifest`].
+///
+/// A target shared by several projects (e.g. one `target/` reached through a
+/// symlink) is cleaned, and its bytes credited, only once. Targets are
+/// compared canonicalized, and the route that isn't through a symlink is
+/// the one cleaned, so the directory itself is removed rather than a link.
+pub fn clean_projects_each(
+    projects: &[&ScannedProject],
+    opts: &CleanOptions,
+    on_result: &(dyn Fn(&ScannedProject, &CleanResult) + Sync),
+) -> Vec<CleanResult> {
+    // Resolve every target before anything is deleted, while links still
+    // lead somewhere.
+    let mut owner: HashMap<PathBuf, (usize, usize)> = HashMap::new();
+    for (i, p) in projects.iter().enumerate() {
+        for (j, t) in p.clean_targets.iter().enumerate() {
+            let key = fs::canonicalize(&t.path).unwrap_or_else(|_| t.path.clone());
+            // Keep the first route, unless it's a link and this one isn't
+            match owner.get(&key) {
+                Some(&(fi, fj)) if t.path != key || projects[fi].clean_targets[fj].path == key => {}
+                _ => {
+                    owner.insert(key, (i, j));
+                }
+            }
+        }
+    }
+    let owned: HashSet<(usize, usize)> = owner.into_values().collect();
+
     projects
         .iter()
-        .map(|p| {
-            clean_project(p, dry_run).unwrap_or_else(|e| CleanResult {
+        .enumerate()
+        .map(|(i, p)| {
+            let unseen: Vec<CleanTarget> = p
+                .clean_targets
+                .iter()
+                .enumerate()
+                .filter(|(j, _)| owned.contains(&(i, *j)))
+                .map(|(_, t)| t.clone())
+                .collect();
+            let deduped;
+            let project = if unseen.len() == p.clean_targets.len() {
+                *p
+            } else {
+                deduped = ScannedProject {
+                    clean_targets: unseen,
+                    ..(*p).clone()
+                };
+                &deduped
+            };
+            let result = clean_project_with(project, opts).unwrap_or_else(|e| CleanResult {
                 project_name: p.name.clone(),
                 targets_cleaned: 0,
                 bytes_freed: 0,
-                errors: vec![e.to_string()],
-            })
+                errors: vec![CleanError {
+                    project: p.name.clone(),
+                    path: p.path.clone(),
+                    message: e.to_string(),
+                }],
+                cleaned: Vec::new(),
+                protected: Vec::new(),
+            });
+            on_result(p, &result);
+            result
         })
         .collect()
 }
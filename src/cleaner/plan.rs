@@ -0,0 +1,120 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+
+use crate::config::DevSweepConfig;
+use crate::scanner::ScannedProject;
+use crate::scanner::walk::analyze_path;
+
+/// A recorded clean, written by `clean --plan` and executed by `clean --apply`.
+///
+/// Holds the selected projects with exactly the targets that were chosen, so
+/// the plan can be reviewed (and archived) before anything is deleted.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CleanPlan {
+    /// When the plan was written.
+    pub created: DateTime<Local>,
+    pub projects: Vec<ScannedProject>,
+}
+
+/// The part of a [`CleanPlan`] that is still safe to apply.
+#[derive(Debug, Default)]
+pub struct PlanCheck {
+    /// Planned projects, narrowed to targets that are still there, with
+    /// freshly measured sizes.
+    pub projects: Vec<ScannedProject>,
+    /// Planned paths that won't be cleaned, with the reason.
+    pub skipped: Vec<(PathBuf, String)>,
+    /// Targets whose size changed since planning: `(path, planned, now)`.
+    pub resized: Vec<(PathBuf, u64, u64)>,
+}
+
+impl CleanPlan {
+    /// Record `projects` (and their current targets) as a plan.
+    pub fn new(projects: &[&ScannedProject]) -> Self {
+        Self {
+            created: Local::now(),
+            projects: projects.iter().map(|&p| p.clone()).collect(),
+        }
+    }
+
+    /// Total bytes the plan would free, as measured when it was written.
+    pub fn total_bytes(&self) -> u64 {
+        self.projects.iter().map(|p| p.total_cleanable_bytes).sum()
+    }
+
+    /// Write the plan as pretty-printed JSON.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let json = serde_json::to_string_pretty(self)?;
+        fs::write(path, json).with_context(|| format!("Failed to write plan {}", path.display()))
+    }
+
+    /// Read a plan written by [`save`](Self::save).
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read plan {}", path.display()))?;
+        serde_json::from_str(&contents)
+            .with_context(|| format!("Invalid plan file {}", path.display()))
+    }
+
+    /// Re-analyze every planned project and keep only the planned targets it
+    /// still offers for cleaning.
+    ///
+    /// Anything that disappeared, or that is no longer a clean target of its
+    /// project (including paths edited into the plan by hand), is skipped
+    /// rather than deleted.
+    pub fn validate(&self, config: &DevSweepConfig) -> PlanCheck {
+        let mut check = PlanCheck::default();
+
+        for planned in &self.projects {
+            let mut current = match analyze_path(&planned.path, config) {
+                Ok(project) => project,
+                Err(e) => {
+                    check.skipped.push((planned.path.clone(), e.to_string()));
+                    continue;
+                }
+            };
+
+            let wanted: HashSet<&Path> = planned
+                .clean_targets
+                .iter()
+                .map(|t| t.path.as_path())
+                .collect();
+            current
+                .clean_targets
+                .retain(|t| wanted.contains(t.path.as_path()));
+
+            for target in &planned.clean_targets {
+                match current.clean_targets.iter().find(|t| t.path == target.path) {
+                    None if !target.path.exists() => check
+                        .skipped
+                        .push((target.path.clone(), "no longer exists".to_string())),
+                    None => check.skipped.push((
+                        target.path.clone(),
+                        "no longer a clean target of its project".to_string(),
+                    )),
+                    Some(now) if now.size_bytes != target.size_bytes => {
+                        check.resized.push((
+                            target.path.clone(),
+                            target.size_bytes,
+                            now.size_bytes,
+                        ));
+                    }
+                    Some(_) => {}
+                }
+            }
+
+            current.total_cleanable_bytes =
+                current.clean_targets.iter().map(|t| t.size_bytes).sum();
+            if !current.clean_targets.is_empty() {
+                check.projects.push(current);
+            }
+        }
+
+        check
+    }
+}
@@ -1,6 +1,14 @@
+use std::ffi::OsString;
+use std::fs;
 use std::path::PathBuf;
 
-use clap::{Parser, Subcommand};
+use anyhow::{Context, Result};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+
+use crate::config::{CacheMode, DefaultCommand};
+use crate::summary::GroupBy;
+use crate::tui::display::Column;
+use crate::util::parse_size;
 
 /// CLI argument definitions for dev-sweep.
 #[derive(Parser)]
@@ -18,21 +26,171 @@ pub struct Cli {
     #[command(subcommand)]
     pub command: Option<Commands>,
 
-    /// Directory to scan (defaults to current directory)
+    /// Directory to scan (defaults to the configured default roots, then the current directory)
     #[arg(global = true)]
     pub path: Option<PathBuf>,
 
-    /// Maximum directory depth to scan
+    /// Maximum directory depth to scan, counted from the scan root (0 = root only)
     #[arg(short = 'd', long, global = true)]
     pub max_depth: Option<usize>,
 
-    /// Only show projects older than this (e.g. "30d", "3m", "1y")
+    /// Maximum depth below a found project to search for nested projects
+    #[arg(long, global = true)]
+    pub project_depth: Option<usize>,
+
+    /// Don't report individual targets smaller than this many bytes (larger ones are sized only up to it)
+    #[arg(long, value_name = "BYTES", global = true)]
+    pub target_min_size: Option<u64>,
+
+    /// List at most N targets per project (the largest); the rest are summarized but still cleaned
+    #[arg(long, value_name = "N", global = true)]
+    pub max_targets: Option<usize>,
+
+    /// Only show projects older than this (e.g. "30d", "3m", "1y") or last modified before a date (e.g. "2024-01-01")
     #[arg(short, long, global = true)]
     pub older_than: Option<String>,
 
+    /// Only show projects modified since the last successful `clean` (everything if there wasn't one)
+    #[arg(long, global = true)]
+    pub since_last_clean: bool,
+
+    /// Only include projects whose name or path contains TEXT (case-insensitive; globs allowed)
+    #[arg(long, value_name = "TEXT", global = true)]
+    pub name: Option<String>,
+
     /// Output results as JSON
     #[arg(long, global = true)]
     pub json: bool,
+
+    /// Print JSON (--json, --format treemap) on one line instead of pretty-printed
+    #[arg(long, global = true)]
+    pub compact: bool,
+
+    /// Show sizes as exact byte counts (e.g. "1,073,741,824 B") instead of rounded units
+    #[arg(long, global = true)]
+    pub exact_bytes: bool,
+
+    /// Decimal places in human-readable sizes (e.g. 2 for "9.14 GB")
+    #[arg(long, value_name = "N", global = true, default_value_t = 1)]
+    pub precision: usize,
+
+    /// Show what restoring each target costs (network restore, local rebuild, …) in the table and JSON
+    #[arg(long, global = true)]
+    pub estimate_restore_cost: bool,
+
+    /// Give up on a prompt after SECS without input, answering no
+    #[arg(long, value_name = "SECS", global = true)]
+    pub prompt_timeout: Option<u64>,
+
+    /// Print only the number of projects with something to clean (scan and summary)
+    #[arg(long, global = true, conflicts_with_all = ["json", "print0"])]
+    pub count_only: bool,
+
+    /// List clean target paths NUL-terminated, for `xargs -0` (implies --format du, without sizes)
+    #[arg(long, short = '0', visible_alias = "null", global = true)]
+    pub print0: bool,
+
+    /// How to render scan results (ignored with --json)
+    #[arg(long, value_enum, global = true, default_value_t = OutputFormat::Table)]
+    pub format: OutputFormat,
+
+    /// Table columns to show, comma-separated and in order (default: all of them)
+    #[arg(long, value_enum, value_name = "LIST", value_delimiter = ',', global = true)]
+    pub columns: Vec<Column>,
+
+    /// Exit non-zero if reclaimable space exceeds SIZE (e.g. "500MB"), for CI (scan and summary)
+    #[arg(long, value_name = "SIZE", value_parser = parse_size, global = true)]
+    pub fail_on_found: Option<u64>,
+
+    /// Write one JSON file per project into DIR instead of printing results (scan only)
+    #[arg(long, value_name = "DIR", global = true)]
+    pub split_output: Option<PathBuf>,
+
+    /// Read project paths from FILE (one per line, `-` for stdin) instead of scanning
+    #[arg(long, value_name = "FILE", global = true)]
+    pub from: Option<PathBuf>,
+
+    /// Never clean paths matching GLOB (e.g. "**/release/**"); repeatable
+    #[arg(long, value_name = "GLOB", global = true)]
+    pub protect: Vec<String>,
+
+    /// Allow cleaning from a filesystem root or the home directory
+    #[arg(long = "i-know-what-im-doing", global = true)]
+    pub allow_sweeping_root: bool,
+
+    /// Report each git repository as one project, folding in nested projects
+    #[arg(long, global = true)]
+    pub ignore_vcs_roots: bool,
+
+    /// Don't cross into other filesystems (e.g. network mounts) while scanning
+    #[arg(long, global = true)]
+    pub one_file_system: bool,
+
+    /// Collect git remote, branch, and dirty state for each project
+    #[arg(long, global = true)]
+    pub git: bool,
+
+    /// List Rust target/ profiles (debug, release, …) as separate targets
+    #[arg(long, global = true)]
+    pub granular: bool,
+
+    /// Estimate sizes by sampling instead of walking every file (shown as "~")
+    #[arg(long, global = true)]
+    pub fast: bool,
+
+    /// Reuse target sizes from earlier scans, checked by directory mtime or content signature
+    #[arg(long, global = true, value_name = "MODE")]
+    pub cache_mode: Option<CacheMode>,
+
+    /// Also report a top-level .cache/ in every detected project, whatever its kind
+    #[arg(long, global = true)]
+    pub generic_caches: bool,
+
+    /// Also report .idea/ and .vs/ IDE state in every detected project (cleaning resets local IDE settings)
+    #[arg(long, global = true)]
+    pub ide_caches: bool,
+
+    /// Don't clean projects whose artifacts are smaller than their source (likely a partial build)
+    #[arg(long, global = true)]
+    pub skip_smaller_than_source: bool,
+
+    /// Don't draw the scanning spinner on stderr
+    #[arg(long, global = true)]
+    pub no_spinner: bool,
+
+    /// Report scan progress on stderr as newline-delimited JSON events instead of the spinner
+    #[arg(long, global = true)]
+    pub progress_json: bool,
+
+    /// Debug detection: descend into every directory except .git (slow)
+    #[arg(long, global = true, hide = true)]
+    pub no_skip_dirs: bool,
+
+    /// Also print how many projects were found at each depth below the scan root
+    #[arg(long, global = true)]
+    pub depth_report: bool,
+
+    /// Print each project as soon as it's sized instead of one table at the end
+    #[arg(long, global = true)]
+    pub stream: bool,
+
+    /// List directories the scan couldn't read (their sizes are missing from the totals)
+    #[arg(long, global = true)]
+    pub report_unreadable: bool,
+}
+
+/// Rendering for the scan results table.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// Box-drawn terminal table.
+    #[default]
+    Table,
+    /// GitHub-flavored Markdown table, for pasting into issues and PRs.
+    Markdown,
+    /// One `size<TAB>path` line per clean target, for `sort -h` and friends.
+    Du,
+    /// Nested kind → project → target JSON, ready for a d3 treemap.
+    Treemap,
 }
 
 #[derive(Subcommand)]
@@ -40,16 +198,24 @@ pub enum Commands {
     /// Scan for projects and show what can be cleaned (default)
     Scan,
     /// Interactively select and clean projects
-    Clean {
-        /// Clean all found projects without prompting
-        #[arg(short, long)]
-        all: bool,
-        /// Show what would be cleaned without actually deleting
-        #[arg(long)]
-        dry_run: bool,
-    },
+    Clean(CleanArgs),
+    /// Clean build outputs in every found project, leaving dependency caches alone
+    Purge(PurgeArgs),
     /// Show a quick summary of reclaimable space
-    Summary,
+    Summary(SummaryArgs),
+    /// Re-scan periodically and warn when reclaimable space exceeds a threshold
+    Watch {
+        /// Seconds to wait between scans
+        #[arg(long, default_value_t = 300, value_parser = clap::value_parser!(u64).range(1..))]
+        interval: u64,
+        /// Warn once reclaimable space exceeds SIZE (e.g. "20GB")
+        #[arg(long, value_name = "SIZE", value_parser = parse_size)]
+        threshold: u64,
+    },
+    /// List every supported project type, its marker files, and what gets cleaned
+    Kinds,
+    /// Show machine-wide caches (Go build/module caches, a global CARGO_TARGET_DIR) and optionally clean them
+    Caches(CachesArgs),
     /// Manage dev-sweep configuration
     Config {
         /// Show the current config
@@ -60,3 +226,153 @@ pub enum Commands {
         reset: bool,
     },
 }
+
+impl From<DefaultCommand> for Commands {
+    /// The command to run when none is given, with its options at their defaults.
+    fn from(default: DefaultCommand) -> Self {
+        match default {
+            DefaultCommand::Scan => Self::Scan,
+            DefaultCommand::Summary => Self::Summary(SummaryArgs::default()),
+            DefaultCommand::Clean => Self::Clean(CleanArgs::default()),
+        }
+    }
+}
+
+/// Options for the `clean` subcommand.
+#[derive(Args, Debug, Clone, Default)]
+pub struct CleanArgs {
+    /// Clean all found projects without prompting
+    #[arg(short, long)]
+    pub all: bool,
+    /// Show what would be cleaned without actually deleting
+    #[arg(long)]
+    pub dry_run: bool,
+    /// With --dry-run, check that each target could actually be deleted and report those that can't
+    #[arg(long, requires = "dry_run")]
+    pub check_perms: bool,
+    /// Clean PATH as a single project, without scanning or prompting
+    #[arg(short, long, visible_alias = "yes")]
+    pub force: bool,
+    /// Remove directories left empty by cleaning, up to the project root
+    #[arg(long)]
+    pub prune_empty: bool,
+    /// Keep the newest of several same-pattern targets (e.g. cmake-build-*)
+    #[arg(long)]
+    pub keep_latest: bool,
+    /// With --all, require typing "clean" instead of y/N to confirm
+    #[arg(long)]
+    pub confirm_word: bool,
+    /// After each clean, rescan and offer to keep going until nothing is left
+    #[arg(long = "loop")]
+    pub repeat: bool,
+    /// Answer every prompt with BOOL instead of asking on stdin (true selects every project)
+    #[arg(long, value_name = "BOOL")]
+    pub confirm: Option<bool>,
+    /// Never clean projects modified within this age (e.g. "12h", "7d"), even with --all
+    #[arg(long, value_name = "AGE")]
+    pub protect_newer_than: Option<String>,
+    /// Only clean targets whose name matches GLOB (e.g. "dist"), in every project
+    #[arg(long, value_name = "GLOB")]
+    pub targets_matching: Option<String>,
+    /// Write the selected projects and targets to FILE as JSON instead of deleting
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["apply", "force"])]
+    pub plan: Option<PathBuf>,
+    /// Clean exactly what a --plan FILE lists, after checking it still applies
+    #[arg(long, value_name = "FILE", conflicts_with = "force")]
+    pub apply: Option<PathBuf>,
+    /// Record each project in FILE as it finishes cleaning, so an interrupted clean can be resumed
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["plan", "force"])]
+    pub manifest: Option<PathBuf>,
+    /// Skip projects a --manifest FILE lists as cleaned, and keep recording to it
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["manifest", "plan", "force"])]
+    pub resume: Option<PathBuf>,
+    /// Allow deleting targets inside a .git directory (this can corrupt the repository)
+    #[arg(long)]
+    pub confirm_delete_git: bool,
+
+    /// Skip targets a running build appears to be using (a held .cargo-lock) or that may be committed source, instead of just warning
+    #[arg(long)]
+    pub safe: bool,
+}
+
+/// Options for the `purge` subcommand.
+#[derive(Args, Debug, Clone, Default)]
+pub struct PurgeArgs {
+    /// Show what would be purged without actually deleting
+    #[arg(long)]
+    pub dry_run: bool,
+    /// Never purge projects modified within this age (e.g. "12h", "7d")
+    #[arg(long, value_name = "AGE")]
+    pub protect_newer_than: Option<String>,
+    /// Skip targets a running build appears to be using (a held .cargo-lock) or that may be committed source, instead of just warning
+    #[arg(long)]
+    pub safe: bool,
+}
+
+/// Options for the `caches` subcommand.
+#[derive(Args, Debug, Clone, Default)]
+pub struct CachesArgs {
+    /// Remove the caches found (after confirming)
+    #[arg(long)]
+    pub clean: bool,
+    /// With --clean, show what would be freed without deleting
+    #[arg(long, requires = "clean")]
+    pub dry_run: bool,
+    /// Answer the confirmation prompt with BOOL instead of asking on stdin
+    #[arg(long, value_name = "BOOL")]
+    pub confirm: Option<bool>,
+}
+
+/// Options for the `summary` subcommand.
+#[derive(Args, Debug, Clone)]
+pub struct SummaryArgs {
+    /// How to group subtotals: kind, root, or prefix=<n> (nth path component under the root)
+    #[arg(long, value_name = "GROUPING", default_value_t = GroupBy::Kind)]
+    pub group_by: GroupBy,
+    /// Projects untouched for longer than this count as stale (e.g. "30d", "3m")
+    #[arg(long, default_value = "30d")]
+    pub stale_after: String,
+    /// Also append a timestamped JSON line with the totals to FILE (for charting over time)
+    #[arg(long, value_name = "FILE")]
+    pub append_log: Option<PathBuf>,
+}
+
+impl Default for SummaryArgs {
+    /// Matches the clap defaults above.
+    fn default() -> Self {
+        Self {
+            group_by: GroupBy::Kind,
+            stale_after: "30d".to_string(),
+            append_log: None,
+        }
+    }
+}
+
+/// Replace every `@FILE` argument with the lines of FILE, one argument per
+/// line, so long lists (e.g. many `--protect` patterns) don't hit the OS
+/// argument-length limit. Line endings are stripped and blank lines skipped;
+/// nothing else is trimmed or unquoted. Files aren't expanded recursively,
+/// and a lone `@` is passed through.
+pub fn expand_arg_files(args: impl IntoIterator<Item = OsString>) -> Result<Vec<OsString>> {
+    let mut expanded = Vec::new();
+    for arg in args {
+        let Some(path) = arg.to_str().and_then(|a| a.strip_prefix('@')) else {
+            expanded.push(arg);
+            continue;
+        };
+        if path.is_empty() {
+            expanded.push(arg);
+            continue;
+        }
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read argument file {path}"))?;
+        expanded.extend(
+            contents
+                .lines()
+                .map(|line| line.strip_suffix('\r').unwrap_or(line))
+                .filter(|line| !line.is_empty())
+                .map(OsString::from),
+        );
+    }
+    Ok(expanded)
+}
@@ -1,72 +1,302 @@
-use std::collections::HashMap;
-use std::path::Path;
+use std::fs::File;
+use std::io::{self, BufReader, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
 
-use anyhow::Result;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local};
 
-use crate::cleaner::clean_projects;
-use crate::config::DevSweepConfig;
-use crate::scanner::{ScannedProject, scan_directory};
-use crate::tui::colors::{blue, cyan, dim, green, red_bold, yellow_bold};
-use crate::tui::display::{confirm, multi_select, print_clean_summary, print_results_table};
-use crate::util::{format_bytes, parse_age};
+use super::args::{CachesArgs, CleanArgs, OutputFormat, PurgeArgs, SummaryArgs};
+use crate::caches::{cargo_target_cache, clean_caches, global_caches};
+use crate::cleaner::{
+    CleanManifest, CleanOptions, CleanPlan, CleanReport, CleanResult, clean_project_with,
+    clean_projects_each, drop_active_venvs, drop_ambiguous_builds, drop_latest_siblings,
+    drop_protected, drop_smaller_than_source, ensure_safe_clean_roots, is_sweeping_root,
+    drop_targets, largest_target, mark_ambiguous_builds, retain_categories, retain_targets_matching,
+    targets_in_use,
+};
+use crate::config::{CacheMode, DevSweepConfig};
+use crate::disk::{DiskUsage, implausible_total_warning, shared_disk_usage};
+use crate::export::write_split_output;
+use crate::scanner::cache::save_size_cache;
+use crate::scanner::walk::{analyze_path, analyze_paths, scan_directories_each};
+use crate::scanner::{ProjectKind, ScannedProject, TargetCategory};
+use crate::stamp::write_stamp;
+use crate::summary::{
+    GroupBy, GroupTotal, SummarySnapshot, depth_histogram, group_totals, size_stats, stale_split,
+    target_totals, threshold_crossed,
+};
+use crate::tui::colors::{blue, cyan, dim, green, red_bold, yellow, yellow_bold};
+use crate::tui::confirm::Confirmer;
+use crate::tui::display::{
+    Column, DisplayOptions, clear_clean_progress, print_clean_progress,
+    print_clean_summary, print_depth_report, print_global_caches, print_kinds,
+    print_results_table_with, print_unreadable, render_du_lines, render_json, render_kinds_json,
+    render_markdown_table, render_print0, render_stream_line, render_treemap, scan_json,
+};
+use crate::util::{parse_age, parse_cutoff, read_path_list};
+
+/// Settings shared by every scanning command, resolved from CLI flags and config.
+pub struct ScanContext<'a> {
+    /// Directories to scan (the CLI path, or every configured default root).
+    pub roots: &'a [PathBuf],
+    pub max_depth: Option<usize>,
+    pub older_than: Option<&'a str>,
+    /// Only projects whose name or path matches this (`--name`).
+    pub name: Option<&'a str>,
+    /// Only projects modified after this, i.e. the last clean (`--since-last-clean`).
+    pub modified_since: Option<DateTime<Local>>,
+    pub json: bool,
+    /// Print JSON output on one line instead of pretty-printed (`--compact`).
+    pub compact: bool,
+    pub format: OutputFormat,
+    /// Which table columns to show, in order (`--columns`).
+    pub columns: &'a [Column],
+    /// How sizes and targets are shown (`--exact-bytes`, `--precision`,
+    /// `--estimate-restore-cost`).
+    pub display: DisplayOptions,
+    /// List clean target paths NUL-terminated instead of any other output (`--print0`).
+    pub print0: bool,
+    /// Print just the number of projects found, and nothing else (`--count-only`).
+    pub count_only: bool,
+    /// Write one JSON file per project into this directory (`--split-output`).
+    pub split_output: Option<&'a Path>,
+    /// A file (or `-` for stdin) listing project paths to use instead of scanning.
+    pub from: Option<&'a Path>,
+    /// Fail once reclaimable space exceeds this many bytes (`--fail-on-found`).
+    pub fail_on_found: Option<u64>,
+    /// Allow cleaning from `/` or the home directory (`--i-know-what-im-doing`).
+    pub allow_sweeping_root: bool,
+    /// Print a histogram of project depths alongside the normal output.
+    pub depth_report: bool,
+    /// Print each project as it is found instead of a table at the end.
+    pub stream: bool,
+    /// List paths that couldn't be read during the scan (on stderr).
+    pub report_unreadable: bool,
+    /// Where a successful `clean` records its time (`None` leaves no stamp).
+    pub stamp: Option<&'a Path>,
+    pub config: &'a DevSweepConfig,
+}
 
 // ── Commands ────────────────────────────────────────────────────────────────
 
-pub fn cmd_scan(
-    path: &Path,
-    max_depth: Option<usize>,
-    older_than: Option<&str>,
-    json: bool,
-    config: &DevSweepConfig,
-) -> Result<()> {
-    let mut projects = scan_directory(path, max_depth, config)?;
-    filter_by_age(&mut projects, older_than)?;
+pub fn cmd_scan(ctx: &ScanContext) -> Result<()> {
+    if ctx.count_only {
+        return print_project_count(ctx);
+    }
+    warn_sweeping_roots(ctx);
+    if ctx.stream && !ctx.print0 && ctx.split_output.is_none() {
+        let projects = stream_scan(ctx)?;
+        if ctx.depth_report && !ctx.json {
+            print_depth_report(&depth_histogram(&projects));
+        }
+        return check_fail_on_found(ctx, &projects);
+    }
+    let mut projects = discover_projects(ctx)?;
+    filter_by_age(&mut projects, ctx.older_than)?;
     sort_by_size(&mut projects);
+    let total: u64 = projects.iter().map(|p| p.total_cleanable_bytes).sum();
+    if total > 0 {
+        warn_implausible_total(ctx, total, shared_disk_usage(ctx.roots).as_ref());
+    }
 
-    if json {
-        println!("{}", serde_json::to_string_pretty(&projects)?);
-    } else {
-        print_results_table(&projects);
+    if ctx.print0 {
+        io::stdout().write_all(&render_print0(&projects))?;
+        return check_fail_on_found(ctx, &projects);
     }
 
-    Ok(())
+    if let Some(dir) = ctx.split_output {
+        let written = write_split_output(dir, &projects, ctx.compact, &ctx.display)?;
+        println!(
+            "  {} Wrote {} project files to {}",
+            green("✓"),
+            cyan(&written.to_string()),
+            dir.display()
+        );
+        return check_fail_on_found(ctx, &projects);
+    }
+
+    if ctx.json {
+        println!("{}", render_json(&scan_json(&projects, &ctx.display)?, ctx.compact)?);
+        return check_fail_on_found(ctx, &projects);
+    }
+
+    match ctx.format {
+        OutputFormat::Table => {
+            print_results_table_with(&projects, ctx.columns, &ctx.display);
+            hint_shared_cargo_target(ctx);
+            if ctx.depth_report {
+                print_depth_report(&depth_histogram(&projects));
+            }
+        }
+        OutputFormat::Markdown => print!("{}", render_markdown_table(&projects, &ctx.display)),
+        OutputFormat::Du => print!("{}", render_du_lines(&projects)),
+        OutputFormat::Treemap => {
+            let treemap = render_treemap(&projects);
+            println!("{}", render_json(&treemap, ctx.compact)?);
+        }
+    }
+
+    check_fail_on_found(ctx, &projects)
 }
 
-pub fn cmd_clean(
-    path: &Path,
-    max_depth: Option<usize>,
-    older_than: Option<&str>,
-    all: bool,
-    dry_run: bool,
-    json: bool,
-    config: &DevSweepConfig,
-) -> Result<()> {
-    let mut projects = scan_directory(path, max_depth, config)?;
-    filter_by_age(&mut projects, older_than)?;
-    sort_by_size(&mut projects);
+/// Interactive (or `--all`) cleaning. Every yes/no decision and the project
+/// selection go through `confirmer`, so the whole flow can run without a
+/// terminal.
+pub fn cmd_clean(ctx: &ScanContext, args: &CleanArgs, confirmer: &dyn Confirmer) -> Result<()> {
+    let dry_run = args.dry_run;
+    let opts = CleanOptions {
+        dry_run,
+        prune_empty: args.prune_empty,
+        protect: ctx.config.protect_paths.clone(),
+        allow_git_dir: args.confirm_delete_git,
+        check_perms: args.check_perms,
+    };
 
-    if projects.is_empty() {
-        println!(
-            "\n  {} No projects with cleanable artifacts found.\n",
-            blue("ℹ")
-        );
-        return Ok(());
+    // A plan names its projects explicitly, so the scan roots don't matter.
+    if let Some(plan_path) = &args.apply {
+        return apply_plan(ctx, args, &opts, plan_path);
     }
 
-    print_results_table(&projects);
+    ensure_safe_clean_roots(ctx.roots, ctx.allow_sweeping_root)?;
+    let protect = protect_cutoff(args.protect_newer_than.as_deref())?;
+    let manifest = ManifestLog::open(args)?;
 
-    let selected_projects: Vec<&ScannedProject> = if all {
-        if !dry_run {
+    if args.force {
+        let [path] = ctx.roots else {
+            anyhow::bail!("--force cleans a single project; pass its PATH explicitly");
+        };
+        let mut project = analyze_path(path, ctx.config)?;
+        if let Some(cutoff) = protect
+            && project.last_modified > cutoff
+        {
+            anyhow::bail!(
+                "{} was modified within {} and is protected by --protect-newer-than",
+                path.display(),
+                args.protect_newer_than.as_deref().unwrap_or_default(),
+            );
+        }
+        if let Some(pattern) = &args.targets_matching {
+            retain_targets_matching(&mut project, pattern);
+        }
+        if args.keep_latest {
+            drop_latest_siblings(&mut project);
+        }
+        let mut projects = vec![project];
+        warn_targets_in_use(ctx, &mut projects, args.safe);
+        warn_ambiguous_builds(ctx, &mut projects, args.safe);
+        let started = Instant::now();
+        let results = projects
+            .iter()
+            .map(|p| clean_project_with(p, &opts))
+            .collect::<Result<Vec<_>>>()?;
+        print_clean_results(ctx, &results, dry_run, started.elapsed())?;
+        return record_clean(ctx, &results, dry_run);
+    }
+
+    let scan = || {
+        let mut projects = discover_projects(ctx)?;
+        filter_by_age(&mut projects, ctx.older_than)?;
+        if let Some(cutoff) = protect {
+            let protected = drop_protected(&mut projects, cutoff);
+            report_protected(ctx, protected, args.protect_newer_than.as_deref());
+        }
+        skip_smaller_than_source(ctx, &mut projects);
+        skip_active_venvs(ctx, &mut projects);
+        warn_targets_in_use(ctx, &mut projects, args.safe);
+        warn_ambiguous_builds(ctx, &mut projects, args.safe);
+        if let Some(log) = &manifest {
+            log.skip_completed(ctx, &mut projects);
+        }
+        if let Some(pattern) = &args.targets_matching {
+            for project in &mut projects {
+                retain_targets_matching(project, pattern);
+            }
+            projects.retain(|p| !p.clean_targets.is_empty());
+        }
+        if args.keep_latest {
+            projects.iter_mut().for_each(drop_latest_siblings);
+            projects.retain(|p| p.total_cleanable_bytes > 0);
+        }
+        sort_by_size(&mut projects);
+
+        if projects.is_empty() {
+            println!(
+                "\n  {} No projects with cleanable artifacts found.\n",
+                blue("ℹ")
+            );
+        }
+        Ok(projects)
+    };
+
+    let pass = |projects: &[ScannedProject]| {
+        let cleaned = clean_pass(ctx, args, &opts, confirmer, manifest.as_ref(), projects)?;
+        Ok(cleaned && args.repeat && confirmer.confirm("Rescan and keep cleaning?")?)
+    };
+
+    clean_loop(scan, pass)?;
+    Ok(())
+}
+
+/// Alternate scanning and cleaning until a scan finds nothing left or `pass`
+/// returns `false` (the user is done). Returns the number of passes run.
+///
+/// `scan` is injectable so the loop can be driven without touching the disk.
+pub fn clean_loop(
+    mut scan: impl FnMut() -> Result<Vec<ScannedProject>>,
+    mut pass: impl FnMut(&[ScannedProject]) -> Result<bool>,
+) -> Result<usize> {
+    let mut passes = 0;
+    loop {
+        let projects = scan()?;
+        if projects.is_empty() {
+            return Ok(passes);
+        }
+        passes += 1;
+        if !pass(&projects)? {
+            return Ok(passes);
+        }
+    }
+}
+
+/// One interactive round of `clean`: show the table, pick projects, confirm,
+/// and clean them. Returns `false` if the user aborted or selected nothing.
+fn clean_pass(
+    ctx: &ScanContext,
+    args: &CleanArgs,
+    opts: &CleanOptions,
+    confirmer: &dyn Confirmer,
+    manifest: Option<&ManifestLog>,
+    projects: &[ScannedProject],
+) -> Result<bool> {
+    let dry_run = opts.dry_run;
+
+    print_results_table_with(projects, ctx.columns, &ctx.display);
+
+    // Writing a plan deletes nothing, so it needs no confirmation.
+    let confirm = !dry_run && args.plan.is_none();
+
+    let selected_projects: Vec<&ScannedProject> = if args.all {
+        if confirm {
             let total: u64 = projects.iter().map(|p| p.total_cleanable_bytes).sum();
-            let confirmed = confirm(&format!(
+            print_largest_target(ctx, projects);
+            let prompt = format!(
                 "Clean ALL {} projects? This will free {} and cannot be undone!",
                 projects.len(),
-                format_bytes(total),
-            ))?;
+                ctx.display.sizes.bytes(total),
+            );
+            let confirmed = if args.confirm_word {
+                confirmer.confirm_word(&prompt, "clean")?
+            } else {
+                confirmer.confirm(&prompt)?
+            };
 
             if !confirmed {
                 println!("  {} Aborted.\n", red_bold("✗"));
-                return Ok(());
+                return Ok(false);
             }
         }
         projects.iter().collect()
@@ -77,8 +307,8 @@ pub fn cmd_clean(
                 format!(
                     "{} ({}) — {} [{}]",
                     p.name,
-                    p.kind,
-                    format_bytes(p.total_cleanable_bytes),
+                    p.kind_label(),
+                    ctx.display.sizes.bytes(p.total_cleanable_bytes),
                     p.clean_targets
                         .iter()
                         .map(|t| t.name.as_str())
@@ -88,32 +318,53 @@ pub fn cmd_clean(
             })
             .collect();
 
-        let selections = multi_select("Select projects to clean:", &items)?;
+        let selections = confirmer.select("Select projects to clean:", &items)?;
 
         if selections.is_empty() {
             println!("  {} Nothing selected.\n", blue("ℹ"));
-            return Ok(());
+            return Ok(false);
         }
 
-        if !dry_run {
+        if confirm {
             let sel_total: u64 = selections
                 .iter()
                 .map(|&i| projects[i].total_cleanable_bytes)
                 .sum();
-            let confirmed = confirm(&format!(
+            print_largest_target(ctx, selections.iter().map(|&i| &projects[i]));
+            let confirmed = confirmer.confirm(&format!(
                 "Clean {} projects? This will free {}.",
                 selections.len(),
-                format_bytes(sel_total),
+                ctx.display.sizes.bytes(sel_total),
             ))?;
             if !confirmed {
                 println!("  {} Aborted.\n", red_bold("✗"));
-                return Ok(());
+                return Ok(false);
             }
         }
 
         selections.iter().map(|&i| &projects[i]).collect()
     };
 
+    if let Some(plan_path) = &args.plan {
+        let plan = CleanPlan::new(&selected_projects);
+        plan.save(plan_path)?;
+        if !ctx.json {
+            println!(
+                "\n  {} Wrote a plan to clean {} projects ({}) to {}",
+                green("✓"),
+                cyan(&plan.projects.len().to_string()),
+                yellow_bold(&ctx.display.sizes.bytes(plan.total_bytes())),
+                plan_path.display(),
+            );
+            println!(
+                "  {} Run it with {}\n",
+                dim("→"),
+                green(&format!("dev-sweep clean --apply {}", plan_path.display())),
+            );
+        }
+        return Ok(false);
+    }
+
     let action = if dry_run { "Would clean" } else { "Cleaning" };
     println!(
         "\n  {} {} {} projects...\n",
@@ -122,91 +373,345 @@ pub fn cmd_clean(
         cyan(&selected_projects.len().to_string()),
     );
 
-    let results = clean_projects(&selected_projects, dry_run);
+    let started = Instant::now();
+    let results = clean_with_progress(ctx, &selected_projects, opts, manifest);
 
-    if json {
-        let summary = serde_json::json!({
-            "dry_run": dry_run,
-            "projects_cleaned": results.len(),
-            "total_bytes_freed": results.iter().map(|r| r.bytes_freed).sum::<u64>(),
-            "errors": results.iter().flat_map(|r| r.errors.clone()).collect::<Vec<_>>(),
-        });
-        println!("{}", serde_json::to_string_pretty(&summary)?);
-    } else {
-        print_clean_summary(&results, dry_run);
+    print_clean_results(ctx, &results, dry_run, started.elapsed())?;
+    record_clean(ctx, &results, dry_run)?;
+    Ok(true)
+}
+
+/// Clean only build outputs in every discovered project, without prompting.
+///
+/// Dependency and tool caches are never touched, nor are unclassified targets
+/// from custom kinds or `DEVCLEAN_EXTRA_TARGETS`, which makes this safe to run
+/// unattended (e.g. from cron).
+pub fn cmd_purge(ctx: &ScanContext, args: &PurgeArgs) -> Result<()> {
+    ensure_safe_clean_roots(ctx.roots, ctx.allow_sweeping_root)?;
+
+    let opts = CleanOptions {
+        dry_run: args.dry_run,
+        protect: ctx.config.protect_paths.clone(),
+        ..Default::default()
+    };
+    let protect = protect_cutoff(args.protect_newer_than.as_deref())?;
+
+    let mut projects = discover_projects(ctx)?;
+    filter_by_age(&mut projects, ctx.older_than)?;
+    if let Some(cutoff) = protect {
+        let protected = drop_protected(&mut projects, cutoff);
+        report_protected(ctx, protected, args.protect_newer_than.as_deref());
     }
+    skip_smaller_than_source(ctx, &mut projects);
+    skip_active_venvs(ctx, &mut projects);
+    warn_targets_in_use(ctx, &mut projects, args.safe);
+    warn_ambiguous_builds(ctx, &mut projects, args.safe);
+    for project in &mut projects {
+        retain_categories(project, &[TargetCategory::BuildOutput]);
+    }
+    projects.retain(|p| p.total_cleanable_bytes > 0);
+    sort_by_size(&mut projects);
 
-    Ok(())
+    let selected: Vec<&ScannedProject> = projects.iter().collect();
+    let started = Instant::now();
+    let results = clean_with_progress(ctx, &selected, &opts, None);
+
+    print_clean_results(ctx, &results, args.dry_run, started.elapsed())
 }
 
-pub fn cmd_summary(
-    path: &Path,
-    max_depth: Option<usize>,
-    older_than: Option<&str>,
-    json: bool,
-    config: &DevSweepConfig,
-) -> Result<()> {
-    let mut projects = scan_directory(path, max_depth, config)?;
-    filter_by_age(&mut projects, older_than)?;
+pub fn cmd_summary(ctx: &ScanContext, args: &SummaryArgs) -> Result<()> {
+    if ctx.count_only {
+        return print_project_count(ctx);
+    }
+    warn_sweeping_roots(ctx);
+    let mut projects = discover_projects(ctx)?;
+    filter_by_age(&mut projects, ctx.older_than)?;
 
     let total_bytes: u64 = projects.iter().map(|p| p.total_cleanable_bytes).sum();
     let total_projects = projects.len();
+    let estimated = projects.iter().any(|p| p.is_estimate());
+    let stats = size_stats(&projects);
+    let disk = shared_disk_usage(ctx.roots);
+    warn_implausible_total(ctx, total_bytes, disk.as_ref());
+
+    let by_kind = group_totals(&projects, GroupBy::Kind);
+    let groups = group_totals(&projects, args.group_by);
 
-    let mut by_kind: HashMap<String, (usize, u64)> = HashMap::new();
-    for p in &projects {
-        let entry = by_kind.entry(p.kind.to_string()).or_insert((0, 0));
-        entry.0 += 1;
-        entry.1 += p.total_cleanable_bytes;
+    let stale_after = parse_age(&args.stale_after)?;
+    let split = stale_split(&projects, chrono::Local::now() - stale_after);
+
+    let node_modules = target_totals(&projects, ProjectKind::Node, "node_modules");
+
+    if let Some(log) = &args.append_log {
+        SummarySnapshot::new(&projects, chrono::Local::now()).append_to(log)?;
     }
 
-    if json {
-        let summary = serde_json::json!({
+    if ctx.json {
+        let group_json = |totals: &[GroupTotal], label: &str| {
+            totals
+                .iter()
+                .map(|g| {
+                    serde_json::json!({
+                        label: g.key,
+                        "projects": g.projects,
+                        "reclaimable_bytes": g.reclaimable_bytes,
+                        "reclaimable_human": ctx.display.sizes.bytes(g.reclaimable_bytes),
+                    })
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let largest = stats.largest.as_ref().map(|(name, bytes)| {
+            serde_json::json!({
+                "name": name,
+                "reclaimable_bytes": bytes,
+                "reclaimable_human": ctx.display.sizes.bytes(*bytes),
+            })
+        });
+
+        let mut summary = serde_json::json!({
             "total_projects": total_projects,
             "total_reclaimable_bytes": total_bytes,
-            "total_reclaimable_human": format_bytes(total_bytes),
-            "by_kind": by_kind.iter().map(|(k, (count, bytes))| {
-                serde_json::json!({
-                    "kind": k,
-                    "projects": count,
-                    "reclaimable_bytes": bytes,
-                    "reclaimable_human": format_bytes(*bytes),
-                })
-            }).collect::<Vec<_>>(),
+            "total_reclaimable_human": ctx.display.sizes.approx(total_bytes, estimated),
+            "average_reclaimable_bytes": stats.average_bytes,
+            "median_reclaimable_bytes": stats.median_bytes,
+            "largest_project": largest,
+            "stale_after": args.stale_after,
+            "active": {
+                "projects": split.active_projects,
+                "reclaimable_bytes": split.active_bytes,
+                "reclaimable_human": ctx.display.sizes.bytes(split.active_bytes),
+            },
+            "stale": {
+                "projects": split.stale_projects,
+                "reclaimable_bytes": split.stale_bytes,
+                "reclaimable_human": ctx.display.sizes.bytes(split.stale_bytes),
+            },
+            "by_kind": group_json(&by_kind, "kind"),
+            "node_modules": {
+                "count": node_modules.count,
+                "bytes": node_modules.bytes,
+                "human": ctx.display.sizes.bytes(node_modules.bytes),
+            },
         });
-        println!("{}", serde_json::to_string_pretty(&summary)?);
+        match args.group_by {
+            GroupBy::Kind => {}
+            GroupBy::Root => summary["by_root"] = group_json(&groups, "root").into(),
+            GroupBy::Prefix(_) => summary["by_prefix"] = group_json(&groups, "prefix").into(),
+        }
+        if let Some(usage) = &disk {
+            summary["disk"] = serde_json::json!({
+                "mount": usage.mount,
+                "used_percent": usage.percent_used(),
+                "used_percent_after_clean": usage.percent_used_after(total_bytes),
+            });
+        }
+        if ctx.depth_report {
+            summary["by_depth"] = depth_histogram(&projects)
+                .into_iter()
+                .map(|(depth, n)| serde_json::json!({ "depth": depth, "projects": n }))
+                .collect::<Vec<_>>()
+                .into();
+        }
+        println!("{}", render_json(&summary, ctx.compact)?);
     } else {
-        println!("\n  📊 dev-sweep summary for {}\n", path.display());
+        let roots = ctx
+            .roots
+            .iter()
+            .map(|r| r.display().to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        println!("\n  📊 dev-sweep summary for {}\n", roots);
         println!(
             "  Total projects:     {}",
             cyan(&total_projects.to_string())
         );
         println!(
             "  Reclaimable space:  {}",
-            yellow_bold(&format_bytes(total_bytes))
+            yellow_bold(&ctx.display.sizes.approx(total_bytes, estimated))
+        );
+        println!(
+            "    {:<18}{} ({} projects)",
+            format!("stale (> {}):", args.stale_after),
+            yellow_bold(&ctx.display.sizes.bytes(split.stale_bytes)),
+            cyan(&split.stale_projects.to_string()),
+        );
+        println!(
+            "    {:<18}{} ({} projects)",
+            format!("active (< {}):", args.stale_after),
+            ctx.display.sizes.bytes(split.active_bytes),
+            cyan(&split.active_projects.to_string()),
         );
+        if let Some((name, bytes)) = &stats.largest {
+            println!(
+                "  Average / median:   {} / {}",
+                ctx.display.sizes.bytes(stats.average_bytes),
+                ctx.display.sizes.bytes(stats.median_bytes),
+            );
+            println!(
+                "  Largest project:    {} ({})",
+                name,
+                yellow_bold(&ctx.display.sizes.bytes(*bytes)),
+            );
+        }
+        if let Some(usage) = &disk {
+            println!(
+                "  Cleaning all would free {}, {} ({}).",
+                yellow_bold(&ctx.display.sizes.bytes(total_bytes)),
+                usage.projection(total_bytes),
+                usage.mount,
+            );
+        }
         println!();
 
-        if !by_kind.is_empty() {
-            println!("  {}", dim("By project type:"));
+        if !groups.is_empty() {
+            let heading = match args.group_by {
+                GroupBy::Kind => "By project type:",
+                GroupBy::Root => "By scan root:",
+                GroupBy::Prefix(_) => "By path prefix:",
+            };
+            println!("  {}", dim(heading));
 
-            let mut sorted: Vec<_> = by_kind.iter().collect();
-            sorted.sort_by(|a, b| b.1 .1.cmp(&a.1 .1));
-
-            for (kind, (count, bytes)) in sorted {
+            for group in &groups {
                 println!(
                     "    {:>12}  {} projects, {}",
-                    kind,
-                    cyan(&count.to_string()),
-                    yellow_bold(&format_bytes(*bytes)),
+                    group.key,
+                    cyan(&group.projects.to_string()),
+                    yellow_bold(&ctx.display.sizes.bytes(group.reclaimable_bytes)),
                 );
             }
             println!();
         }
+
+        // Several copies of node_modules usually means duplicated packages.
+        if node_modules.count > 1 {
+            println!(
+                "  {} {} node_modules directories use {}. A shared store (e.g. pnpm) can deduplicate packages across projects.",
+                blue("ℹ"),
+                cyan(&node_modules.count.to_string()),
+                yellow_bold(&ctx.display.sizes.bytes(node_modules.bytes)),
+            );
+            println!();
+        }
+        hint_shared_cargo_target(ctx);
+
+        if ctx.depth_report {
+            print_depth_report(&depth_histogram(&projects));
+        }
+    }
+
+    check_fail_on_found(ctx, &projects)
+}
+
+pub fn cmd_watch(ctx: &ScanContext, interval: u64, threshold: u64) -> Result<()> {
+    let stop = Arc::new(AtomicBool::new(false));
+    {
+        let stop = Arc::clone(&stop);
+        ctrlc::set_handler(move || stop.store(true, Ordering::SeqCst))
+            .context("Failed to install Ctrl-C handler")?;
+    }
+
+    if !ctx.json {
+        println!(
+            "\n  👀 Watching every {}s, alerting above {} (Ctrl-C to stop)\n",
+            interval,
+            yellow_bold(&ctx.display.sizes.bytes(threshold))
+        );
+    }
+
+    let mut previous = None;
+    while !stop.load(Ordering::SeqCst) {
+        let mut projects = discover_projects(ctx)?;
+        filter_by_age(&mut projects, ctx.older_than)?;
+        let total_bytes: u64 = projects.iter().map(|p| p.total_cleanable_bytes).sum();
+        let alert = threshold_crossed(previous, total_bytes, threshold);
+        let now = chrono::Local::now();
+
+        if ctx.json {
+            let tick = serde_json::json!({
+                "timestamp": now.to_rfc3339(),
+                "total_projects": projects.len(),
+                "total_reclaimable_bytes": total_bytes,
+                "total_reclaimable_human": ctx.display.sizes.bytes(total_bytes),
+                "over_threshold": total_bytes > threshold,
+                "alert": alert,
+            });
+            println!("{tick}");
+        } else {
+            let line = format!(
+                "  [{}] {} reclaimable across {} projects",
+                now.format("%H:%M:%S"),
+                ctx.display.sizes.bytes(total_bytes),
+                projects.len()
+            );
+            if alert {
+                println!("{line}  {}\x07", red_bold("⚠ over threshold"));
+            } else if total_bytes > threshold {
+                println!("{line}  {}", yellow("(still over threshold)"));
+            } else {
+                println!("{line}");
+            }
+        }
+        previous = Some(total_bytes);
+
+        // Sleep in short slices so Ctrl-C takes effect promptly.
+        let deadline = Instant::now() + Duration::from_secs(interval);
+        while !stop.load(Ordering::SeqCst) && Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(200));
+        }
     }
 
+    if !ctx.json {
+        println!("\n  {}", dim("Stopped watching."));
+    }
     Ok(())
 }
 
+/// List the built-in project kinds so users can audit what cleaning removes.
+pub fn cmd_kinds(json: bool, compact: bool) -> Result<()> {
+    let kinds = ProjectKind::all();
+    if json {
+        let listing = render_kinds_json(kinds);
+        println!("{}", render_json(&listing, compact)?);
+    } else {
+        print_kinds(kinds);
+    }
+    Ok(())
+}
+
+/// List machine-wide toolchain caches, and with `--clean` remove them once
+/// `confirmer` agrees.
+pub fn cmd_caches(ctx: &ScanContext, args: &CachesArgs, confirmer: &dyn Confirmer) -> Result<()> {
+    let caches = global_caches();
+    if ctx.json && !args.clean {
+        println!("{}", render_json(&caches, ctx.compact)?);
+        return Ok(());
+    }
+    if !ctx.json {
+        print_global_caches(&caches, &ctx.display);
+    }
+    if !args.clean || caches.is_empty() {
+        return Ok(());
+    }
+
+    if !args.dry_run {
+        let total: u64 = caches.iter().map(|c| c.size_bytes).sum();
+        let prompt = format!(
+            "Clean {} global caches? This will free {}.",
+            caches.len(),
+            ctx.display.sizes.bytes(total)
+        );
+        if !confirmer.confirm(&prompt)? {
+            println!("  {} Aborted.\n", red_bold("✗"));
+            return Ok(());
+        }
+    }
+
+    let started = Instant::now();
+    let results = clean_caches(&caches, args.dry_run);
+    print_clean_results(ctx, &results, args.dry_run, started.elapsed())
+}
+
 pub fn cmd_config(show: bool, reset: bool) -> Result<()> {
     if reset {
         let config = DevSweepConfig::default();
@@ -252,14 +757,500 @@ pub fn cmd_config(show: bool, reset: bool) -> Result<()> {
 
 // ── Helpers ─────────────────────────────────────────────────────────────────
 
+/// `clean --apply`: clean what a plan lists, minus anything that changed since.
+fn apply_plan(
+    ctx: &ScanContext,
+    args: &CleanArgs,
+    opts: &CleanOptions,
+    plan_path: &Path,
+) -> Result<()> {
+    let plan = CleanPlan::load(plan_path)?;
+    let mut check = plan.validate(ctx.config);
+
+    for (path, reason) in &check.skipped {
+        eprintln!("  {} Skipping {}: {}", yellow("⚠"), path.display(), reason);
+    }
+    for (path, planned, now) in &check.resized {
+        eprintln!(
+            "  {} {} changed size since planning ({} → {})",
+            blue("ℹ"),
+            path.display(),
+            ctx.display.sizes.bytes(*planned),
+            ctx.display.sizes.bytes(*now),
+        );
+    }
+    if let Some(cutoff) = protect_cutoff(args.protect_newer_than.as_deref())? {
+        let protected = drop_protected(&mut check.projects, cutoff);
+        report_protected(ctx, protected, args.protect_newer_than.as_deref());
+    }
+
+    let selected: Vec<&ScannedProject> = check.projects.iter().collect();
+    let started = Instant::now();
+    let results = clean_with_progress(ctx, &selected, opts, None);
+
+    print_clean_results(ctx, &results, opts.dry_run, started.elapsed())?;
+    record_clean(ctx, &results, opts.dry_run)
+}
+
+/// `scan --stream`: print each project the moment it has been sized (one JSON
+/// object per line with `--json`), then a one-line total. Returns the projects
+/// found, for `--fail-on-found` and `--depth-report`.
+fn stream_scan(ctx: &ScanContext) -> Result<Vec<ScannedProject>> {
+    let cutoff = match ctx.older_than {
+        Some(age) => Some(parse_cutoff(age, chrono::Local::now())?),
+        None => None,
+    };
+    let print = |project: &ScannedProject| {
+        if cutoff.is_some_and(|c| project.last_modified >= c) {
+            return;
+        }
+        if ctx.json {
+            if let Ok(line) = scan_json(project, &ctx.display).map(|json| json.to_string()) {
+                println!("{line}");
+            }
+        } else {
+            println!("{}", render_stream_line(project, &ctx.display));
+        }
+    };
+
+    // The spinner would garble the lines printed while it's still spinning.
+    let config = DevSweepConfig {
+        no_spinner: true,
+        ..ctx.config.clone()
+    };
+    let quiet = ScanContext {
+        config: &config,
+        ..*ctx
+    };
+    let mut projects = discover_projects_each(&quiet, &print)?;
+    filter_by_age(&mut projects, ctx.older_than)?;
+
+    if !ctx.json {
+        let total: u64 = projects.iter().map(|p| p.total_cleanable_bytes).sum();
+        let estimated = projects.iter().any(|p| p.is_estimate());
+        println!(
+            "\n  {} Found {} projects with {} of reclaimable space\n",
+            green("✓"),
+            cyan(&projects.len().to_string()),
+            yellow_bold(&ctx.display.sizes.approx(total, estimated)),
+        );
+    }
+
+    Ok(projects)
+}
+
+/// Clean `projects`, drawing a bar of bytes freed so far against the planned
+/// total on stderr. The bar is only shown for a real clean on a terminal, never
+/// with `--json`. A real clean also records each finished project in
+/// `manifest`, if there is one.
+fn clean_with_progress(
+    ctx: &ScanContext,
+    projects: &[&ScannedProject],
+    opts: &CleanOptions,
+    manifest: Option<&ManifestLog>,
+) -> Vec<CleanResult> {
+    let record = |project: &ScannedProject, result: &CleanResult| {
+        if let Some(log) = manifest
+            && !opts.dry_run
+        {
+            log.record(project, result);
+        }
+    };
+    if opts.dry_run || ctx.json || !io::stderr().is_terminal() {
+        return clean_projects_each(projects, opts, &record);
+    }
+
+    let total: u64 = projects
+        .iter()
+        .flat_map(|p| &p.clean_targets)
+        .map(|t| t.size_bytes)
+        .sum();
+    let freed = AtomicU64::new(0);
+    print_clean_progress(0, total, &ctx.display);
+    let results = clean_projects_each(projects, opts, &|project, result| {
+        record(project, result);
+        let so_far = freed.fetch_add(result.bytes_freed, Ordering::Relaxed) + result.bytes_freed;
+        print_clean_progress(so_far, total, &ctx.display);
+    });
+    clear_clean_progress();
+    results
+}
+
+/// The `--manifest`/`--resume` file of a clean, updated on disk as each
+/// project finishes so an interrupted clean loses nothing.
+struct ManifestLog {
+    path: PathBuf,
+    manifest: Mutex<CleanManifest>,
+}
+
+impl ManifestLog {
+    /// Start a fresh manifest for `--manifest`, or pick up the one named by
+    /// `--resume`. `None` if neither was given.
+    fn open(args: &CleanArgs) -> Result<Option<Self>> {
+        let (path, manifest) = match (&args.manifest, &args.resume) {
+            (_, Some(path)) => (path, CleanManifest::load(path)?),
+            (Some(path), None) => (path, CleanManifest::default()),
+            (None, None) => return Ok(None),
+        };
+        manifest.save(path)?;
+        Ok(Some(Self {
+            path: path.clone(),
+            manifest: Mutex::new(manifest),
+        }))
+    }
+
+    /// Drop the projects already recorded as cleaned and say how many.
+    fn skip_completed(&self, ctx: &ScanContext, projects: &mut Vec<ScannedProject>) {
+        let skipped = self.manifest.lock().unwrap().retain_pending(projects);
+        if skipped == 0 {
+            return;
+        }
+        let msg = format!(
+            "  {} Resuming: {} projects already cleaned per {}.",
+            blue("ℹ"),
+            cyan(&skipped.to_string()),
+            self.path.display(),
+        );
+        notice(ctx, &msg);
+    }
+
+    /// Record a finished project and rewrite the file. A failed write only
+    /// warns: the clean itself already happened.
+    fn record(&self, project: &ScannedProject, result: &CleanResult) {
+        let mut manifest = self.manifest.lock().unwrap();
+        if manifest.record(project, result)
+            && let Err(e) = manifest.save(&self.path)
+        {
+            eprintln!("  {} {:#}", yellow("⚠"), e);
+        }
+    }
+}
+
+fn print_clean_results(
+    ctx: &ScanContext,
+    results: &[CleanResult],
+    dry_run: bool,
+    elapsed: Duration,
+) -> Result<()> {
+    if ctx.json {
+        let report = CleanReport::new(results, dry_run);
+        println!("{}", render_json(&report, ctx.compact)?);
+        return Ok(());
+    }
+
+    print_clean_summary(results, dry_run, elapsed, &ctx.display);
+    if dry_run && let Some(usage) = shared_disk_usage(ctx.roots) {
+        let freed: u64 = results.iter().map(|r| r.bytes_freed).sum();
+        println!(
+            "  {} Would free {}, {}.\n",
+            dim("💾"),
+            yellow_bold(&ctx.display.sizes.bytes(freed)),
+            usage.projection(freed),
+        );
+    }
+
+    Ok(())
+}
+
+/// Stamp the time of a clean that actually ran and hit no errors, for
+/// `--since-last-clean`.
+fn record_clean(ctx: &ScanContext, results: &[CleanResult], dry_run: bool) -> Result<()> {
+    match ctx.stamp {
+        Some(path) if !dry_run && results.iter().all(|r| r.errors.is_empty()) => {
+            write_stamp(path, Local::now())
+        }
+        _ => Ok(()),
+    }
+}
+
 fn sort_by_size(projects: &mut [ScannedProject]) {
     projects.sort_unstable_by_key(|p| std::cmp::Reverse(p.total_cleanable_bytes));
 }
 
+/// Show the biggest single target about to be deleted, ahead of a confirmation.
+fn print_largest_target<'a>(
+    ctx: &ScanContext,
+    projects: impl IntoIterator<Item = &'a ScannedProject>,
+) {
+    if let Some(target) = largest_target(projects) {
+        println!(
+            "  {} Largest target: {} ({})",
+            dim("→"),
+            target.path.display(),
+            yellow_bold(&ctx.display.sizes.bytes(target.size_bytes)),
+        );
+    }
+}
+
+/// Warn (but carry on) when a read-only command scans `/` or the home directory.
+fn warn_sweeping_roots(ctx: &ScanContext) {
+    for root in ctx.roots.iter().filter(|r| is_sweeping_root(r)) {
+        eprintln!(
+            "  {} Scanning {} — this may take a while.",
+            yellow("⚠"),
+            root.display()
+        );
+    }
+}
+
+/// `--count-only`: print how many projects have something to clean, for
+/// shell prompts and scripts. No warnings, no `--fail-on-found` check.
+fn print_project_count(ctx: &ScanContext) -> Result<()> {
+    let mut projects = discover_projects(ctx)?;
+    filter_by_age(&mut projects, ctx.older_than)?;
+    println!("{}", projects.len());
+    Ok(())
+}
+
+/// Find the projects to work on: those listed in `--from`, or a scan of the roots.
+fn discover_projects(ctx: &ScanContext) -> Result<Vec<ScannedProject>> {
+    discover_projects_each(ctx, &|_| {})
+}
+
+/// Like [`discover_projects`], handing each project to `on_project` as soon as
+/// it is ready. Applies `--name` and `--since-last-clean`, and with
+/// `--report-unreadable` also lists paths that couldn't be read.
+fn discover_projects_each(
+    ctx: &ScanContext,
+    on_project: &(dyn Fn(&ScannedProject) + Sync),
+) -> Result<Vec<ScannedProject>> {
+    let wanted = |p: &ScannedProject| {
+        ctx.name.is_none_or(|name| p.matches_name(name))
+            && ctx.modified_since.is_none_or(|since| p.last_modified > since)
+    };
+    let on_wanted = |p: &ScannedProject| {
+        if wanted(p) {
+            on_project(p);
+        }
+    };
+
+    let Some(from) = ctx.from else {
+        let (mut projects, unreadable) =
+            scan_directories_each(ctx.roots, ctx.max_depth, ctx.config, &on_wanted)?;
+        if ctx.report_unreadable {
+            print_unreadable(&unreadable);
+        }
+        save_size_cache_for(ctx);
+        projects.retain(wanted);
+        return Ok(projects);
+    };
+
+    let paths = if from == Path::new("-") {
+        read_path_list(io::stdin().lock())?
+    } else {
+        let file = File::open(from)
+            .with_context(|| format!("Failed to open project list {}", from.display()))?;
+        read_path_list(BufReader::new(file))?
+    };
+
+    let (mut projects, skipped) = analyze_paths(&paths, ctx.config);
+    for (path, reason) in &skipped {
+        eprintln!("  {} Skipping {}: {}", yellow("⚠"), path.display(), reason);
+    }
+    if ctx.report_unreadable {
+        let unreadable: Vec<_> = projects
+            .iter()
+            .flat_map(|p| p.unreadable.iter().cloned())
+            .collect();
+        print_unreadable(&unreadable);
+    }
+    save_size_cache_for(ctx);
+    projects.retain(|p| p.total_cleanable_bytes > 0 && wanted(p));
+    projects.iter().for_each(on_project);
+
+    Ok(projects)
+}
+
+/// With `--cache-mode`, write the sizes measured by this scan back to the
+/// size cache. A failure only costs the next scan its head start, so it's a
+/// warning.
+fn save_size_cache_for(ctx: &ScanContext) {
+    if ctx.config.cache_mode == CacheMode::Off {
+        return;
+    }
+    if let Err(e) = save_size_cache() {
+        eprintln!("  {} {:#}", yellow("⚠"), e);
+    }
+}
+
+/// Resolve `--protect-newer-than` into the cutoff after which projects are
+/// off-limits for cleaning.
+fn protect_cutoff(
+    protect_newer_than: Option<&str>,
+) -> Result<Option<chrono::DateTime<chrono::Local>>> {
+    protect_newer_than
+        .map(|age| Ok(chrono::Local::now() - parse_age(age)?))
+        .transpose()
+}
+
+/// With `--fail-on-found`, fail when the reclaimable total exceeds the limit,
+/// listing the largest offenders on stderr, so CI can gate on a clean tree.
+fn check_fail_on_found(ctx: &ScanContext, projects: &[ScannedProject]) -> Result<()> {
+    let Some(limit) = ctx.fail_on_found else {
+        return Ok(());
+    };
+    let total: u64 = projects.iter().map(|p| p.total_cleanable_bytes).sum();
+    if total <= limit {
+        return Ok(());
+    }
+
+    let mut offenders: Vec<&ScannedProject> = projects.iter().collect();
+    offenders.sort_by_key(|p| std::cmp::Reverse(p.total_cleanable_bytes));
+    eprintln!("  {} Largest offenders:", red_bold("✗"));
+    for project in offenders.iter().take(10) {
+        eprintln!(
+            "    {:>10}  {}",
+            ctx.display.sizes.bytes(project.total_cleanable_bytes),
+            project.path.display()
+        );
+    }
+    anyhow::bail!(
+        "{} reclaimable exceeds the --fail-on-found limit of {}",
+        ctx.display.sizes.bytes(total),
+        ctx.display.sizes.bytes(limit)
+    )
+}
+
+/// Point out a global `CARGO_TARGET_DIR`: Rust projects then have no
+/// `target/` of their own, so the scan can't credit them with their build
+/// output and would otherwise understate what's reclaimable.
+fn hint_shared_cargo_target(ctx: &ScanContext) {
+    if let Some(shared) = cargo_target_cache() {
+        println!(
+            "  {} CARGO_TARGET_DIR is set: Rust build output ({}) is in {}, not each project's target/. See {}.",
+            blue("ℹ"),
+            yellow_bold(&ctx.display.sizes.bytes(shared.size_bytes)),
+            shared.path.display(),
+            green("dev-sweep caches"),
+        );
+        println!();
+    }
+}
+
+/// Warn (on stderr) when `total` reclaimable bytes can't fit on `disk`.
+fn warn_implausible_total(ctx: &ScanContext, total: u64, disk: Option<&DiskUsage>) {
+    let sizes = ctx.display.sizes;
+    if let Some(msg) = disk.and_then(|d| implausible_total_warning(total, d, sizes)) {
+        eprintln!("  {} {}", yellow("⚠"), msg);
+    }
+}
+
+/// Print a note for the user: on stdout normally, on stderr under `--json` so
+/// stdout stays machine-readable.
+fn notice(ctx: &ScanContext, msg: &str) {
+    if ctx.json {
+        eprintln!("{msg}");
+    } else {
+        println!("{msg}");
+    }
+}
+
+/// Tell the user how many projects `--protect-newer-than` held back (see
+/// [`notice`]).
+fn report_protected(ctx: &ScanContext, count: usize, age: Option<&str>) {
+    if count == 0 {
+        return;
+    }
+    let msg = format!(
+        "  {} {} projects modified within {} are protected and won't be cleaned.",
+        blue("🛡"),
+        cyan(&count.to_string()),
+        age.unwrap_or_default(),
+    );
+    notice(ctx, &msg);
+}
+
+/// With `--skip-smaller-than-source`, drop projects whose artifacts are
+/// smaller than their source and say how many were held back.
+fn skip_smaller_than_source(ctx: &ScanContext, projects: &mut Vec<ScannedProject>) {
+    if !ctx.config.skip_smaller_than_source {
+        return;
+    }
+    let skipped = drop_smaller_than_source(projects);
+    if skipped == 0 {
+        return;
+    }
+    let msg = format!(
+        "  {} {} projects with artifacts smaller than their source are skipped (likely a partial build).",
+        blue("ℹ"),
+        cyan(&skipped.to_string()),
+    );
+    notice(ctx, &msg);
+}
+
+/// Hold back the virtualenv active in this shell, which only `clean --force`
+/// removes, and say so.
+fn skip_active_venvs(ctx: &ScanContext, projects: &mut Vec<ScannedProject>) {
+    for path in drop_active_venvs(projects) {
+        let msg = format!(
+            "  {} Skipping {}: it's the active virtualenv. Use clean --force PATH to remove it.",
+            yellow("⚠"),
+            path.display(),
+        );
+        notice(ctx, &msg);
+    }
+}
+
+/// Warn about targets a running build appears to be using, since deleting
+/// them mid-build can corrupt it. With `--safe` they're skipped instead.
+fn warn_targets_in_use(ctx: &ScanContext, projects: &mut Vec<ScannedProject>, safe: bool) {
+    let in_use = targets_in_use(projects);
+    for (path, lock) in &in_use {
+        let msg = if safe {
+            format!(
+                "  {} Skipping {}: a running build holds {}.",
+                yellow("⚠"),
+                path.display(),
+                lock.display(),
+            )
+        } else {
+            format!(
+                "  {} {} looks in use by a running build ({} is locked). Use --safe to skip it.",
+                yellow("⚠"),
+                path.display(),
+                lock.display(),
+            )
+        };
+        notice(ctx, &msg);
+    }
+    if safe {
+        let paths: Vec<PathBuf> = in_use.into_iter().map(|(path, _)| path).collect();
+        drop_targets(projects, &paths);
+    }
+}
+
+/// Warn about `build/` dirs that may be committed source, since they may not
+/// be output at all. With `--safe` they're skipped instead.
+fn warn_ambiguous_builds(ctx: &ScanContext, projects: &mut Vec<ScannedProject>, safe: bool) {
+    mark_ambiguous_builds(projects);
+    let ambiguous = projects
+        .iter()
+        .flat_map(|p| &p.clean_targets)
+        .filter(|t| t.ambiguous);
+    for target in ambiguous {
+        let msg = if safe {
+            format!(
+                "  {} Skipping {}: it may be committed source.",
+                yellow("⚠"),
+                target.path.display(),
+            )
+        } else {
+            format!(
+                "  {} {} may be committed source. Use --safe to skip it.",
+                yellow("⚠"),
+                target.path.display(),
+            )
+        };
+        notice(ctx, &msg);
+    }
+    if safe {
+        drop_ambiguous_builds(projects);
+    }
+}
+
 fn filter_by_age(projects: &mut Vec<ScannedProject>, older_than: Option<&str>) -> Result<()> {
     if let Some(age_str) = older_than {
-        let duration = parse_age(age_str)?;
-        let cutoff = chrono::Local::now() - duration;
+        let cutoff = parse_cutoff(age_str, chrono::Local::now())?;
         projects.retain(|p| p.last_modified < cutoff);
     }
     Ok(())
@@ -1,4 +1,6 @@
 pub mod args;
 pub mod commands;
 
-pub use args::{Cli, Commands};
+pub use args::{
+    CachesArgs, CleanArgs, Cli, Commands, OutputFormat, PurgeArgs, SummaryArgs, expand_arg_files,
+};
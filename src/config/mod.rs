@@ -4,6 +4,45 @@ use serde::{Deserialize, Serialize};
 
 use crate::scanner::ProjectKind;
 
+/// A user-defined project kind, detected and cleaned alongside the built-ins.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CustomKind {
+    /// Display name for the kind (e.g. "MyBuild").
+    pub name: String,
+    /// Marker files that identify a project root, in the same syntax as the
+    /// built-in kinds: `name`, `*suffix`, or `sub/path`.
+    pub marker_files: Vec<String>,
+    /// Directories (or `prefix-*` globs) to clean, relative to the project root.
+    #[serde(default)]
+    pub cleanable_dirs: Vec<String>,
+}
+
+/// The subcommand to run when none is given on the command line.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DefaultCommand {
+    #[default]
+    Scan,
+    Summary,
+    Clean,
+}
+
+/// How `--cache-mode` decides whether a cached target size is still valid.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum CacheMode {
+    /// Always walk targets; don't read or write the cache.
+    #[default]
+    Off,
+    /// Reuse a size while the directory's own mtime is unchanged.
+    Mtime,
+    /// Also compare the count and mtimes of the directory's entries, which
+    /// catches files rewritten in place.
+    Signature,
+}
+
 /// Persistent configuration for dev-sweep.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct DevSweepConfig {
@@ -11,6 +50,11 @@ pub struct DevSweepConfig {
     #[serde(default)]
     pub ignore_paths: Vec<PathBuf>,
 
+    /// Glob patterns (e.g. `**/release/**`) for paths that are never
+    /// cleaned, even when selected.
+    #[serde(default)]
+    pub protect_paths: Vec<String>,
+
     /// Project kinds to exclude from scanning.
     #[serde(default)]
     pub exclude_kinds: Vec<ProjectKind>,
@@ -22,15 +66,104 @@ pub struct DevSweepConfig {
     /// Maximum directory depth to scan.
     #[serde(default)]
     pub max_depth: Option<usize>,
+
+    /// Individual clean targets smaller than this many bytes are not reported;
+    /// larger ones are only sized up to it.
+    #[serde(default)]
+    pub target_min_size: Option<u64>,
+
+    /// List at most this many targets per project, keeping the largest.
+    /// Targets beyond the cap are summarized in the table but still count
+    /// toward the project's total and are still cleaned.
+    #[serde(default)]
+    pub max_targets: Option<usize>,
+
+    /// Maximum depth below a discovered project to look for nested projects.
+    #[serde(default)]
+    pub project_depth: Option<usize>,
+
+    /// Report each git repository as a single project, folding nested
+    /// projects into the repository root.
+    #[serde(default)]
+    pub ignore_vcs_roots: bool,
+
+    /// Don't descend into directories on a different filesystem than the
+    /// scan root (e.g. network mounts).
+    #[serde(default)]
+    pub one_file_system: bool,
+
+    /// Collect git remote/branch/dirty state for each project.
+    #[serde(default)]
+    pub git: bool,
+
+    /// List each Rust `target/` profile directory as its own target instead
+    /// of the whole `target/`.
+    #[serde(default)]
+    pub granular: bool,
+
+    /// Estimate directory sizes by sampling instead of walking every file.
+    #[serde(default)]
+    pub fast: bool,
+
+    /// Reuse target sizes from earlier scans: `off`, `mtime`, or `signature`.
+    #[serde(default)]
+    pub cache_mode: CacheMode,
+
+    /// Also report a top-level `.cache/` in every detected project, whatever
+    /// its kind, as a tool cache.
+    #[serde(default)]
+    pub generic_caches: bool,
+
+    /// Also report JetBrains `.idea/` and Visual Studio `.vs/` directories in
+    /// every detected project. Cleaning them resets local IDE state.
+    #[serde(default)]
+    pub ide_caches: bool,
+
+    /// Leave projects alone when their artifacts are smaller than their
+    /// source, which usually means an early or partial build.
+    #[serde(default)]
+    pub skip_smaller_than_source: bool,
+
+    /// Never draw the scanning spinner, even on a terminal.
+    #[serde(default)]
+    pub no_spinner: bool,
+
+    /// Report scan progress as newline-delimited JSON on stderr instead of
+    /// the spinner. Set by `--progress-json`, never saved.
+    #[serde(skip)]
+    pub progress_json: bool,
+
+    /// Descend into every directory except `.git`, ignoring the usual
+    /// skip list. Debug-only: set by `--no-skip-dirs`, never saved.
+    #[serde(skip)]
+    pub no_skip_dirs: bool,
+
+    /// User-defined project kinds, checked after the built-in ones.
+    #[serde(default)]
+    pub custom_kinds: Vec<CustomKind>,
+
+    /// What to run when no subcommand is given: `scan`, `summary`, or `clean`.
+    #[serde(default)]
+    pub default_command: DefaultCommand,
 }
 
 impl DevSweepConfig {
     /// Load config from the default location (~/.config/dev-sweep/config.json).
+    ///
+    /// An invalid config file (bad JSON, or an unknown value such as a
+    /// misspelled `default_command`) is reported on stderr and ignored.
     pub fn load() -> Self {
         let config_path = Self::config_path();
         if config_path.exists() {
             match std::fs::read_to_string(&config_path) {
-                Ok(contents) => serde_json::from_str(&contents).unwrap_or_default(),
+                Ok(contents) => Self::parse(&contents).unwrap_or_else(|e| {
+                    eprintln!(
+                        "  ⚠ Ignoring invalid config {}: {}",
+                        config_path.display(),
+                        e
+                    );
+                    Self::default()
+                }),
                 Err(_) => Self::default(),
             }
         } else {
@@ -38,6 +171,13 @@ impl DevSweepConfig {
         }
     }
 
+    /// Parse and validate the contents of a config file. `//` and `/* */`
+    /// comments are allowed (see [`strip_json_comments`]); [`save`](Self::save)
+    /// writes plain JSON, so they don't survive a save.
+    pub fn parse(contents: &str) -> anyhow::Result<Self> {
+        Ok(serde_json::from_str(&strip_json_comments(contents))?)
+    }
+
     /// Save config to the default location.
     pub fn save(&self) -> anyhow::Result<()> {
         let config_path = Self::config_path();
@@ -57,3 +197,49 @@ impl DevSweepConfig {
             .join("config.json")
     }
 }
+
+/// Remove `//` line comments and `/* */` block comments from JSON, leaving
+/// string contents (e.g. a `"//server/share"` path) alone. Each comment is
+/// replaced by a space, or by the newlines it spanned, so serde's error
+/// positions still point at the right line.
+pub fn strip_json_comments(json: &str) -> String {
+    let mut out = String::with_capacity(json.len());
+    let mut chars = json.chars().peekable();
+    let mut in_string = false;
+    while let Some(c) = chars.next() {
+        if in_string {
+            out.push(c);
+            match c {
+                '\\' => out.extend(chars.next()),
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match (c, chars.peek()) {
+            ('"', _) => {
+                in_string = true;
+                out.push(c);
+            }
+            ('/', Some('/')) => {
+                while chars.next_if(|&c| c != '\n').is_some() {}
+            }
+            ('/', Some('*')) => {
+                chars.next();
+                let mut prev = ' ';
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        out.push('\n');
+                    }
+                    if prev == '*' && c == '/' {
+                        break;
+                    }
+                    prev = c;
+                }
+                out.push(' ');
+            }
+            _ => out.push(c),
+        }
+    }
+    out
+}
@@ -0,0 +1,116 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::util::SizeFormat;
+
+/// Space on the filesystem holding a path, as reported by `df`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiskUsage {
+    /// Where the filesystem is mounted.
+    pub mount: String,
+    pub total_bytes: u64,
+    pub used_bytes: u64,
+    pub available_bytes: u64,
+}
+
+impl DiskUsage {
+    /// Percentage of the disk in use, the way `df` computes it: blocks reserved
+    /// for root count as neither used nor available.
+    pub fn percent_used(&self) -> f64 {
+        self.percent_used_with(self.used_bytes)
+    }
+
+    /// Percentage of the disk that would be in use after freeing `freed` bytes.
+    pub fn percent_used_after(&self, freed: u64) -> f64 {
+        self.percent_used_with(self.used_bytes.saturating_sub(freed))
+    }
+
+    /// Describe the effect of freeing `freed` bytes, e.g.
+    /// "bringing disk from 91% to 74% full".
+    pub fn projection(&self, freed: u64) -> String {
+        format!(
+            "bringing disk from {:.0}% to {:.0}% full",
+            self.percent_used(),
+            self.percent_used_after(freed)
+        )
+    }
+
+    fn percent_used_with(&self, used: u64) -> f64 {
+        let capacity = self.used_bytes + self.available_bytes;
+        if capacity == 0 {
+            return 0.0;
+        }
+        used as f64 / capacity as f64 * 100.0
+    }
+}
+
+/// A warning when `reclaimable` bytes is more than the whole disk holds, which
+/// no real scan can find: the same files were counted more than once, through
+/// symlinks or hardlinks, or from other mounted filesystems.
+pub fn implausible_total_warning(
+    reclaimable: u64,
+    usage: &DiskUsage,
+    sizes: SizeFormat,
+) -> Option<String> {
+    (reclaimable > usage.total_bytes).then(|| {
+        format!(
+            "Reclaimable space ({}) exceeds the capacity of {} ({}); sizes are likely \
+             double-counted through symlinks, hardlinks, or other mounts. Try --one-file-system.",
+            sizes.bytes(reclaimable),
+            usage.mount,
+            sizes.bytes(usage.total_bytes),
+        )
+    })
+}
+
+/// Query the filesystem holding `path`.
+///
+/// Returns `None` if `df` is unavailable (e.g. on Windows) or its output
+/// can't be parsed.
+pub fn disk_usage(path: &Path) -> Option<DiskUsage> {
+    let output = Command::new("df")
+        .args(["-P", "-k"])
+        .arg(path)
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    parse_df_output(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Query the filesystem shared by all `roots`.
+///
+/// Returns `None` if any root can't be queried or the roots live on different
+/// filesystems, since a single before/after percentage wouldn't mean much.
+pub fn shared_disk_usage(roots: &[PathBuf]) -> Option<DiskUsage> {
+    let mut usages = roots.iter().map(|r| disk_usage(r));
+    let first = usages.next()??;
+    for usage in usages {
+        if usage?.mount != first.mount {
+            return None;
+        }
+    }
+    Some(first)
+}
+
+/// Parse POSIX `df -P -k` output (a header line, then one line per filesystem).
+///
+/// Filesystem names and mount points may contain spaces, so the numeric
+/// columns are located by the `Capacity` field ending in `%`.
+pub fn parse_df_output(output: &str) -> Option<DiskUsage> {
+    let line = output.lines().nth(1)?;
+    let fields: Vec<&str> = line.split_whitespace().collect();
+
+    let capacity = (4..fields.len()).find(|&i| fields[i].ends_with('%'))?;
+    let kib = |i: usize| fields[i].parse::<u64>().ok().map(|n| n * 1024);
+
+    Some(DiskUsage {
+        mount: fields[capacity + 1..].join(" "),
+        total_bytes: kib(capacity - 3)?,
+        used_bytes: kib(capacity - 2)?,
+        available_bytes: kib(capacity - 1)?,
+    })
+}
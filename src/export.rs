@@ -0,0 +1,52 @@
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+
+use crate::scanner::ScannedProject;
+use crate::tui::display::{DisplayOptions, render_json, scan_json};
+
+/// File name for `project` under `--split-output`: `<name>-<hash>.json`, where
+/// the hash of the project's path keeps same-named projects apart. Characters
+/// that don't belong in a file name are replaced with `_`.
+pub fn split_output_file_name(project: &ScannedProject) -> String {
+    let name: String = project
+        .name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    let hash = path_hash(&project.path.to_string_lossy());
+    format!("{name}-{hash:016x}.json")
+}
+
+/// Write each of `projects` as its own JSON file into `dir` (created if
+/// missing), returning how many files were written.
+pub fn write_split_output(
+    dir: &Path,
+    projects: &[ScannedProject],
+    compact: bool,
+    opts: &DisplayOptions,
+) -> Result<usize> {
+    fs::create_dir_all(dir)
+        .with_context(|| format!("Failed to create output directory {}", dir.display()))?;
+    for project in projects {
+        let path = dir.join(split_output_file_name(project));
+        fs::write(&path, render_json(&scan_json(project, opts)?, compact)? + "\n")
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+    }
+    Ok(projects.len())
+}
+
+/// FNV-1a, so file names stay the same across runs and Rust versions (unlike
+/// `DefaultHasher`).
+fn path_hash(path: &str) -> u64 {
+    path.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
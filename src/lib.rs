@@ -1,6 +1,11 @@
+pub mod caches;
 pub mod cleaner;
 pub mod cli;
 pub mod config;
+pub mod disk;
+pub mod export;
 pub mod scanner;
+pub mod stamp;
+pub mod summary;
 pub mod tui;
 pub mod util;
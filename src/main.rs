@@ -1,13 +1,20 @@
 use std::path::PathBuf;
-use std::process;
+use std::time::Duration;
+use std::{env, process};
 
 use anyhow::Result;
 use clap::Parser;
 
-use dev_sweep::cli::commands::{cmd_clean, cmd_config, cmd_scan, cmd_summary};
-use dev_sweep::cli::{Cli, Commands};
+use dev_sweep::cli::commands::{
+    ScanContext, cmd_caches, cmd_clean, cmd_config, cmd_kinds, cmd_purge, cmd_scan, cmd_summary, cmd_watch,
+};
+use dev_sweep::cli::{Cli, Commands, expand_arg_files};
 use dev_sweep::config::DevSweepConfig;
-use dev_sweep::tui::colors::red_bold;
+use dev_sweep::stamp::{read_stamp, stamp_path};
+use dev_sweep::tui::colors::{blue, red_bold, yellow};
+use dev_sweep::tui::confirm::confirmer_for;
+use dev_sweep::tui::display::{Column, DisplayOptions};
+use dev_sweep::util::SizeFormat;
 
 fn main() {
     if let Err(e) = run() {
@@ -17,54 +24,129 @@ fn main() {
 }
 
 fn run() -> Result<()> {
-    let cli = Cli::parse();
-    let config = DevSweepConfig::load();
+    let mut cli = Cli::parse_from(expand_arg_files(env::args_os())?);
+    let mut config = DevSweepConfig::load();
 
     // CLI flags take precedence over config; config provides defaults.
     let max_depth = cli.max_depth.or(config.max_depth);
+    config.project_depth = cli.project_depth.or(config.project_depth);
+    config.target_min_size = cli.target_min_size.or(config.target_min_size);
+    config.max_targets = cli.max_targets.or(config.max_targets);
+    config.protect_paths.append(&mut cli.protect);
+    config.ignore_vcs_roots |= cli.ignore_vcs_roots;
+    config.one_file_system |= cli.one_file_system;
+    config.git |= cli.git;
+    config.granular |= cli.granular;
+    config.fast |= cli.fast;
+    config.cache_mode = cli.cache_mode.unwrap_or(config.cache_mode);
+    config.no_spinner |= cli.no_spinner || cli.count_only;
+    config.progress_json = cli.progress_json;
+    config.generic_caches |= cli.generic_caches;
+    config.ide_caches |= cli.ide_caches;
+    config.skip_smaller_than_source |= cli.skip_smaller_than_source;
+    if config.ide_caches {
+        eprintln!(
+            "  {} --ide-caches includes .idea/ and .vs/; cleaning them resets local IDE settings \
+             such as run configurations and window layout.",
+            yellow("⚠")
+        );
+    }
+    config.no_skip_dirs = cli.no_skip_dirs;
+    if config.no_skip_dirs {
+        eprintln!(
+            "  {} --no-skip-dirs descends into every directory but .git; expect a slow scan.",
+            yellow("⚠")
+        );
+    }
 
-    let scan_path = resolve_scan_path(&cli, &config)?;
-
-    match cli.command.unwrap_or(Commands::Scan) {
-        Commands::Scan => cmd_scan(
-            &scan_path,
-            max_depth,
-            cli.older_than.as_deref(),
-            cli.json,
-            &config,
-        ),
-        Commands::Clean { all, dry_run } => cmd_clean(
-            &scan_path,
-            max_depth,
-            cli.older_than.as_deref(),
-            all,
-            dry_run,
-            cli.json,
-            &config,
-        ),
-        Commands::Summary => cmd_summary(
-            &scan_path,
-            max_depth,
-            cli.older_than.as_deref(),
-            cli.json,
-            &config,
-        ),
+
+    let scan_roots = resolve_scan_roots(&cli, &config)?;
+    let stamp = stamp_path();
+    let modified_since = cli.since_last_clean.then(|| read_stamp(&stamp)).flatten();
+    if cli.since_last_clean && modified_since.is_none() {
+        eprintln!("  {} No clean recorded yet; showing everything.", blue("ℹ"));
+    }
+
+    let columns = if cli.columns.is_empty() {
+        Column::ALL.to_vec()
+    } else {
+        cli.columns.clone()
+    };
+
+    let ctx = ScanContext {
+        roots: &scan_roots,
+        max_depth,
+        older_than: cli.older_than.as_deref(),
+        name: cli.name.as_deref(),
+        modified_since,
+        json: cli.json,
+        compact: cli.compact,
+        format: cli.format,
+        columns: &columns,
+        display: DisplayOptions {
+            sizes: SizeFormat {
+                exact: cli.exact_bytes,
+                precision: cli.precision,
+            },
+            restore_cost: cli.estimate_restore_cost,
+            max_targets: config.max_targets,
+        },
+        print0: cli.print0,
+        count_only: cli.count_only,
+        split_output: cli.split_output.as_deref(),
+        from: cli.from.as_deref(),
+        fail_on_found: cli.fail_on_found,
+        allow_sweeping_root: cli.allow_sweeping_root,
+        depth_report: cli.depth_report,
+        stream: cli.stream,
+        report_unreadable: cli.report_unreadable,
+        stamp: Some(&stamp),
+        config: &config,
+    };
+
+    // A timeout of 0 waits forever.
+    let prompt_timeout = cli.prompt_timeout.filter(|&secs| secs > 0).map(Duration::from_secs);
+    let command = cli
+        .command
+        .take()
+        .unwrap_or_else(|| config.default_command.into());
+
+    match command {
+        Commands::Scan => cmd_scan(&ctx),
+        Commands::Clean(args) => {
+            cmd_clean(&ctx, &args, &*confirmer_for(args.confirm, prompt_timeout))
+        }
+        Commands::Purge(args) => cmd_purge(&ctx, &args),
+        Commands::Summary(args) => cmd_summary(&ctx, &args),
+        Commands::Watch {
+            interval,
+            threshold,
+        } => cmd_watch(&ctx, interval, threshold),
+        Commands::Kinds => cmd_kinds(ctx.json, ctx.compact),
+        Commands::Caches(args) => {
+            cmd_caches(&ctx, &args, &*confirmer_for(args.confirm, prompt_timeout))
+        }
         Commands::Config { show, reset } => cmd_config(show, reset),
     }
 }
 
-/// Determine the scan path from CLI args, config defaults, or the current directory.
+/// Determine the scan roots from CLI args, config defaults, or the current directory.
 ///
-/// Priority: CLI `--path` > config `default_roots[0]` > current directory.
-fn resolve_scan_path(cli: &Cli, config: &DevSweepConfig) -> Result<PathBuf> {
+/// Priority: CLI `--path` > config `default_roots` (all of them) > current directory.
+fn resolve_scan_roots(cli: &Cli, config: &DevSweepConfig) -> Result<Vec<PathBuf>> {
     let raw = if let Some(ref p) = cli.path {
-        p.clone()
-    } else if let Some(first) = config.default_roots.first() {
-        first.clone()
+        vec![p.clone()]
+    } else if !config.default_roots.is_empty() {
+        config.default_roots.clone()
     } else {
-        std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."))
+        vec![std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."))]
     };
 
+    raw.into_iter().map(expand_root).collect()
+}
+
+/// Expand a leading `~` and check that the root is an existing directory.
+fn expand_root(raw: PathBuf) -> Result<PathBuf> {
     let expanded = if raw.starts_with("~") {
         dirs::home_dir()
             .unwrap_or_default()
@@ -0,0 +1,148 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::config::{CacheMode, DevSweepConfig};
+
+/// Cheap summary of a directory's state, compared to decide whether a cached
+/// size is still good.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Fingerprint {
+    /// The directory's own mtime, in nanoseconds since the epoch.
+    pub mtime: u64,
+    /// With [`CacheMode::Signature`]: the number of top-level entries...
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub entries: Option<u64>,
+    /// ...and the sum of their mtimes, which moves when a file is rewritten
+    /// in place (which leaves the directory's mtime alone).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub entries_mtime: Option<u64>,
+}
+
+/// A directory's measured size, valid while its [`Fingerprint`] matches.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CacheEntry {
+    fingerprint: Fingerprint,
+    size_bytes: u64,
+    newest: Option<SystemTime>,
+}
+
+/// Sizes of clean targets from earlier scans (`--cache-mode`), so unchanged
+/// directories don't have to be walked again.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SizeCache {
+    entries: HashMap<PathBuf, CacheEntry>,
+}
+
+impl SizeCache {
+    /// Where the cache is kept: `.devclean-size-cache.json`, next to the
+    /// config file.
+    pub fn path() -> PathBuf {
+        DevSweepConfig::config_path().with_file_name(".devclean-size-cache.json")
+    }
+
+    /// Read the cache at `path`. A missing or unreadable cache is just empty.
+    pub fn load(path: &Path) -> Self {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    /// Write the cache to `path`.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_string(self)?;
+        fs::write(path, json)
+            .with_context(|| format!("Failed to write size cache {}", path.display()))
+    }
+
+    /// The cached size and newest mtime of `dir`, if it hasn't changed (as
+    /// far as `mode` can tell) since it was stored.
+    pub fn get(&self, dir: &Path, mode: CacheMode) -> Option<(u64, Option<SystemTime>)> {
+        let entry = self.entries.get(dir)?;
+        (fingerprint(dir, mode)? == entry.fingerprint).then_some((entry.size_bytes, entry.newest))
+    }
+
+    /// Remember the size and newest mtime measured for `dir`.
+    pub fn insert(
+        &mut self,
+        dir: &Path,
+        mode: CacheMode,
+        size_bytes: u64,
+        newest: Option<SystemTime>,
+    ) {
+        if let Some(fingerprint) = fingerprint(dir, mode) {
+            let entry = CacheEntry {
+                fingerprint,
+                size_bytes,
+                newest,
+            };
+            self.entries.insert(dir.to_path_buf(), entry);
+        }
+    }
+}
+
+/// Fingerprint `dir` the way `mode` compares it. `None` for
+/// [`CacheMode::Off`] or if `dir` can't be read.
+pub fn fingerprint(dir: &Path, mode: CacheMode) -> Option<Fingerprint> {
+    let mtime = nanos(fs::metadata(dir).ok()?.modified().ok()?);
+    let (entries, entries_mtime) = match mode {
+        CacheMode::Off => return None,
+        CacheMode::Mtime => (None, None),
+        CacheMode::Signature => {
+            let (count, sum) = fs::read_dir(dir)
+                .ok()?
+                .filter_map(|e| e.ok())
+                .fold((0u64, 0u64), |(count, sum), entry| {
+                    let modified = entry.metadata().ok().and_then(|m| m.modified().ok());
+                    (count + 1, sum.wrapping_add(modified.map_or(0, nanos)))
+                });
+            (Some(count), Some(sum))
+        }
+    };
+    Some(Fingerprint {
+        mtime,
+        entries,
+        entries_mtime,
+    })
+}
+
+fn nanos(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_nanos() as u64)
+}
+
+/// The cache shared by every scan in this process, loaded on first use.
+static SIZE_CACHE: LazyLock<Mutex<SizeCache>> =
+    LazyLock::new(|| Mutex::new(SizeCache::load(&SizeCache::path())));
+
+/// Size `dir` with `measure`, unless the process-wide cache already holds an
+/// up-to-date entry for it. With [`CacheMode::Off`] this is just `measure()`.
+pub fn cached_dir_size(
+    dir: &Path,
+    mode: CacheMode,
+    measure: impl FnOnce() -> (u64, Option<SystemTime>),
+) -> (u64, Option<SystemTime>) {
+    if mode == CacheMode::Off {
+        return measure();
+    }
+    if let Some(hit) = SIZE_CACHE.lock().unwrap().get(dir, mode) {
+        return hit;
+    }
+    let (size, newest) = measure();
+    SIZE_CACHE.lock().unwrap().insert(dir, mode, size, newest);
+    (size, newest)
+}
+
+/// Persist the process-wide cache for the next run.
+pub fn save_size_cache() -> Result<()> {
+    SIZE_CACHE.lock().unwrap().save(&SizeCache::path())
+}
@@ -0,0 +1,68 @@
+use std::path::Path;
+use std::process::Command;
+
+/// Git context for a project directory, gathered by shelling out to `git`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GitContext {
+    /// URL of the `origin` remote (or the first remote, if there is no `origin`).
+    pub remote: Option<String>,
+    /// The currently checked-out branch, if HEAD is not detached.
+    pub branch: Option<String>,
+    /// Whether the project directory has uncommitted or untracked changes.
+    pub dirty: Option<bool>,
+}
+
+/// Collect git context for `dir`.
+///
+/// Returns `None` if `git` is unavailable or `dir` is not inside a work tree.
+pub fn git_context(dir: &Path) -> Option<GitContext> {
+    if git(dir, &["rev-parse", "--is-inside-work-tree"])?.as_str() != "true" {
+        return None;
+    }
+
+    let remote = git(dir, &["remote", "get-url", "origin"]).or_else(|| {
+        let first = git(dir, &["remote"])?.lines().next()?.to_string();
+        git(dir, &["remote", "get-url", &first])
+    });
+
+    let branch = git(dir, &["symbolic-ref", "--short", "-q", "HEAD"]);
+
+    let dirty = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(["status", "--porcelain", "--", "."])
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| !o.stdout.is_empty());
+
+    Some(GitContext {
+        remote,
+        branch,
+        dirty,
+    })
+}
+
+/// Whether git tracks any file under `dir`. `false` outside a work tree or
+/// without `git`.
+pub fn has_tracked_files(dir: &Path) -> bool {
+    git(dir, &["ls-files", "--", "."]).is_some()
+}
+
+/// Run a git command in `dir` and return its trimmed stdout, or `None` on failure
+/// or empty output.
+fn git(dir: &Path, args: &[&str]) -> Option<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!stdout.is_empty()).then_some(stdout)
+}
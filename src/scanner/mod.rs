@@ -1,5 +1,9 @@
+pub mod cache;
+pub mod git;
 pub mod project;
 pub mod walk;
 
-pub use project::{CleanTarget, ProjectKind, ScannedProject};
-pub use walk::scan_directory;
+pub use project::{
+    CleanTarget, KindRules, PatternRule, ProjectKind, ScannedProject, TargetCategory, TargetKind,
+};
+pub use walk::{scan_directories, scan_directory};
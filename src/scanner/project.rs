@@ -1,20 +1,36 @@
 use std::fmt;
 use std::path::PathBuf;
+use std::str::FromStr;
 
+use anyhow::anyhow;
 use chrono::{DateTime, Local};
 use serde::{Deserialize, Serialize};
 
+use crate::util::glob_match;
+
 /// The kind of development project detected.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum ProjectKind {
     Rust,
+    /// Checked before [`Node`](Self::Node): Bun projects carry a
+    /// `package.json` too.
+    Bun,
     Node,
     Python,
     Java,
+    /// Checked before [`DotNet`](Self::DotNet): Godot projects using C#
+    /// carry a `*.csproj` too, and build into the same `bin/` and `obj/`.
+    Godot,
     DotNet,
     Go,
     Zig,
+    /// Checked before [`CMake`](Self::CMake): Conan projects usually carry a
+    /// `CMakeLists.txt` too, and Conan's targets are a superset.
+    Conan,
     CMake,
+    /// qmake-based Qt projects. Qt Creator shadow-builds into `build-*`
+    /// directories next to the sources.
+    Qt,
     Swift,
     Elixir,
     Haskell,
@@ -22,8 +38,18 @@ pub enum ProjectKind {
     Ruby,
     Scala,
     Unity,
-    Godot,
     Terraform,
+    Latex,
+    /// Generated Sphinx or MkDocs documentation. Checked after the language
+    /// kinds, so a Python package with a `docs/conf.py` stays a Python project.
+    Docs,
+    /// Only project-local `.docker/` build caches are cleaned — never the
+    /// global Docker daemon storage (images, volumes, build cache).
+    Docker,
+    /// A user-defined kind from `custom_kinds` in the config. Its markers and
+    /// cleanable directories live in the config, and its name in
+    /// [`ScannedProject::custom_kind`].
+    Custom,
 }
 
 impl ProjectKind {
@@ -31,13 +57,23 @@ impl ProjectKind {
     pub fn marker_files(&self) -> &[&str] {
         match self {
             Self::Rust => &["Cargo.toml"],
+            Self::Bun => &["bun.lockb", "bunfig.toml"],
             Self::Node => &["package.json"],
             Self::Python => &["pyproject.toml", "setup.py", "requirements.txt"],
-            Self::Java => &["pom.xml", "build.gradle", "build.gradle.kts"],
+            Self::Java => &[
+                "pom.xml",
+                "build.gradle",
+                "build.gradle.kts",
+                "settings.gradle",
+                "settings.gradle.kts",
+            ],
+            Self::Godot => &["project.godot"],
             Self::DotNet => &["*.csproj", "*.fsproj", "*.sln"],
             Self::Go => &["go.mod"],
             Self::Zig => &["build.zig"],
+            Self::Conan => &["conanfile.txt", "conanfile.py"],
             Self::CMake => &["CMakeLists.txt"],
+            Self::Qt => &["*.pro"],
             Self::Swift => &["Package.swift"],
             Self::Elixir => &["mix.exs"],
             Self::Haskell => &["stack.yaml", "*.cabal"],
@@ -45,45 +81,192 @@ impl ProjectKind {
             Self::Ruby => &["Gemfile"],
             Self::Scala => &["build.sbt"],
             Self::Unity => &["ProjectSettings/ProjectVersion.txt"],
-            Self::Godot => &["project.godot"],
             Self::Terraform => &["main.tf", "*.tf"],
+            Self::Latex => &["*.tex", "latexmkrc", ".latexmkrc"],
+            Self::Docs => &["mkdocs.yml", "docs/conf.py"],
+            Self::Docker => &["Dockerfile", "docker-compose.yml", "compose.yaml"],
+            Self::Custom => &[],
         }
     }
 
     /// Returns the directories that can be safely cleaned for this project kind.
+    /// Glob patterns (`*suffix`, `prefix-*`) match directories only, like
+    /// exact names.
     pub fn cleanable_dirs(&self) -> &[&str] {
         match self {
             Self::Rust => &["target"],
-            Self::Node => &["node_modules", ".next", ".nuxt", "dist", ".cache"],
-            Self::Python => &["__pycache__", ".venv", "venv", ".tox", "*.egg-info", ".mypy_cache", ".pytest_cache"],
+            Self::Bun => &["node_modules", ".bun"],
+            Self::Node => &[
+                "node_modules",
+                ".next",
+                ".nuxt",
+                "dist",
+                ".cache",
+                "coverage",
+                ".nyc_output",
+                ".parcel-cache",
+                ".turbo",
+                ".vite",
+                ".svelte-kit",
+                ".angular",
+            ],
+            Self::Python => &[
+                "__pycache__",
+                ".venv",
+                "venv",
+                ".tox",
+                "*.egg-info",
+                ".mypy_cache",
+                ".pytest_cache",
+                ".ruff_cache",
+                "htmlcov",
+            ],
             Self::Java => &["target", "build", ".gradle"],
+            Self::Godot => &[".godot", ".mono"],
             Self::DotNet => &["bin", "obj"],
             Self::Go => &[],  // Go modules are shared, not per-project artifacts
             Self::Zig => &["zig-cache", "zig-out"],
-            Self::CMake => &["build", "cmake-build-debug", "cmake-build-release"],
+            Self::Conan => &["build", ".conan"],
+            Self::CMake => &["build", "cmake-build-*"],
+            Self::Qt => &["build", "build-*"],
             Self::Swift => &[".build"],
             Self::Elixir => &["_build", "deps"],
             Self::Haskell => &[".stack-work"],
             Self::Dart => &[".dart_tool", "build"],
-            Self::Ruby => &["vendor/bundle"],
-            Self::Scala => &["target", "project/target"],
+            Self::Ruby => &["vendor/bundle", "coverage"],
+            Self::Scala => &["target", "project/target", ".bloop", ".bsp", ".metals"],
             Self::Unity => &["Library", "Temp", "Obj", "Logs"],
-            Self::Godot => &[".godot"],
             Self::Terraform => &[".terraform"],
+            Self::Latex => &[],
+            Self::Docs => &["site", "_build", "docs/_build"],
+            Self::Docker => &[".docker"],
+            Self::Custom => &[],
+        }
+    }
+
+    /// Returns directory names that are cleanable wherever they appear below
+    /// the project root, not just at its top level.
+    pub fn recursive_dirs(&self) -> &[&str] {
+        match self {
+            // Monorepos keep these per package, anywhere in the tree.
+            Self::Python => &["__pycache__", ".mypy_cache", ".pytest_cache", ".ruff_cache"],
+            Self::Terraform => &[".terragrunt-cache"],
+            _ => &[],
+        }
+    }
+
+    /// Returns the files (exact names or globs, at the project root) that can
+    /// be safely cleaned, for artifacts that are loose files rather than
+    /// directories.
+    pub fn cleanable_files(&self) -> &[&str] {
+        match self {
+            Self::Node => &["lcov.info"],
+            Self::Python => &[".coverage", "coverage.xml"],
+            Self::Latex => &[
+                "*.aux",
+                "*.log",
+                "*.out",
+                "*.toc",
+                "*.lof",
+                "*.lot",
+                "*.bbl",
+                "*.blg",
+                "*.fls",
+                "*.fdb_latexmk",
+                "*.synctex.gz",
+            ],
+            // In-source qmake builds leave generated makefiles, moc/uic/rcc
+            // output, and object files next to the .pro file.
+            Self::Qt => &[
+                "Makefile*",
+                "*.qmake.stash",
+                "moc_*",
+                "ui_*.h",
+                "qrc_*.cpp",
+                "*.o",
+            ],
+            _ => &[],
+        }
+    }
+
+    /// Classify one of this kind's cleanable patterns (as returned by
+    /// [`cleanable_dirs`](Self::cleanable_dirs),
+    /// [`recursive_dirs`](Self::recursive_dirs) or
+    /// [`cleanable_files`](Self::cleanable_files)). A pattern that isn't one
+    /// of this kind's own, such as a custom kind's, is
+    /// [`Unclassified`](TargetCategory::Unclassified) unless its name is known.
+    pub fn target_category(&self, pattern: &str) -> TargetCategory {
+        match (self, pattern) {
+            (
+                _,
+                "node_modules" | ".venv" | "venv" | ".tox" | "vendor/bundle" | ".terraform"
+                | ".terragrunt-cache" | "__pypackages__",
+            ) => TargetCategory::DependencyCache,
+            (Self::Elixir, "deps") | (Self::Conan, ".conan") | (Self::Bun, ".bun") => {
+                TargetCategory::DependencyCache
+            }
+            (
+                _,
+                "__pycache__" | ".mypy_cache" | ".pytest_cache" | ".ruff_cache" | ".cache"
+                | ".gradle" | "zig-cache" | ".dart_tool" | ".godot" | "Library" | "Temp" | "Logs"
+                | ".docker" | ".bloop" | ".bsp" | ".metals" | ".mono" | ".parcel-cache" | ".turbo"
+                | ".vite" | ".svelte-kit" | ".angular",
+            ) => TargetCategory::ToolCache,
+            (
+                _,
+                "coverage" | ".nyc_output" | "htmlcov" | ".coverage" | "coverage.xml" | "lcov.info",
+            ) => TargetCategory::CoverageArtifacts,
+            (_, ".sccache" | ".ccache") => TargetCategory::CompilerCache,
+            _ if self.lists(pattern) => TargetCategory::BuildOutput,
+            _ => TargetCategory::Unclassified,
+        }
+    }
+
+    /// Whether `pattern` is one of this kind's own built-in patterns.
+    fn lists(&self, pattern: &str) -> bool {
+        self.cleanable_dirs()
+            .iter()
+            .chain(self.recursive_dirs())
+            .chain(self.cleanable_files())
+            .any(|p| *p == pattern)
+    }
+
+    /// This kind's detection and cleaning rules as plain data.
+    pub fn rules(&self) -> KindRules {
+        let with_category = |patterns: &[&str]| {
+            patterns
+                .iter()
+                .map(|p| PatternRule {
+                    pattern: p.to_string(),
+                    category: self.target_category(p),
+                })
+                .collect()
+        };
+        KindRules {
+            kind: *self,
+            name: self.to_string(),
+            marker_files: self.marker_files().iter().map(|m| m.to_string()).collect(),
+            cleanable_dirs: with_category(self.cleanable_dirs()),
+            recursive_dirs: with_category(self.recursive_dirs()),
+            cleanable_files: with_category(self.cleanable_files()),
         }
     }
 
-    /// Returns all known project kinds.
+    /// Returns all built-in project kinds (everything except [`ProjectKind::Custom`]).
     pub fn all() -> &'static [ProjectKind] {
         &[
             Self::Rust,
+            Self::Bun,
             Self::Node,
             Self::Python,
             Self::Java,
+            Self::Godot,
             Self::DotNet,
             Self::Go,
             Self::Zig,
+            Self::Conan,
             Self::CMake,
+            Self::Qt,
             Self::Swift,
             Self::Elixir,
             Self::Haskell,
@@ -91,23 +274,53 @@ impl ProjectKind {
             Self::Ruby,
             Self::Scala,
             Self::Unity,
-            Self::Godot,
             Self::Terraform,
+            Self::Latex,
+            Self::Docs,
+            Self::Docker,
         ]
     }
 }
 
+/// One kind's rules, as listed by `kinds --json`, so other tools can replicate
+/// dev-sweep's detection without linking against it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KindRules {
+    pub kind: ProjectKind,
+    /// Display name (e.g. "Node.js").
+    pub name: String,
+    /// Files (or `*suffix` globs, or `sub/path`s) whose presence marks a project.
+    pub marker_files: Vec<String>,
+    /// Directories cleaned at the project root.
+    pub cleanable_dirs: Vec<PatternRule>,
+    /// Directories cleaned wherever they appear below the project root.
+    pub recursive_dirs: Vec<PatternRule>,
+    /// Loose files cleaned at the project root.
+    pub cleanable_files: Vec<PatternRule>,
+}
+
+/// A cleanable pattern and what sort of artifact it matches.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PatternRule {
+    pub pattern: String,
+    pub category: TargetCategory,
+}
+
 impl fmt::Display for ProjectKind {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let name = match self {
             Self::Rust => "Rust",
+            Self::Bun => "Bun",
             Self::Node => "Node.js",
             Self::Python => "Python",
             Self::Java => "Java",
+            Self::Godot => "Godot",
             Self::DotNet => ".NET",
             Self::Go => "Go",
             Self::Zig => "Zig",
+            Self::Conan => "Conan",
             Self::CMake => "CMake",
+            Self::Qt => "Qt",
             Self::Swift => "Swift",
             Self::Elixir => "Elixir",
             Self::Haskell => "Haskell",
@@ -115,22 +328,148 @@ impl fmt::Display for ProjectKind {
             Self::Ruby => "Ruby",
             Self::Scala => "Scala",
             Self::Unity => "Unity",
-            Self::Godot => "Godot",
             Self::Terraform => "Terraform",
+            Self::Latex => "LaTeX",
+            Self::Docs => "Docs",
+            Self::Docker => "Docker",
+            Self::Custom => "Custom",
         };
         write!(f, "{name}")
     }
 }
 
-/// A directory within a project that can be cleaned.
+/// Parses the [`Display`](fmt::Display) name (`"Node.js"`, `".NET"`), the
+/// variant name (`"DotNet"`), or a common alias (`"node"`, `"c#"`), ignoring
+/// case. Round-trips with `to_string()` for every kind.
+impl FromStr for ProjectKind {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let wanted = s.trim().to_ascii_lowercase();
+
+        let alias = match wanted.as_str() {
+            "node" | "nodejs" | "js" | "javascript" | "typescript" => Some(Self::Node),
+            "py" => Some(Self::Python),
+            "dotnet" | "c#" | "csharp" | "f#" | "fsharp" => Some(Self::DotNet),
+            "golang" => Some(Self::Go),
+            "tex" => Some(Self::Latex),
+            "tf" | "terragrunt" => Some(Self::Terraform),
+            "sphinx" | "mkdocs" => Some(Self::Docs),
+            "qmake" => Some(Self::Qt),
+            _ => None,
+        };
+
+        alias
+            .or_else(|| {
+                Self::all()
+                    .iter()
+                    .chain([&Self::Custom])
+                    .copied()
+                    .find(|kind| {
+                        kind.to_string().to_ascii_lowercase() == wanted
+                            || format!("{kind:?}").to_ascii_lowercase() == wanted
+                    })
+            })
+            .ok_or_else(|| {
+                let names: Vec<String> = Self::all().iter().map(|k| k.to_string()).collect();
+                anyhow!(
+                    "Unknown project type \"{s}\" (expected one of: {})",
+                    names.join(", ")
+                )
+            })
+    }
+}
+
+/// A directory (or single file) within a project that can be cleaned.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CleanTarget {
-    /// Absolute path to the cleanable directory.
+    /// Absolute path to the cleanable directory or file.
     pub path: PathBuf,
     /// Display name (e.g. "node_modules", "target").
     pub name: String,
     /// Size in bytes.
     pub size_bytes: u64,
+    /// Whether the target is a directory or a single file.
+    pub kind: TargetKind,
+    /// What sort of artifact this is, and so what it costs to get back.
+    pub category: TargetCategory,
+    /// Whether `size_bytes` was extrapolated from a sample (`--fast`).
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub estimated: bool,
+    /// Whether `size_bytes` is only a lower bound: sizing stopped once the
+    /// target reached `--target-min-size`.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub at_least: bool,
+    /// When the newest file inside the target was written.
+    #[serde(default)]
+    pub last_modified: DateTime<Local>,
+    /// Whether this is the Python environment activated in the current shell
+    /// (`$VIRTUAL_ENV`), which is only cleaned with `clean --force`.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub active: bool,
+    /// Whether this `build/` may be committed source rather than output (it
+    /// holds git-tracked files or a `.gitkeep`), so `--safe` skips it. Only
+    /// `clean` and `purge` check, via `mark_ambiguous_builds`.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub ambiguous: bool,
+}
+
+/// Broad classification of a clean target.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TargetCategory {
+    /// Compiled or generated output, rebuilt locally from source.
+    BuildOutput,
+    /// Downloaded dependencies; restoring them needs the network.
+    DependencyCache,
+    /// Tool-managed caches that are regenerated automatically.
+    ToolCache,
+    /// Coverage data and reports left behind by test runs.
+    CoverageArtifacts,
+    /// Compiler caches such as sccache and ccache; deleting one only makes
+    /// the next build slower.
+    CompilerCache,
+    /// A user-listed target (a custom kind's pattern or an entry in
+    /// `DEVCLEAN_EXTRA_TARGETS`) that fits none of the above. `purge` leaves
+    /// it alone, since nothing says it can be rebuilt.
+    Unclassified,
+}
+
+impl fmt::Display for TargetCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::BuildOutput => "build output",
+            Self::DependencyCache => "dependency cache",
+            Self::ToolCache => "tool cache",
+            Self::CoverageArtifacts => "coverage",
+            Self::CompilerCache => "compiler cache",
+            Self::Unclassified => "unclassified",
+        };
+        write!(f, "{name}")
+    }
+}
+
+impl TargetCategory {
+    /// What it takes to get a target of this category back after cleaning,
+    /// shown with `--estimate-restore-cost`.
+    pub fn restore_cost(self) -> &'static str {
+        match self {
+            Self::BuildOutput => "local rebuild",
+            Self::DependencyCache => "network restore",
+            Self::ToolCache => "regenerated automatically",
+            Self::CoverageArtifacts => "rerun tests",
+            Self::CompilerCache => "slower next build",
+            Self::Unclassified => "unknown",
+        }
+    }
+}
+
+/// What kind of filesystem entry a [`CleanTarget`] is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TargetKind {
+    Dir,
+    File,
 }
 
 /// A discovered developer project on disk.
@@ -138,6 +477,13 @@ pub struct CleanTarget {
 pub struct ScannedProject {
     /// The project root directory.
     pub path: PathBuf,
+    /// The scan root this project was discovered under (the project root
+    /// itself when analyzed directly).
+    #[serde(default)]
+    pub scan_root: PathBuf,
+    /// Walk depth of the project root below `scan_root` (0 = the root itself).
+    #[serde(default)]
+    pub depth: usize,
     /// The detected project kind.
     pub kind: ProjectKind,
     /// A human-friendly project name (usually the directory name).
@@ -148,4 +494,73 @@ pub struct ScannedProject {
     pub clean_targets: Vec<CleanTarget>,
     /// Total reclaimable bytes across all clean targets.
     pub total_cleanable_bytes: u64,
+    /// URL of the project's git remote (only collected with `--git`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub git_remote: Option<String>,
+    /// The project's checked-out git branch (only collected with `--git`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub git_branch: Option<String>,
+    /// Whether the project has uncommitted changes (only collected with `--git`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub git_dirty: Option<bool>,
+    /// The name of the user-defined kind, when `kind` is [`ProjectKind::Custom`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub custom_kind: Option<String>,
+    /// Paths inside the clean targets that couldn't be read while sizing, with
+    /// the reason. When non-empty, `total_cleanable_bytes` is an undercount.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub unreadable: Vec<(PathBuf, String)>,
+    /// Size of everything in the project outside its clean targets and the
+    /// directories a scan skips. Only measured with `skip_smaller_than_source`,
+    /// and only until it exceeds `total_cleanable_bytes`, so a larger value is
+    /// a lower bound.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_bytes: Option<u64>,
+}
+
+impl ScannedProject {
+    /// The kind shown to users: the custom kind's name, or the built-in kind.
+    pub fn kind_label(&self) -> String {
+        match &self.custom_kind {
+            Some(name) => name.clone(),
+            None => self.kind.to_string(),
+        }
+    }
+
+    /// Whether the project matches a `--name` filter: a case-insensitive
+    /// substring of its name or path, or a glob (`*`, `?`) over either.
+    pub fn matches_name(&self, filter: &str) -> bool {
+        let filter = filter.to_lowercase();
+        let name = self.name.to_lowercase();
+        let path = self.path.display().to_string().to_lowercase();
+        if filter.contains(['*', '?']) {
+            glob_match(&filter, &name) || glob_match(&filter, &path)
+        } else {
+            name.contains(&filter) || path.contains(&filter)
+        }
+    }
+
+    /// Whether the artifacts are smaller than the source they were built from
+    /// (false when the source wasn't measured).
+    pub fn artifacts_smaller_than_source(&self) -> bool {
+        self.source_bytes
+            .is_some_and(|source| self.total_cleanable_bytes < source)
+    }
+
+    /// Whether any target's size is a `--fast` estimate or a lower bound
+    /// rather than exact.
+    pub fn is_estimate(&self) -> bool {
+        self.clean_targets.iter().any(|t| t.estimated || t.at_least)
+    }
+
+    /// Whether everything cleanable here is a dependency cache, so cleaning
+    /// means a network download to restore the project (e.g. a lone
+    /// `node_modules/`).
+    pub fn needs_network_restore(&self) -> bool {
+        !self.clean_targets.is_empty()
+            && self
+                .clean_targets
+                .iter()
+                .all(|t| t.category == TargetCategory::DependencyCache)
+    }
 }
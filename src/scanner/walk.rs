@@ -1,17 +1,26 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use std::env;
+use std::ffi::OsStr;
 use std::fs;
-use std::io::{self, Write};
+use std::io::{self, BufRead, Write};
+use std::panic;
 use std::path::{Path, PathBuf};
-use std::sync::LazyLock;
+use std::sync::{LazyLock, mpsc};
+use std::thread;
 use std::time::SystemTime;
 
 use anyhow::Result;
 use chrono::{DateTime, Local};
+use ignore::gitignore::Gitignore;
+use rayon::iter::Either;
 use rayon::prelude::*;
 use walkdir::WalkDir;
 
-use super::project::{CleanTarget, ProjectKind, ScannedProject};
-use crate::config::DevSweepConfig;
+use super::cache::cached_dir_size;
+use super::git::{git_context, has_tracked_files};
+use super::project::{CleanTarget, ProjectKind, ScannedProject, TargetCategory, TargetKind};
+use crate::config::{CacheMode, CustomKind, DevSweepConfig};
+use crate::util::glob_match;
 
 /// Directory names to skip during scanning (build artifacts, VCS, caches, etc.).
 ///
@@ -35,33 +44,86 @@ static SKIP_DIRS: LazyLock<HashSet<&'static str>> = LazyLock::new(|| {
         ".build",
         "zig-cache",
         "zig-out",
+        ".sccache",
+        ".ccache",
     ])
 });
 
-/// A simple spinner for terminal feedback.
-struct Spinner {
+/// Per-directory ignore file (gitignore syntax) listing paths dev-sweep must
+/// never clean or descend into.
+pub const IGNORE_FILE: &str = ".devcleanignore";
+
+/// Paths that couldn't be read during a scan, each with the reason.
+pub type Unreadable = Vec<(PathBuf, String)>;
+
+/// A simple spinner for terminal feedback. With no writer, every call is a no-op.
+///
+/// With `json` set (`--progress-json`), it writes newline-delimited JSON
+/// events instead of drawing: `{"phase":"walk","dirs":N}` while walking,
+/// `{"phase":"analyze","done":N,"total":M}` while sizing, and
+/// `{"phase":"done"}` at the end. Free-form messages are dropped.
+struct Spinner<'w> {
     frames: &'static [&'static str],
     idx: usize,
+    out: Option<&'w mut dyn Write>,
+    json: bool,
 }
 
-impl Spinner {
-    fn new() -> Self {
+impl<'w> Spinner<'w> {
+    fn new(out: Option<&'w mut dyn Write>, json: bool) -> Self {
         Self {
             frames: &["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"],
             idx: 0,
+            out,
+            json,
         }
     }
 
     fn tick(&mut self, msg: &str) {
+        if self.json {
+            return;
+        }
+        let Some(out) = self.out.as_mut() else {
+            return;
+        };
         let frame = self.frames[self.idx % self.frames.len()];
-        eprint!("\r  \x1b[36m{frame}\x1b[0m {msg}");
-        let _ = io::stderr().flush();
+        let _ = write!(out, "\r  \x1b[36m{frame}\x1b[0m {msg}");
+        let _ = out.flush();
         self.idx += 1;
     }
 
-    fn finish(&self) {
-        eprint!("\r\x1b[2K");
-        let _ = io::stderr().flush();
+    /// Report how many directories the walk has checked so far.
+    fn walked(&mut self, dirs: u64) {
+        if self.json {
+            self.event(serde_json::json!({ "phase": "walk", "dirs": dirs }));
+        } else {
+            self.tick(&format!("Scanning... {} directories checked", dirs));
+        }
+    }
+
+    /// Report how many of the `total` projects found have been sized.
+    fn analyzed(&mut self, done: usize, total: usize) {
+        if self.json {
+            self.event(serde_json::json!({ "phase": "analyze", "done": done, "total": total }));
+        } else {
+            self.tick(&format!("Calculating sizes... {done}/{total} projects"));
+        }
+    }
+
+    fn finish(&mut self) {
+        if self.json {
+            self.event(serde_json::json!({ "phase": "done" }));
+        } else if let Some(out) = self.out.as_mut() {
+            let _ = write!(out, "\r\x1b[2K");
+            let _ = out.flush();
+        }
+    }
+
+    fn event(&mut self, event: serde_json::Value) {
+        if let Some(out) = self.out.as_mut() {
+            let _ = writeln!(out, "{event}");
+            let _ = out.flush();
+        }
     }
 }
 
@@ -77,34 +139,195 @@ pub fn scan_directory(
     max_depth: Option<usize>,
     config: &DevSweepConfig,
 ) -> Result<Vec<ScannedProject>> {
-    let mut spinner = Spinner::new();
+    scan_directory_to(root, max_depth, config, &mut io::stderr())
+}
+
+/// Like [`scan_directory`], but draws the progress spinner on `progress`
+/// instead of stderr. Nothing is written when `config.no_spinner` is set,
+/// unless `config.progress_json` asks for JSON events instead.
+pub fn scan_directory_to(
+    root: &Path,
+    max_depth: Option<usize>,
+    config: &DevSweepConfig,
+    progress: &mut dyn Write,
+) -> Result<Vec<ScannedProject>> {
+    let (projects, _) = scan_directory_each(root, max_depth, config, progress, &|_| {})?;
+    Ok(projects)
+}
+
+/// Like [`scan_directory_to`], but also hands each project to `on_project` as
+/// soon as it has been sized, so results can be shown before the scan ends.
+///
+/// Projects arrive in completion order, possibly from several threads at once.
+/// With `ignore_vcs_roots`, repositories can only be merged once everything is
+/// sized, so `on_project` is called at the end instead.
+///
+/// Also returns every path that couldn't be read during the walk or while
+/// sizing, with the reason, since those make the reported sizes incomplete.
+pub fn scan_directory_each(
+    root: &Path,
+    max_depth: Option<usize>,
+    config: &DevSweepConfig,
+    progress: &mut dyn Write,
+    on_project: &(dyn Fn(&ScannedProject) + Sync),
+) -> Result<(Vec<ScannedProject>, Unreadable)> {
+    let show = !config.no_spinner || config.progress_json;
+    let mut spinner = Spinner::new(show.then_some(progress), config.progress_json);
     spinner.tick(&format!("Scanning {}...", root.display()));
 
-    let candidates = find_project_roots(root, max_depth, config, &mut spinner)?;
+    let mut unreadable = Vec::new();
+    let candidates = find_project_roots(root, max_depth, config, &mut spinner, &mut unreadable)?;
 
     spinner.tick(&format!(
         "Found {} projects, calculating sizes...",
         candidates.len()
     ));
 
-    let projects: Vec<ScannedProject> = candidates
-        .into_par_iter()
-        .filter_map(|(path, kind)| analyze_project(&path, kind).ok())
-        .filter(|p| p.total_cleanable_bytes > 0)
+    // Size on a worker thread so this one can report each project as it's done.
+    let total = candidates.len();
+    let stream = !config.ignore_vcs_roots;
+    let (sized, sized_rx) = mpsc::channel();
+    let (builds, mut analyzed): (Vec<_>, Vec<_>) = thread::scope(|s| {
+        let worker = s.spawn(move || {
+            candidates
+                .into_par_iter()
+                .filter_map(|c| {
+                    let project = analyze_detected(&c.path, c.kind, c.custom, config);
+                    let _ = sized.send(());
+                    let mut project = project.ok()?;
+                    project.depth = c.depth;
+                    project.scan_root = root.to_path_buf();
+                    Some((project, c.vcs_root, c.gradle_root))
+                })
+                .inspect(|(p, _, gradle_root)| {
+                    if stream && gradle_root.is_none() && p.total_cleanable_bytes > 0 {
+                        on_project(p);
+                    }
+                })
+                .partition_map(|(p, vcs_root, gradle_root)| match gradle_root {
+                    Some(gradle_root) => Either::Left(((p, Some(gradle_root)), vcs_root)),
+                    None => Either::Right((p, vcs_root)),
+                })
+        });
+        for (done, ()) in sized_rx.iter().enumerate() {
+            spinner.analyzed(done + 1, total);
+        }
+        worker.join().unwrap_or_else(|e| panic::resume_unwind(e))
+    });
+
+    // Gradle modules fold into their settings root, which then takes part in
+    // the VCS merge like any other project.
+    let (modules, vcs_roots): (Vec<_>, Vec<_>) = builds.into_iter().unzip();
+    let vcs_roots: HashMap<PathBuf, Option<PathBuf>> = modules
+        .iter()
+        .map(|(p, _)| p.path.clone())
+        .zip(vcs_roots)
         .collect();
+    for build in merge_under_roots(modules) {
+        if stream && build.total_cleanable_bytes > 0 {
+            on_project(&build);
+        }
+        let vcs_root = vcs_roots.get(&build.path).cloned().flatten();
+        analyzed.push((build, vcs_root));
+    }
+
+    // Keep sizing errors even from projects that came out empty: an unreadable
+    // `node_modules` sizes to nothing, which is exactly what the user needs to know.
+    unreadable.extend(analyzed.iter().flat_map(|(p, _)| p.unreadable.iter().cloned()));
+    analyzed.retain(|(p, _)| p.total_cleanable_bytes > 0);
+
+    let projects = if config.ignore_vcs_roots {
+        let mut merged = merge_under_roots(analyzed);
+        for project in &mut merged {
+            project.scan_root = root.to_path_buf();
+            on_project(project);
+        }
+        merged
+    } else {
+        analyzed.into_iter().map(|(p, _)| p).collect()
+    };
 
     spinner.finish();
 
+    Ok((projects, unreadable))
+}
+
+/// Scan several roots and combine the results.
+///
+/// Each project records the root it was found under in `scan_root`. A project
+/// reachable from more than one root (overlapping roots) is reported once,
+/// under the first root that found it.
+pub fn scan_directories(
+    roots: &[PathBuf],
+    max_depth: Option<usize>,
+    config: &DevSweepConfig,
+) -> Result<Vec<ScannedProject>> {
+    let (projects, _) = scan_directories_each(roots, max_depth, config, &|_| {})?;
     Ok(projects)
 }
 
+/// Like [`scan_directories`], calling `on_project` for each project as it is
+/// sized (see [`scan_directory_each`]). Projects already reported under an
+/// earlier root are not reported again. Unreadable paths from every root are
+/// returned together.
+pub fn scan_directories_each(
+    roots: &[PathBuf],
+    max_depth: Option<usize>,
+    config: &DevSweepConfig,
+    on_project: &(dyn Fn(&ScannedProject) + Sync),
+) -> Result<(Vec<ScannedProject>, Unreadable)> {
+    let mut seen: HashSet<PathBuf> = HashSet::new();
+    let mut projects = Vec::new();
+    let mut unreadable = Vec::new();
+
+    for root in roots {
+        let (found, errors) =
+            scan_directory_each(root, max_depth, config, &mut io::stderr(), &|p| {
+                if !seen.contains(&p.path) {
+                    on_project(p);
+                }
+            })?;
+        unreadable.extend(errors);
+        for project in found {
+            if seen.insert(project.path.clone()) {
+                projects.push(project);
+            }
+        }
+    }
+
+    Ok((projects, unreadable))
+}
+
+/// A project root found during the walk, before analysis.
+struct Candidate {
+    path: PathBuf,
+    kind: ProjectKind,
+    /// Index into `config.custom_kinds` when `kind` is [`ProjectKind::Custom`].
+    custom: Option<usize>,
+    /// Walk depth below the scan root.
+    depth: usize,
+    /// The outermost enclosing git repository (only tracked with `ignore_vcs_roots`).
+    vcs_root: Option<PathBuf>,
+    /// For a Gradle build or module, the enclosing settings root its
+    /// targets are reported under (see [`is_gradle_settings_root`]).
+    gradle_root: Option<PathBuf>,
+}
+
 /// Walk the filesystem to find project root directories.
+///
+/// `max_depth` is counted from the scan root: `Some(0)` checks only the root
+/// itself, `Some(1)` its immediate children, and so on. `config.project_depth`
+/// is counted from the nearest enclosing project instead, limiting how far the
+/// walk descends below a discovered project looking for nested ones.
+///
+/// Directories the walk couldn't read are recorded in `unreadable`.
 fn find_project_roots(
     root: &Path,
     max_depth: Option<usize>,
     config: &DevSweepConfig,
-    spinner: &mut Spinner,
-) -> Result<Vec<(PathBuf, ProjectKind)>> {
+    spinner: &mut Spinner<'_>,
+    unreadable: &mut Unreadable,
+) -> Result<Vec<Candidate>> {
     let mut candidates = Vec::new();
     let mut walker = WalkDir::new(root).follow_links(false);
 
@@ -121,54 +344,235 @@ fn find_project_roots(
 
     let mut dirs_scanned: u64 = 0;
 
-    for entry in walker.into_iter().filter_entry(should_visit) {
+    // Walk depths of the enclosing project roots, innermost last. Used to
+    // measure `project_depth` relative to the nearest project, not the root.
+    let mut project_stack: Vec<(PathBuf, usize)> = Vec::new();
+
+    // The outermost git repository containing the current entry, if any.
+    let mut vcs_root: Option<PathBuf> = None;
+
+    // The outermost Gradle settings root containing the current entry, if any.
+    let mut gradle_root: Option<PathBuf> = None;
+
+    // With `one_file_system`, stay on the scan root's device.
+    let root_dev = if config.one_file_system {
+        device_id(root)
+    } else {
+        None
+    };
+
+    // `.devcleanignore` matchers of the directories enclosing the current entry.
+    let mut ignore_stack: Vec<(PathBuf, Gitignore)> = Vec::new();
+
+    let mut entries = walker.into_iter().filter_entry(|e| {
+        should_visit_with(e, config.no_skip_dirs)
+            && (!e.file_type().is_dir() || on_device(e.path(), root_dev))
+    });
+    while let Some(entry) = entries.next() {
         let entry = match entry {
             Ok(e) => e,
-            Err(_) => continue,
+            Err(e) => {
+                record_walk_error(&e, unreadable);
+                continue;
+            }
         };
 
         if !entry.file_type().is_dir() {
             continue;
         }
 
+        while project_stack
+            .last()
+            .is_some_and(|(path, _)| !entry.path().starts_with(path))
+        {
+            project_stack.pop();
+        }
+
+        while ignore_stack
+            .last()
+            .is_some_and(|(path, _)| !entry.path().starts_with(path))
+        {
+            ignore_stack.pop();
+        }
+
+        if is_ignored(&ignore_stack, entry.path(), true) {
+            entries.skip_current_dir();
+            continue;
+        }
+
+        if let Some(matcher) = load_ignore_file(entry.path()) {
+            ignore_stack.push((entry.path().to_path_buf(), matcher));
+        }
+
+        if let Some(limit) = config.project_depth
+            && let Some((_, project_depth)) = project_stack.last()
+            && entry.depth() - project_depth > limit
+        {
+            entries.skip_current_dir();
+            continue;
+        }
+
+        if config.ignore_vcs_roots {
+            if vcs_root.as_ref().is_some_and(|r| !entry.path().starts_with(r)) {
+                vcs_root = None;
+            }
+            if vcs_root.is_none() && entry.path().join(".git").exists() {
+                vcs_root = Some(entry.path().to_path_buf());
+            }
+        }
+
+        if gradle_root.as_ref().is_some_and(|r| !entry.path().starts_with(r)) {
+            gradle_root = None;
+        }
+
         dirs_scanned += 1;
         #[allow(clippy::manual_is_multiple_of)]
         if dirs_scanned % 200 == 0 {
-            spinner.tick(&format!("Scanning... {} directories checked", dirs_scanned));
+            spinner.walked(dirs_scanned);
         }
 
         let dir_path = entry.path();
 
         // Skip paths the user has explicitly told us to ignore.
-        if let Ok(canonical) = fs::canonicalize(dir_path) {
-            if ignored.contains(&canonical) {
-                continue;
-            }
+        if let Ok(canonical) = fs::canonicalize(dir_path)
+            && ignored.contains(&canonical)
+        {
+            continue;
         }
 
-        if let Some(kind) = detect_project_kind(dir_path) {
+        if let Some((kind, custom)) = detect_project_kind_with(dir_path, config) {
             // Skip project kinds the user has excluded.
             if config.exclude_kinds.contains(&kind) {
                 continue;
             }
-            candidates.push((dir_path.to_path_buf(), kind));
+            if kind == ProjectKind::Java
+                && gradle_root.is_none()
+                && is_gradle_settings_root(dir_path)
+            {
+                gradle_root = Some(dir_path.to_path_buf());
+            }
+            project_stack.push((dir_path.to_path_buf(), entry.depth()));
+            candidates.push(Candidate {
+                path: dir_path.to_path_buf(),
+                kind,
+                custom,
+                depth: entry.depth(),
+                vcs_root: vcs_root.clone(),
+                gradle_root: gradle_root
+                    .clone()
+                    .filter(|_| kind == ProjectKind::Java && is_gradle_build(dir_path)),
+            });
         }
     }
 
+    // The final count, so even a short walk reports where it ended up
+    #[allow(clippy::manual_is_multiple_of)]
+    if dirs_scanned % 200 != 0 {
+        spinner.walked(dirs_scanned);
+    }
+
     Ok(candidates)
 }
 
+/// Merge all projects paired with the same root (a git repository, or a
+/// Gradle settings root) into a single entry rooted there. Projects paired
+/// with `None` are passed through.
+///
+/// The merged entry keeps the kind of the project at the root (or the first
+/// nested project if the root itself isn't one). Targets from nested
+/// projects are renamed relative to the root, e.g. `packages/web/node_modules`.
+fn merge_under_roots(analyzed: Vec<(ScannedProject, Option<PathBuf>)>) -> Vec<ScannedProject> {
+    let mut projects: Vec<ScannedProject> = Vec::new();
+    let mut by_root: Vec<(PathBuf, Vec<ScannedProject>)> = Vec::new();
+
+    for (project, vcs_root) in analyzed {
+        match vcs_root {
+            None => projects.push(project),
+            Some(root) => match by_root.iter_mut().find(|(r, _)| *r == root) {
+                Some((_, group)) => group.push(project),
+                None => by_root.push((root, vec![project])),
+            },
+        }
+    }
+
+    for (root, mut group) in by_root {
+        // Put the repo-root project (if any) first so it provides the kind.
+        group.sort_by_key(|p| p.path != root);
+
+        let mut merged = group[0].clone();
+        merged.path = root.clone();
+        merged.name = root
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| root.display().to_string());
+        merged.clean_targets.clear();
+        merged.unreadable.clear();
+
+        for project in group {
+            let prefix = project.path.strip_prefix(&root).unwrap_or(Path::new(""));
+            merged.last_modified = merged.last_modified.max(project.last_modified);
+            merged.unreadable.extend(project.unreadable);
+            for mut target in project.clean_targets {
+                if !prefix.as_os_str().is_empty() {
+                    target.name = prefix.join(&target.name).display().to_string();
+                }
+                merged.clean_targets.push(target);
+            }
+        }
+
+        merged.total_cleanable_bytes = merged.clean_targets.iter().map(|t| t.size_bytes).sum();
+        // Nested projects' targets would otherwise count as the root's source.
+        if merged.source_bytes.is_some() {
+            let bound = merged.total_cleanable_bytes + 1;
+            merged.source_bytes =
+                Some(source_size_at_least(&root, &merged.clean_targets, bound).bytes());
+        }
+        projects.push(merged);
+    }
+
+    projects
+}
+
+/// Whether `dir` holds a `settings.gradle(.kts)`, making it the root of a
+/// (possibly multi-module) Gradle build. Modules below it are reported as
+/// part of this one project rather than each on their own.
+pub fn is_gradle_settings_root(dir: &Path) -> bool {
+    ["settings.gradle", "settings.gradle.kts"]
+        .iter()
+        .any(|m| marker_exists(dir, m))
+}
+
+/// Whether `dir` is part of a Gradle build: a settings root or a module with
+/// its own `build.gradle(.kts)`. A Maven project nested in a Gradle tree is
+/// not, and stays separate.
+fn is_gradle_build(dir: &Path) -> bool {
+    is_gradle_settings_root(dir)
+        || ["build.gradle", "build.gradle.kts"]
+            .iter()
+            .any(|m| marker_exists(dir, m))
+}
+
 /// Determine if a walkdir entry should be descended into.
 ///
 /// Skips all hidden directories (dot-prefixed) at depth > 0, as well as
 /// any directory in [`SKIP_DIRS`] (build artifacts, dependency caches, etc.).
 pub fn should_visit(entry: &walkdir::DirEntry) -> bool {
+    should_visit_with(entry, false)
+}
+
+/// Like [`should_visit`], but with `no_skip_dirs` (`--no-skip-dirs`) only
+/// `.git` is skipped, so every other directory is searched for projects.
+pub fn should_visit_with(entry: &walkdir::DirEntry, no_skip_dirs: bool) -> bool {
     if !entry.file_type().is_dir() {
         return true;
     }
 
     let name = entry.file_name().to_string_lossy();
 
+    if no_skip_dirs {
+        return name != ".git";
+    }
+
     // Skip all hidden (dot-prefixed) directories below the root — these are
     // almost never useful to scan (.cache, .local, .backup, etc.) and the
     // known artifact dirs (.git, .venv, …) are already in SKIP_DIRS.
@@ -179,6 +583,53 @@ pub fn should_visit(entry: &walkdir::DirEntry) -> bool {
     !SKIP_DIRS.contains(name.as_ref())
 }
 
+/// Load the [`IGNORE_FILE`] in `dir`, if there is one.
+///
+/// Malformed lines are skipped; the rest of the file still applies.
+fn load_ignore_file(dir: &Path) -> Option<Gitignore> {
+    let path = dir.join(IGNORE_FILE);
+    if !path.is_file() {
+        return None;
+    }
+    let (matcher, _) = Gitignore::new(path);
+    (!matcher.is_empty()).then_some(matcher)
+}
+
+/// Whether any matcher in `stack` ignores `path` (or one of its parents).
+///
+/// Every matcher's root must be an ancestor of `path`.
+fn is_ignored(stack: &[(PathBuf, Gitignore)], path: &Path, is_dir: bool) -> bool {
+    stack.iter().any(|(_, matcher)| {
+        matcher
+            .matched_path_or_any_parents(path, is_dir)
+            .is_ignore()
+    })
+}
+
+/// The id of the device holding `path`, or `None` where that isn't available
+/// (non-unix platforms, unreadable paths).
+#[cfg(unix)]
+pub fn device_id(path: &Path) -> Option<u64> {
+    use std::os::unix::fs::MetadataExt;
+    fs::metadata(path).ok().map(|m| m.dev())
+}
+
+#[cfg(not(unix))]
+pub fn device_id(_path: &Path) -> Option<u64> {
+    None
+}
+
+/// Whether `path` is on the device `root_dev`.
+///
+/// Always true when `root_dev` is `None` or the device of `path` is unknown,
+/// so `--one-file-system` degrades to a no-op where device ids aren't available.
+pub fn on_device(path: &Path, root_dev: Option<u64>) -> bool {
+    let Some(root_dev) = root_dev else {
+        return true;
+    };
+    device_id(path).is_none_or(|dev| dev == root_dev)
+}
+
 /// Detect what kind of project a directory contains, if any.
 pub fn detect_project_kind(dir: &Path) -> Option<ProjectKind> {
     ProjectKind::all()
@@ -187,6 +638,25 @@ pub fn detect_project_kind(dir: &Path) -> Option<ProjectKind> {
         .copied()
 }
 
+/// Detect a project's kind, falling back to the user-defined kinds in `config`.
+///
+/// Built-in kinds always win. A custom match is reported as
+/// [`ProjectKind::Custom`] with its index into `config.custom_kinds`.
+fn detect_project_kind_with(
+    dir: &Path,
+    config: &DevSweepConfig,
+) -> Option<(ProjectKind, Option<usize>)> {
+    if let Some(kind) = detect_project_kind(dir) {
+        return Some((kind, None));
+    }
+
+    config
+        .custom_kinds
+        .iter()
+        .position(|custom| custom.marker_files.iter().any(|m| marker_exists(dir, m)))
+        .map(|i| (ProjectKind::Custom, Some(i)))
+}
+
 /// Check whether a single marker pattern matches anything in `dir`.
 ///
 /// Supports three pattern styles:
@@ -210,61 +680,314 @@ fn marker_exists(dir: &Path, marker: &str) -> bool {
     }
 }
 
+/// Detect and analyze the project rooted at `path`, without walking the tree.
+///
+/// Fails if `path` isn't a recognized project root.
+pub fn analyze_path(path: &Path, config: &DevSweepConfig) -> Result<ScannedProject> {
+    let (kind, custom) = detect_project_kind_with(path, config).ok_or_else(|| {
+        anyhow::anyhow!(
+            "Not a project root (no marker files found): {}",
+            path.display()
+        )
+    })?;
+
+    analyze_detected(path, kind, custom, config)
+}
+
+/// Analyze a project as detected by [`detect_project_kind_with`].
+fn analyze_detected(
+    path: &Path,
+    kind: ProjectKind,
+    custom: Option<usize>,
+    config: &DevSweepConfig,
+) -> Result<ScannedProject> {
+    match custom.and_then(|i| config.custom_kinds.get(i)) {
+        Some(custom) => analyze_custom_project(path, custom, config),
+        None => analyze_project_with(path, kind, config),
+    }
+}
+
+/// Analyze an explicit list of project roots, skipping the tree walk.
+///
+/// Paths that aren't recognized project roots (or can't be analyzed) are
+/// returned separately with the reason, in input order.
+pub fn analyze_paths(
+    paths: &[PathBuf],
+    config: &DevSweepConfig,
+) -> (Vec<ScannedProject>, Vec<(PathBuf, anyhow::Error)>) {
+    let mut projects = Vec::new();
+    let mut skipped = Vec::new();
+
+    for path in paths {
+        match analyze_path(path, config) {
+            Ok(project) => projects.push(project),
+            Err(e) => skipped.push((path.clone(), e)),
+        }
+    }
+
+    (projects, skipped)
+}
+
+/// Extra directories cleaned in a Python project managed by Poetry or PDM
+/// (see [`uses_poetry_or_pdm`]).
+const PYTHON_PACKAGING_DIRS: &[&str] = &["__pypackages__", "dist", "build"];
+
+/// Whether the `pyproject.toml` in `project_root` declares Poetry or PDM,
+/// either as a `[tool.poetry]`/`[tool.pdm]` table or as its build backend.
+pub fn uses_poetry_or_pdm(project_root: &Path) -> bool {
+    const SIGNS: &[&str] = &[
+        "[tool.poetry",
+        "[tool.pdm",
+        "poetry.core.masonry.api",
+        "pdm.backend",
+        "pdm.pep517",
+    ];
+    fs::read_to_string(project_root.join("pyproject.toml"))
+        .is_ok_and(|toml| SIGNS.iter().any(|sign| toml.contains(sign)))
+}
+
 /// Analyze a single project: find cleanable targets and calculate sizes.
 pub fn analyze_project(project_root: &Path, kind: ProjectKind) -> Result<ScannedProject> {
+    analyze_project_with(project_root, kind, &DevSweepConfig::default())
+}
+
+/// Analyze a single project, honoring the analysis options in `config`.
+pub fn analyze_project_with(
+    project_root: &Path,
+    kind: ProjectKind,
+    config: &DevSweepConfig,
+) -> Result<ScannedProject> {
+    // A Godot project using C# also builds through MSBuild. Only then are its
+    // `bin/` and `obj/` outputs; otherwise `bin/` often holds GDExtension
+    // libraries the project needs at runtime.
+    let csharp_godot = kind == ProjectKind::Godot && marker_exists(project_root, "*.csproj");
+    // Likewise `dist/` and `build/` are only known publish output (and
+    // `__pypackages__/` PDM's install dir) in a Poetry or PDM project.
+    let packaged_python = kind == ProjectKind::Python && uses_poetry_or_pdm(project_root);
+    let extra: &[&str] = if csharp_godot {
+        ProjectKind::DotNet.cleanable_dirs()
+    } else if packaged_python {
+        PYTHON_PACKAGING_DIRS
+    } else {
+        &[]
+    };
+    let dirs: Vec<&str> = kind.cleanable_dirs().iter().chain(extra).copied().collect();
+
+    analyze_patterns(
+        project_root,
+        kind,
+        kind.marker_files(),
+        &dirs,
+        kind.cleanable_files(),
+        config,
+    )
+}
+
+/// Analyze a project of a user-defined kind from `config.custom_kinds`.
+pub fn analyze_custom_project(
+    project_root: &Path,
+    custom: &CustomKind,
+    config: &DevSweepConfig,
+) -> Result<ScannedProject> {
+    let markers: Vec<&str> = custom.marker_files.iter().map(String::as_str).collect();
+    let patterns: Vec<&str> = custom.cleanable_dirs.iter().map(String::as_str).collect();
+
+    let mut project = analyze_patterns(
+        project_root,
+        ProjectKind::Custom,
+        &markers,
+        &patterns,
+        &[],
+        config,
+    )?;
+    project.custom_kind = Some(custom.name.clone());
+    Ok(project)
+}
+
+/// Resolve `dir_patterns` and `file_patterns` under `project_root` into clean
+/// targets and build the project entry. `markers` date the project (see
+/// [`get_last_modified`]).
+fn analyze_patterns(
+    project_root: &Path,
+    kind: ProjectKind,
+    markers: &[&str],
+    dir_patterns: &[&str],
+    file_patterns: &[&str],
+    config: &DevSweepConfig,
+) -> Result<ScannedProject> {
     let name = project_root
         .file_name()
         .map(|n| n.to_string_lossy().to_string())
         .unwrap_or_else(|| project_root.display().to_string());
 
-    let last_modified = get_last_modified(project_root, &kind)?;
+    let last_modified = get_last_modified(project_root, markers)?;
+
+    // With `granular`, Rust's `target/` is split into one target per profile.
+    let split_profiles = config.granular && kind == ProjectKind::Rust;
+
+    let mut clean_targets: Vec<CleanTarget> = Vec::new();
+    let mut unreadable = Vec::new();
+    let patterns = dir_patterns
+        .iter()
+        .map(|p| (p, false))
+        .chain(file_patterns.iter().map(|p| (p, true)));
+    for (pattern, is_file) in patterns {
+        let category = kind.target_category(pattern);
+        let mut candidates = if split_profiles && *pattern == "target" {
+            rust_profile_dirs(project_root)
+        } else {
+            resolve_pattern(project_root, pattern, is_file)
+        };
+        // A hand-written `Makefile` (or `Makefile.am`, ...) can sit next to
+        // a `.pro` file; only the ones qmake generated are output.
+        if kind == ProjectKind::Qt && *pattern == "Makefile*" {
+            candidates.retain(|(path, _)| is_qmake_makefile(path));
+        }
+        clean_targets.extend(candidates.into_iter().filter_map(|(path, name)| {
+            as_clean_target(path, name, category, config, &mut unreadable)
+        }));
+    }
+
+    // Directories any kind may have: project-local compiler caches, then the
+    // opt-in ones. Only inside a recognized project root, so a stray
+    // `~/.cache` never counts.
+    let mut cross_cutting = vec![
+        (".sccache", TargetCategory::CompilerCache),
+        (".ccache", TargetCategory::CompilerCache),
+    ];
+    // Categorized like a kind's own `.cache` pattern, so `purge` treats both alike.
+    if config.generic_caches {
+        cross_cutting.push((".cache", kind.target_category(".cache")));
+    }
+    if config.ide_caches {
+        cross_cutting.extend([
+            (".idea", TargetCategory::ToolCache),
+            (".vs", TargetCategory::ToolCache),
+        ]);
+    }
+    let extra = env::var(EXTRA_TARGETS_VAR).unwrap_or_default();
+    let extra = parse_extra_targets(&extra);
+    cross_cutting.extend(extra.iter().map(|n| (n.as_str(), kind.target_category(n))));
+    for (dir_name, category) in cross_cutting {
+        let path = project_root.join(dir_name);
+        if path.is_dir() && !clean_targets.iter().any(|t| t.name == dir_name) {
+            let name = dir_name.to_string();
+            clean_targets.extend(as_clean_target(path, name, category, config, &mut unreadable));
+        }
+    }
 
-    let mut clean_targets: Vec<CleanTarget> = kind
-        .cleanable_dirs()
+    // A recursive name may also be a top-level pattern; count each dir once.
+    let mut nested = Vec::new();
+    let recursive: Vec<_> = kind
+        .recursive_dirs()
         .iter()
-        .flat_map(|pattern| resolve_pattern(project_root, pattern))
-        .filter_map(|(path, name)| as_clean_target(path, name))
+        .map(|&name| (name, kind.target_category(name)))
         .collect();
+    find_dirs_recursive(project_root, &recursive, config, &mut unreadable, &mut nested);
+    nested.retain(|n| !clean_targets.iter().any(|t| t.path == n.path));
+    clean_targets.append(&mut nested);
 
-    if kind == ProjectKind::Python {
-        find_pycache_recursive(project_root, &mut clean_targets);
+    if let Some(matcher) = load_ignore_file(project_root) {
+        let stack = [(project_root.to_path_buf(), matcher)];
+        clean_targets.retain(|t| !is_ignored(&stack, &t.path, t.kind == TargetKind::Dir));
+    }
+
+    if let Some(min) = config.target_min_size {
+        clean_targets.retain(|t| t.size_bytes >= min);
+    }
+
+    if kind == ProjectKind::Python
+        && let Some(active) = env::var_os("VIRTUAL_ENV")
+    {
+        mark_active_venv(&mut clean_targets, Path::new(&active));
     }
 
     let total_cleanable_bytes = clean_targets.iter().map(|t| t.size_bytes).sum();
 
+    // Only "is the source bigger than the artifacts" matters, so stop there.
+    let source_bytes = config.skip_smaller_than_source.then(|| {
+        source_size_at_least(project_root, &clean_targets, total_cleanable_bytes + 1).bytes()
+    });
+
+    let git = if config.git {
+        git_context(project_root).unwrap_or_default()
+    } else {
+        Default::default()
+    };
+
     Ok(ScannedProject {
         path: project_root.to_path_buf(),
+        scan_root: project_root.to_path_buf(),
+        depth: 0,
         kind,
         name,
         last_modified,
         clean_targets,
         total_cleanable_bytes,
+        git_remote: git.remote,
+        git_branch: git.branch,
+        git_dirty: git.dirty,
+        custom_kind: None,
+        unreadable,
+        source_bytes,
     })
 }
 
-/// Resolve a cleanable-dir pattern into concrete (path, display_name) candidates.
+/// Environment variable listing extra directory names (comma-separated, e.g.
+/// `.cache,tmp`) to clean in every project, whatever its kind.
+pub const EXTRA_TARGETS_VAR: &str = "DEVCLEAN_EXTRA_TARGETS";
+
+/// Split a [`EXTRA_TARGETS_VAR`] value into directory names. Each must be a
+/// plain name directly under the project root: entries with a path separator
+/// or `..` are dropped, as are blanks.
 ///
-/// - `"*suffix"` → glob: scan the project root for matching directories
+/// These come after the kind's own patterns (including a custom kind's
+/// `cleanable_dirs`), so a name the kind already cleans keeps its listing.
+pub fn parse_extra_targets(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|n| !n.is_empty() && *n != "." && *n != "..")
+        .filter(|n| !n.contains(['/', '\\']))
+        .map(String::from)
+        .collect()
+}
+
+/// Resolve a cleanable pattern into concrete (path, display_name) candidates.
+///
+/// - `"*suffix"`, `"prefix-*"` → glob: scan the project root for matching entries
 /// - `"sub/dir"` → nested path: check if the exact subdirectory exists
 /// - `"dirname"` → simple: check if the directory exists at the project root
-fn resolve_pattern(project_root: &Path, pattern: &str) -> Vec<(PathBuf, String)> {
+///
+/// Both globs and exact names match only directories, or only files with
+/// `is_file` (for [`ProjectKind::cleanable_files`]), so e.g. a `build` script
+/// is never mistaken for a `build/` output directory, nor `build-linux.sh`
+/// for a `build-*` shadow build.
+fn resolve_pattern(project_root: &Path, pattern: &str, is_file: bool) -> Vec<(PathBuf, String)> {
     if pattern.contains('*') {
-        // Glob pattern — match directory names by suffix
-        let suffix = pattern.trim_start_matches('*');
+        // Glob pattern — match entry names against the wildcard pattern
         fs::read_dir(project_root)
             .into_iter()
             .flatten()
             .filter_map(|e| e.ok())
+            .filter(|e| {
+                let path = e.path();
+                if is_file { path.is_file() } else { path.is_dir() }
+            })
             .filter_map(|e| {
                 let name = e.file_name().to_string_lossy().into_owned();
-                (name.ends_with(suffix) && e.path().is_dir())
-                    .then(|| (e.path(), name))
+                glob_match(pattern, &name).then(|| (e.path(), name))
             })
             .collect()
     } else {
         // Exact path (simple name or nested like "project/target")
         let target = project_root.join(pattern);
-        if target.is_dir() {
+        let matches = if is_file {
+            target.is_file()
+        } else {
+            target.is_dir()
+        };
+        if matches {
             vec![(target, pattern.to_string())]
         } else {
             vec![]
@@ -272,21 +995,122 @@ fn resolve_pattern(project_root: &Path, pattern: &str) -> Vec<(PathBuf, String)>
     }
 }
 
-/// Try to turn a candidate directory into a CleanTarget. Returns None if empty or unreadable.
-fn as_clean_target(path: PathBuf, name: String) -> Option<CleanTarget> {
-    let size = dir_size(&path).ok()?;
-    (size > 0).then_some(CleanTarget {
+/// Whether `path` is a Makefile generated by qmake, which says so in a
+/// `# Generated by qmake` line at the top.
+pub fn is_qmake_makefile(path: &Path) -> bool {
+    let Ok(file) = fs::File::open(path) else {
+        return false;
+    };
+    io::BufReader::new(file)
+        .lines()
+        .take(5)
+        .map_while(Result::ok)
+        .any(|line| line.starts_with("# Generated by qmake"))
+}
+
+/// Whether a `build/` directory looks like committed source rather than build
+/// output: it holds a `.gitkeep`, or git tracks files inside it.
+pub fn is_ambiguous_build_dir(path: &Path) -> bool {
+    path.join(".gitkeep").exists() || has_tracked_files(path)
+}
+
+/// Flag the `.venv`/`venv` in `targets` that is `virtual_env`, the environment
+/// activated in the current shell. Paths are compared canonicalized, so a
+/// symlinked checkout still matches.
+pub fn mark_active_venv(targets: &mut [CleanTarget], virtual_env: &Path) {
+    let active = fs::canonicalize(virtual_env).unwrap_or_else(|_| virtual_env.to_path_buf());
+    for target in targets {
+        if matches!(target.name.as_str(), ".venv" | "venv") {
+            let path = fs::canonicalize(&target.path).unwrap_or_else(|_| target.path.clone());
+            target.active = path == active;
+        }
+    }
+}
+
+/// List the per-profile directories inside a Rust `target/` (e.g.
+/// `target/debug`, `target/release`, `target/doc`), sorted by name.
+fn rust_profile_dirs(project_root: &Path) -> Vec<(PathBuf, String)> {
+    let mut dirs: Vec<(PathBuf, String)> = fs::read_dir(project_root.join("target"))
+        .into_iter()
+        .flatten()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_ok_and(|t| t.is_dir()))
+        .map(|e| {
+            let name = format!("target/{}", e.file_name().to_string_lossy());
+            (e.path(), name)
+        })
+        .collect();
+    dirs.sort_by(|a, b| a.1.cmp(&b.1));
+    dirs
+}
+
+/// Try to turn a candidate path into a CleanTarget. Returns None if empty or unreadable.
+///
+/// Directories are sized recursively (estimated with `fast`, or reused from
+/// the size cache with `cache_mode`); regular files by their length. With
+/// `target_min_size` and no cache, sizing stops once a directory reaches the
+/// minimum, since only "big enough to list" matters.
+/// `last_modified` is the newest file inside the target; estimated and
+/// bounded directories fall back to the directory's own mtime.
+fn as_clean_target(
+    path: PathBuf,
+    name: String,
+    category: TargetCategory,
+    config: &DevSweepConfig,
+    unreadable: &mut Unreadable,
+) -> Option<CleanTarget> {
+    let fast = config.fast;
+    let mut at_least = false;
+    let (kind, size, newest) = if path.is_dir() && fast {
+        (TargetKind::Dir, estimate_dir_size(&path).ok()?, None)
+    } else if path.is_dir()
+        && config.cache_mode == CacheMode::Off
+        && let Some(min) = config.target_min_size
+    {
+        // A partial size must never reach the cache, so this is cache-off only.
+        let bounded = dir_size_at_least_with(&path, min, unreadable);
+        at_least = matches!(bounded, BoundedSize::AtLeast(_));
+        (TargetKind::Dir, bounded.bytes(), None)
+    } else if path.is_dir() {
+        let (size, newest) = cached_dir_size(&path, config.cache_mode, || {
+            dir_size_and_mtime_with(&path, unreadable)
+        });
+        (TargetKind::Dir, size, newest)
+    } else if path.is_file() {
+        let meta = fs::metadata(&path).ok()?;
+        (TargetKind::File, meta.len(), meta.modified().ok())
+    } else {
+        return None;
+    };
+
+    (size > 0).then(|| CleanTarget {
+        last_modified: modified_time(&path, newest),
         path,
         name,
         size_bytes: size,
+        kind,
+        category,
+        estimated: fast && kind == TargetKind::Dir,
+        at_least,
+        active: false,
+        ambiguous: false,
     })
 }
 
+/// `newest` as a local timestamp, falling back to `path`'s own mtime (and,
+/// failing that, now — so an unknown age never looks stale).
+fn modified_time(path: &Path, newest: Option<SystemTime>) -> DateTime<Local> {
+    newest
+        .or_else(|| fs::metadata(path).and_then(|m| m.modified()).ok())
+        .map(DateTime::<Local>::from)
+        .unwrap_or_else(Local::now)
+}
+
 /// Get the last modified time of a project based on its marker files.
-fn get_last_modified(project_root: &Path, kind: &ProjectKind) -> Result<DateTime<Local>> {
+fn get_last_modified(project_root: &Path, markers: &[&str]) -> Result<DateTime<Local>> {
     let mut latest: Option<SystemTime> = None;
 
-    for marker in kind.marker_files() {
+    for marker in markers {
         if marker.contains('*') || marker.contains('/') {
             continue;
         }
@@ -310,43 +1134,235 @@ fn get_last_modified(project_root: &Path, kind: &ProjectKind) -> Result<DateTime
     Ok(DateTime::<Local>::from(time))
 }
 
+/// Total size of the files under `project_root` that aren't inside one of
+/// `targets`, skipping the same directories a scan does (VCS metadata,
+/// dependency caches, build output).
+pub fn source_size(project_root: &Path, targets: &[CleanTarget]) -> u64 {
+    source_file_sizes(project_root, targets).sum()
+}
+
+/// Like [`source_size`], but stops walking once the total passes
+/// `threshold`, for when only "more or less than" matters.
+pub fn source_size_at_least(
+    project_root: &Path,
+    targets: &[CleanTarget],
+    threshold: u64,
+) -> BoundedSize {
+    sum_sizes_until(source_file_sizes(project_root, targets), threshold)
+}
+
+fn source_file_sizes(project_root: &Path, targets: &[CleanTarget]) -> impl Iterator<Item = u64> {
+    WalkDir::new(project_root)
+        .follow_links(false)
+        .into_iter()
+        .filter_entry(|e| should_visit(e) && !targets.iter().any(|t| t.path == e.path()))
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter_map(|e| e.metadata().ok())
+        .map(|m| m.len())
+}
+
+/// A size from [`dir_size_at_least`], which may have stopped counting early.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BoundedSize {
+    /// Everything was counted.
+    Exact(u64),
+    /// Counting stopped once the total reached the threshold; the real size
+    /// is at least this.
+    AtLeast(u64),
+}
+
+impl BoundedSize {
+    /// The bytes counted, exact or not.
+    pub fn bytes(self) -> u64 {
+        match self {
+            Self::Exact(n) | Self::AtLeast(n) => n,
+        }
+    }
+}
+
+/// Sum `sizes`, consuming no more of them once the total reaches `threshold`.
+pub fn sum_sizes_until(sizes: impl IntoIterator<Item = u64>, threshold: u64) -> BoundedSize {
+    let mut total: u64 = 0;
+    for size in sizes {
+        total += size;
+        if total >= threshold {
+            return BoundedSize::AtLeast(total);
+        }
+    }
+    BoundedSize::Exact(total)
+}
+
 /// Calculate the total size of a directory recursively.
 pub fn dir_size(path: &Path) -> Result<u64> {
+    Ok(dir_size_and_mtime(path)?.0)
+}
+
+/// Like [`dir_size`], but stops walking once the total reaches `threshold`.
+/// Only for callers that need to know whether a directory is *that* big, not
+/// exactly how big.
+pub fn dir_size_at_least(path: &Path, threshold: u64) -> BoundedSize {
+    dir_size_at_least_with(path, threshold, &mut Vec::new())
+}
+
+/// Like [`dir_size_at_least`], but records every entry that couldn't be read
+/// in `unreadable`.
+pub fn dir_size_at_least_with(
+    path: &Path,
+    threshold: u64,
+    unreadable: &mut Unreadable,
+) -> BoundedSize {
+    let sizes = WalkDir::new(path).into_iter().filter_map(|entry| {
+        let entry = entry.map_err(|e| record_walk_error(&e, unreadable)).ok()?;
+        if !entry.file_type().is_file() {
+            return None;
+        }
+        let meta = entry.metadata().map_err(|e| record_walk_error(&e, unreadable)).ok()?;
+        Some(meta.len())
+    });
+    sum_sizes_until(sizes, threshold)
+}
+
+/// Calculate the total size of a directory recursively, along with the
+/// newest file modification time seen on the way (`None` if it has no files).
+pub fn dir_size_and_mtime(path: &Path) -> Result<(u64, Option<SystemTime>)> {
+    Ok(dir_size_and_mtime_with(path, &mut Vec::new()))
+}
+
+/// Like [`dir_size_and_mtime`], but records every entry that couldn't be read
+/// in `unreadable` instead of silently leaving it out of the total.
+pub fn dir_size_and_mtime_with(
+    path: &Path,
+    unreadable: &mut Unreadable,
+) -> (u64, Option<SystemTime>) {
     let mut total: u64 = 0;
+    let mut newest: Option<SystemTime> = None;
+
+    for entry in WalkDir::new(path) {
+        let entry = match entry {
+            Ok(e) => e,
+            Err(e) => {
+                record_walk_error(&e, unreadable);
+                continue;
+            }
+        };
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        match entry.metadata() {
+            Ok(meta) => {
+                total += meta.len();
+                if let Ok(modified) = meta.modified() {
+                    newest = newest.max(Some(modified));
+                }
+            }
+            Err(e) => record_walk_error(&e, unreadable),
+        }
+    }
+
+    (total, newest)
+}
+
+/// Remember the path a walk failed on and why, e.g. "Permission denied".
+fn record_walk_error(err: &walkdir::Error, unreadable: &mut Unreadable) {
+    let Some(path) = err.path() else {
+        return;
+    };
+    let reason = err
+        .io_error()
+        .map(|io| io.to_string())
+        .unwrap_or_else(|| err.to_string());
+    unreadable.push((path.to_path_buf(), reason));
+}
 
-    for entry in WalkDir::new(path).into_iter().filter_map(|e| e.ok()) {
-        if entry.file_type().is_file()
+/// How many subdirectories [`estimate_dir_size`] walks before extrapolating.
+const SIZE_SAMPLE_DIRS: usize = 8;
+
+/// Estimate the size of a directory without walking all of it.
+///
+/// Files directly inside `path` are counted exactly. If there are more than
+/// [`SIZE_SAMPLE_DIRS`] subdirectories, an evenly spaced sample of them is
+/// walked and the average scaled up to the full count; otherwise every
+/// subdirectory is walked and the result is exact.
+pub fn estimate_dir_size(path: &Path) -> Result<u64> {
+    let mut files: u64 = 0;
+    let mut subdirs: Vec<PathBuf> = Vec::new();
+
+    for entry in fs::read_dir(path)?.filter_map(|e| e.ok()) {
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        if file_type.is_dir() {
+            subdirs.push(entry.path());
+        } else if file_type.is_file()
             && let Ok(meta) = entry.metadata()
         {
-            total += meta.len();
+            files += meta.len();
         }
     }
 
-    Ok(total)
+    if subdirs.len() <= SIZE_SAMPLE_DIRS {
+        let nested: u64 = subdirs.iter().filter_map(|d| dir_size(d).ok()).sum();
+        return Ok(files + nested);
+    }
+
+    subdirs.sort();
+    let step = subdirs.len() / SIZE_SAMPLE_DIRS;
+    let sampled: u64 = subdirs
+        .iter()
+        .step_by(step)
+        .take(SIZE_SAMPLE_DIRS)
+        .filter_map(|d| dir_size(d).ok())
+        .sum();
+
+    Ok(files + sampled * subdirs.len() as u64 / SIZE_SAMPLE_DIRS as u64)
 }
 
 /// Recursively find all __pycache__ directories under a path.
 pub fn find_pycache_recursive(root: &Path, targets: &mut Vec<CleanTarget>) {
-    for entry in WalkDir::new(root)
-        .into_iter()
-        .filter_entry(|e| {
-            let name = e.file_name().to_string_lossy();
-            !SKIP_DIRS.contains(name.as_ref()) || name == "__pycache__"
-        })
-        .filter_map(|e| e.ok())
-    {
-        if entry.file_type().is_dir()
-            && entry.file_name() == "__pycache__"
-            && entry.depth() > 0
-            && let Ok(size) = dir_size(entry.path())
-            && size > 0
-        {
-            let relative = entry.path().strip_prefix(root).unwrap_or(entry.path());
-            targets.push(CleanTarget {
-                path: entry.path().to_path_buf(),
-                name: relative.display().to_string(),
-                size_bytes: size,
-            });
+    let names = [("__pycache__", TargetCategory::ToolCache)];
+    let config = DevSweepConfig::default();
+    find_dirs_recursive(root, &names, &config, &mut Vec::new(), targets);
+}
+
+/// Recursively find every non-empty directory below `root` named like one of
+/// `names`, in a single walk, and size it like any other target.
+///
+/// Doesn't descend into [`SKIP_DIRS`] (other than the names searched for) or
+/// into a match, so caches nested inside a cache aren't counted twice.
+pub fn find_dirs_recursive(
+    root: &Path,
+    names: &[(&str, TargetCategory)],
+    config: &DevSweepConfig,
+    unreadable: &mut Unreadable,
+    targets: &mut Vec<CleanTarget>,
+) {
+    let category_of = |name: &OsStr| {
+        names
+            .iter()
+            .find(|(n, _)| name == *n)
+            .map(|&(_, category)| category)
+    };
+    let mut entries = WalkDir::new(root).into_iter().filter_entry(|e| {
+        let name = e.file_name();
+        !SKIP_DIRS.contains(name.to_string_lossy().as_ref()) || category_of(name).is_some()
+    });
+
+    while let Some(entry) = entries.next() {
+        let Ok(entry) = entry else {
+            continue;
+        };
+        if !entry.file_type().is_dir() || entry.depth() == 0 {
+            continue;
         }
+        let Some(category) = category_of(entry.file_name()) else {
+            continue;
+        };
+        entries.skip_current_dir();
+
+        let relative = entry.path().strip_prefix(root).unwrap_or(entry.path());
+        let name = relative.display().to_string();
+        let path = entry.path().to_path_buf();
+        targets.extend(as_clean_target(path, name, category, config, unreadable));
     }
 }
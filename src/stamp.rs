@@ -0,0 +1,31 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local};
+
+use crate::config::DevSweepConfig;
+
+/// Where `clean` records when it last finished: `.devclean-stamp`, next to the
+/// config file.
+pub fn stamp_path() -> PathBuf {
+    DevSweepConfig::config_path().with_file_name(".devclean-stamp")
+}
+
+/// Record `at` as the time of the last successful clean (RFC 3339 text).
+pub fn write_stamp(path: &Path, at: DateTime<Local>) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, at.to_rfc3339())
+        .with_context(|| format!("Failed to write clean stamp {}", path.display()))
+}
+
+/// The time recorded by [`write_stamp`], or `None` if there is no stamp (or it
+/// can't be parsed).
+pub fn read_stamp(path: &Path) -> Option<DateTime<Local>> {
+    let contents = fs::read_to_string(path).ok()?;
+    DateTime::parse_from_rfc3339(contents.trim())
+        .ok()
+        .map(|t| t.with_timezone(&Local))
+}
@@ -0,0 +1,264 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Component, Path};
+use std::str::FromStr;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+
+use crate::scanner::{ProjectKind, ScannedProject};
+
+/// How `summary` groups projects into subtotals.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum GroupBy {
+    /// Group by project type (Rust, Node.js, …).
+    #[default]
+    Kind,
+    /// Group by the scan root each project was found under.
+    Root,
+    /// Group by the nth (1-based) path component below the scan root, e.g.
+    /// `prefix=1` puts `~/work/client-a/api` under `client-a`.
+    Prefix(usize),
+}
+
+impl fmt::Display for GroupBy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Kind => write!(f, "kind"),
+            Self::Root => write!(f, "root"),
+            Self::Prefix(n) => write!(f, "prefix={n}"),
+        }
+    }
+}
+
+/// Parses `kind`, `root`, or `prefix=<n>` (n ≥ 1). Round-trips with
+/// `to_string()`.
+impl FromStr for GroupBy {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "kind" => Ok(Self::Kind),
+            "root" => Ok(Self::Root),
+            other => {
+                let n = other
+                    .strip_prefix("prefix=")
+                    .and_then(|n| n.parse::<usize>().ok())
+                    .filter(|&n| n > 0);
+                n.map(Self::Prefix).ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "Invalid grouping '{other}'. Expected kind, root, or prefix=<n> (n ≥ 1)"
+                    )
+                })
+            }
+        }
+    }
+}
+
+/// Reclaimable-space subtotal for one group of projects.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GroupTotal {
+    /// The group label (a kind name or a scan root path).
+    pub key: String,
+    /// Number of projects in the group.
+    pub projects: usize,
+    /// Total reclaimable bytes across the group.
+    pub reclaimable_bytes: u64,
+}
+
+/// The headline numbers of one `summary` run, appended as a JSON line by
+/// `summary --append-log` so reclaimable space can be charted over time.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SummarySnapshot {
+    pub timestamp: DateTime<Local>,
+    pub total_projects: usize,
+    pub total_reclaimable_bytes: u64,
+    /// Reclaimable bytes per project kind, keyed by kind name.
+    pub by_kind: BTreeMap<String, u64>,
+}
+
+impl SummarySnapshot {
+    /// Snapshot the totals of `projects` as of `timestamp`.
+    pub fn new(projects: &[ScannedProject], timestamp: DateTime<Local>) -> Self {
+        let by_kind = group_totals(projects, GroupBy::Kind)
+            .into_iter()
+            .map(|g| (g.key, g.reclaimable_bytes))
+            .collect();
+        Self {
+            timestamp,
+            total_projects: projects.len(),
+            total_reclaimable_bytes: projects.iter().map(|p| p.total_cleanable_bytes).sum(),
+            by_kind,
+        }
+    }
+
+    /// Append the snapshot to `path` as one line of JSON, creating the file
+    /// if needed.
+    pub fn append_to(&self, path: &Path) -> Result<()> {
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .with_context(|| format!("Failed to open log {}", path.display()))?;
+        writeln!(file, "{}", serde_json::to_string(self)?)
+            .with_context(|| format!("Failed to write log {}", path.display()))
+    }
+}
+
+/// Compute per-group subtotals, sorted by reclaimable bytes (largest first).
+pub fn group_totals(projects: &[ScannedProject], group_by: GroupBy) -> Vec<GroupTotal> {
+    let mut groups: HashMap<String, (usize, u64)> = HashMap::new();
+    for p in projects {
+        let key = match group_by {
+            GroupBy::Kind => p.kind_label(),
+            GroupBy::Root => p.scan_root.display().to_string(),
+            GroupBy::Prefix(n) => path_prefix(p, n),
+        };
+        let entry = groups.entry(key).or_insert((0, 0));
+        entry.0 += 1;
+        entry.1 += p.total_cleanable_bytes;
+    }
+
+    let mut totals: Vec<GroupTotal> = groups
+        .into_iter()
+        .map(|(key, (projects, reclaimable_bytes))| GroupTotal {
+            key,
+            projects,
+            reclaimable_bytes,
+        })
+        .collect();
+    totals.sort_by(|a, b| {
+        b.reclaimable_bytes
+            .cmp(&a.reclaimable_bytes)
+            .then_with(|| a.key.cmp(&b.key))
+    });
+    totals
+}
+
+/// The nth (1-based) component of `project`'s path below its scan root, or
+/// `.` for a project that sits less than `n` levels deep.
+fn path_prefix(project: &ScannedProject, n: usize) -> String {
+    let relative = project
+        .path
+        .strip_prefix(&project.scan_root)
+        .unwrap_or(&project.path);
+    relative
+        .components()
+        .filter(|c| matches!(c, Component::Normal(_)))
+        .nth(n - 1)
+        .map(|c| c.as_os_str().to_string_lossy().into_owned())
+        .unwrap_or_else(|| ".".to_string())
+}
+
+/// How reclaimable space is distributed across projects.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SizeStats {
+    pub average_bytes: u64,
+    pub median_bytes: u64,
+    /// Name and reclaimable bytes of the largest project, if there is one.
+    pub largest: Option<(String, u64)>,
+}
+
+/// Compute per-project average, median, and largest reclaimable size.
+pub fn size_stats(projects: &[ScannedProject]) -> SizeStats {
+    let sizes: Vec<u64> = projects.iter().map(|p| p.total_cleanable_bytes).collect();
+    let total: u64 = sizes.iter().sum();
+
+    SizeStats {
+        average_bytes: total.checked_div(sizes.len() as u64).unwrap_or(0),
+        median_bytes: median(&sizes),
+        largest: projects
+            .iter()
+            .max_by_key(|p| p.total_cleanable_bytes)
+            .map(|p| (p.name.clone(), p.total_cleanable_bytes)),
+    }
+}
+
+/// The median of `sizes`, or 0 if empty. With an even count it's the mean of
+/// the two middle values, rounded down.
+pub fn median(sizes: &[u64]) -> u64 {
+    let mut sorted = sizes.to_vec();
+    sorted.sort_unstable();
+
+    let mid = sorted.len() / 2;
+    match sorted.len() {
+        0 => 0,
+        n if n % 2 == 1 => sorted[mid],
+        _ => sorted[mid - 1] + (sorted[mid] - sorted[mid - 1]) / 2,
+    }
+}
+
+/// Count projects by their walk depth below the scan root, shallowest first.
+pub fn depth_histogram(projects: &[ScannedProject]) -> Vec<(usize, usize)> {
+    let mut counts: BTreeMap<usize, usize> = BTreeMap::new();
+    for p in projects {
+        *counts.entry(p.depth).or_default() += 1;
+    }
+    counts.into_iter().collect()
+}
+
+/// Reclaimable space split between recently active and stale projects.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StaleSplit {
+    pub active_projects: usize,
+    pub active_bytes: u64,
+    pub stale_projects: usize,
+    pub stale_bytes: u64,
+}
+
+/// Split projects into active and stale buckets by `last_modified`.
+///
+/// A project is stale if it was last modified before `cutoff`.
+pub fn stale_split(projects: &[ScannedProject], cutoff: DateTime<Local>) -> StaleSplit {
+    let mut split = StaleSplit::default();
+    for p in projects {
+        if p.last_modified < cutoff {
+            split.stale_projects += 1;
+            split.stale_bytes += p.total_cleanable_bytes;
+        } else {
+            split.active_projects += 1;
+            split.active_bytes += p.total_cleanable_bytes;
+        }
+    }
+    split
+}
+
+/// Count and total size of one kind of clean target across a scan.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TargetTotal {
+    pub count: usize,
+    pub bytes: u64,
+}
+
+/// Aggregate every target directory named `dir_name` in projects of `kind`.
+///
+/// Matches on the final path component, so targets folded in from nested
+/// packages (e.g. `packages/web/node_modules`) are counted too.
+pub fn target_totals(
+    projects: &[ScannedProject],
+    kind: ProjectKind,
+    dir_name: &str,
+) -> TargetTotal {
+    let mut total = TargetTotal::default();
+    for target in projects
+        .iter()
+        .filter(|p| p.kind == kind)
+        .flat_map(|p| &p.clean_targets)
+        .filter(|t| t.path.file_name().is_some_and(|n| n == dir_name))
+    {
+        total.count += 1;
+        total.bytes += target.size_bytes;
+    }
+    total
+}
+
+/// Whether a watch tick with `current` reclaimable bytes should raise an alert.
+///
+/// Alerts fire only on the tick where the total first rises above `threshold`
+/// (or on the first tick, if it starts above), not on every tick it stays there.
+pub fn threshold_crossed(previous: Option<u64>, current: u64, threshold: u64) -> bool {
+    current > threshold && previous.is_none_or(|prev| prev <= threshold)
+}
@@ -0,0 +1,115 @@
+use std::io;
+use std::time::Duration;
+
+use anyhow::Result;
+
+use super::colors::yellow;
+use super::display::{self, PromptReader};
+
+/// Where `clean` gets its answers from: yes/no questions and which projects
+/// to select.
+///
+/// The interactive implementation reads stdin; [`AlwaysYes`] and [`AlwaysNo`]
+/// answer without a terminal, for scripts, embedding, and tests.
+pub trait Confirmer {
+    /// Answer a y/N question.
+    fn confirm(&self, prompt: &str) -> Result<bool>;
+
+    /// Answer a question that interactively requires typing `word` in full.
+    fn confirm_word(&self, prompt: &str, word: &str) -> Result<bool>;
+
+    /// Pick some of `items`, returning their indices.
+    fn select(&self, prompt: &str, items: &[String]) -> Result<Vec<usize>>;
+}
+
+/// Prompt on the terminal (the default).
+pub struct Interactive {
+    /// Give up on a prompt after this long without input (`--prompt-timeout`).
+    timeout: Option<Duration>,
+    reader: PromptReader,
+}
+
+/// Answer yes to every prompt and select every item (`--confirm=true`).
+pub struct AlwaysYes;
+
+/// Answer no to every prompt and select nothing (`--confirm=false`).
+pub struct AlwaysNo;
+
+impl Interactive {
+    /// Prompt on stdin, giving up after `timeout` without input (`None`
+    /// waits forever).
+    pub fn new(timeout: Option<Duration>) -> Self {
+        Self::with_reader(PromptReader::stdin(), timeout)
+    }
+
+    /// Prompt with answers from `reader` instead of stdin.
+    pub fn with_reader(reader: PromptReader, timeout: Option<Duration>) -> Self {
+        Self { timeout, reader }
+    }
+
+    /// Read one answer. A timeout reads as an empty line, which every prompt
+    /// treats as "no" (or selecting nothing).
+    fn read_answer(&self) -> Result<String> {
+        match self.reader.read_line(self.timeout)? {
+            Some(line) => Ok(line),
+            None => {
+                println!();
+                let secs = self.timeout.unwrap_or_default().as_secs();
+                eprintln!("  {} No answer within {}s; treating it as no.", yellow("⚠"), secs);
+                Ok(String::new())
+            }
+        }
+    }
+}
+
+impl Confirmer for Interactive {
+    fn confirm(&self, prompt: &str) -> Result<bool> {
+        display::confirm(prompt, &mut || self.read_answer())
+    }
+
+    fn confirm_word(&self, prompt: &str, word: &str) -> Result<bool> {
+        display::confirm_word(prompt, word, &mut || self.read_answer())
+    }
+
+    fn select(&self, prompt: &str, items: &[String]) -> Result<Vec<usize>> {
+        display::multi_select(prompt, items, &mut || self.read_answer(), &mut io::stdout())
+    }
+}
+
+impl Confirmer for AlwaysYes {
+    fn confirm(&self, _prompt: &str) -> Result<bool> {
+        Ok(true)
+    }
+
+    fn confirm_word(&self, _prompt: &str, _word: &str) -> Result<bool> {
+        Ok(true)
+    }
+
+    fn select(&self, _prompt: &str, items: &[String]) -> Result<Vec<usize>> {
+        Ok((0..items.len()).collect())
+    }
+}
+
+impl Confirmer for AlwaysNo {
+    fn confirm(&self, _prompt: &str) -> Result<bool> {
+        Ok(false)
+    }
+
+    fn confirm_word(&self, _prompt: &str, _word: &str) -> Result<bool> {
+        Ok(false)
+    }
+
+    fn select(&self, _prompt: &str, _items: &[String]) -> Result<Vec<usize>> {
+        Ok(Vec::new())
+    }
+}
+
+/// Pick the confirmer for a `--confirm` value: `None` prompts interactively,
+/// giving up after `timeout` (`--prompt-timeout`).
+pub fn confirmer_for(answer: Option<bool>, timeout: Option<Duration>) -> Box<dyn Confirmer> {
+    match answer {
+        None => Box::new(Interactive::new(timeout)),
+        Some(true) => Box::new(AlwaysYes),
+        Some(false) => Box::new(AlwaysNo),
+    }
+}
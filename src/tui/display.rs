@@ -1,11 +1,34 @@
+use std::cell::Cell;
 use std::io::{self, Write};
+use std::path::PathBuf;
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
 
+use crate::caches::GlobalCache;
 use crate::cleaner::CleanResult;
-use crate::scanner::ScannedProject;
+use crate::scanner::{CleanTarget, KindRules, ProjectKind, ScannedProject, TargetCategory};
+use crate::summary::{GroupBy, group_totals};
 use crate::tui::colors::{
     blue, bold, cyan, cyan_bold, dim, green, green_bold, red, yellow, yellow_bold,
 };
-use crate::util::{format_age, format_bytes, pad_left, pad_right, shorten_path, truncate};
+use crate::util::{
+    SizeFormat, format_age, format_bytes_du, format_throughput, pad_left, pad_right, shorten_path,
+    truncate, visible_len,
+};
+
+/// Display switches from the command line, passed to every renderer that
+/// shows sizes or targets.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DisplayOptions {
+    /// `--exact-bytes` and `--precision`.
+    pub sizes: SizeFormat,
+    /// Annotate targets with their restore cost (`--estimate-restore-cost`).
+    pub restore_cost: bool,
+    /// List at most this many targets per project in the table
+    /// (`--max-targets`).
+    pub max_targets: Option<usize>,
+}
 
 // ── Table rendering ─────────────────────────────────────────────────────────
 
@@ -19,208 +42,821 @@ struct TableRow {
     path: String,
 }
 
-/// Print a formatted table of scanned projects.
-pub fn print_results_table(projects: &[ScannedProject]) {
-    if projects.is_empty() {
-        println!(
-            "\n  {} No projects with cleanable artifacts found.\n",
-            blue("ℹ")
-        );
-        return;
-    }
-
-    let total_bytes: u64 = projects.iter().map(|p| p.total_cleanable_bytes).sum();
-    let total_projects = projects.len();
-
-    println!(
-        "\n  {} Found {} projects with {} of reclaimable space\n",
-        green_bold("✓"),
-        cyan_bold(&total_projects.to_string()),
-        yellow_bold(&format_bytes(total_bytes)),
-    );
-
+/// Build the display rows shared by the table and Markdown renderers.
+fn table_rows(projects: &[ScannedProject], opts: &DisplayOptions) -> Vec<TableRow> {
     let now = chrono::Local::now();
 
-    let rows: Vec<TableRow> = projects
+    projects
         .iter()
         .enumerate()
         .map(|(i, p)| {
             let age = now.signed_duration_since(p.last_modified);
             let age_str = format_age(age);
 
-            let targets_str = p
-                .clean_targets
-                .iter()
-                .map(|t| format!("{} ({})", t.name, format_bytes(t.size_bytes)))
-                .collect::<Vec<_>>()
-                .join(", ");
+            let targets_str = targets_cell(&p.clean_targets, opts);
 
             let display_path = shorten_path(&p.path.display().to_string());
 
+            // Flag projects with uncommitted changes (only known with `--git`),
+            // and those that need a network download to restore.
+            let mut name = p.name.clone();
+            if p.git_dirty == Some(true) {
+                name.push_str(" *");
+            }
+            if p.needs_network_restore() {
+                name.push_str(" ⚠");
+            }
+
             TableRow {
                 index: format!("{}", i + 1),
-                name: p.name.clone(),
-                kind: p.kind.to_string(),
-                size: format_bytes(p.total_cleanable_bytes),
+                name,
+                kind: p.kind_label(),
+                size: opts.sizes.approx(p.total_cleanable_bytes, p.is_estimate()),
                 targets: targets_str,
                 last_modified: age_str,
                 path: display_path,
             }
         })
-        .collect();
+        .collect()
+}
 
-    // Calculate column widths
-    let headers = [
-        "#",
-        "Project",
-        "Type",
-        "Cleanable",
-        "Targets",
-        "Last Modified",
-        "Path",
-    ];
-    let mut widths: Vec<usize> = headers.iter().map(|h| h.len()).collect();
+/// The targets column of one row. Past [`DisplayOptions::max_targets`], only
+/// the largest targets are listed and the rest are summarized; every target
+/// is still cleaned.
+fn targets_cell(targets: &[CleanTarget], opts: &DisplayOptions) -> String {
+    let mut shown: Vec<&CleanTarget> = targets.iter().collect();
+    let mut omitted = Vec::new();
+    if let Some(max) = opts.max_targets
+        && shown.len() > max
+    {
+        shown.sort_by_key(|t| std::cmp::Reverse(t.size_bytes));
+        omitted = shown.split_off(max);
+    }
 
-    for row in &rows {
-        widths[0] = widths[0].max(row.index.len());
-        widths[1] = widths[1].max(row.name.len());
-        widths[2] = widths[2].max(row.kind.len());
-        widths[3] = widths[3].max(row.size.len());
-        widths[4] = widths[4].max(row.targets.len());
-        widths[5] = widths[5].max(row.last_modified.len());
-        widths[6] = widths[6].max(row.path.len());
+    let mut labels: Vec<String> = shown.iter().map(|t| target_label(t, opts)).collect();
+    if !omitted.is_empty() {
+        let omitted_bytes: u64 = omitted.iter().map(|t| t.size_bytes).sum();
+        labels.push(format!(
+            "... and {} more targets ({} total)",
+            omitted.len(),
+            opts.sizes.bytes(omitted_bytes)
+        ));
     }
+    labels.join(", ")
+}
 
-    // Clamp columns to prevent insanely wide tables
-    widths[4] = widths[4].min(50);
-    widths[6] = widths[6].min(45);
+/// How a target is listed in the results table, e.g. `node_modules (1.2 GB)`,
+/// with `active`/`ambiguous` flags and (with `--estimate-restore-cost`) its
+/// restore cost inside the parentheses.
+pub fn target_label(target: &CleanTarget, opts: &DisplayOptions) -> String {
+    let size = if target.at_least {
+        format!("≥{}", opts.sizes.bytes(target.size_bytes))
+    } else {
+        opts.sizes.approx(target.size_bytes, target.estimated)
+    };
+    let flag = if target.active {
+        ", active"
+    } else if target.ambiguous {
+        ", ambiguous"
+    } else {
+        ""
+    };
+    let cost = if opts.restore_cost {
+        format!(", {}", target.category.restore_cost())
+    } else {
+        String::new()
+    };
+    format!("{} ({}{}{})", target.name, size, flag, cost)
+}
 
-    let stdout = io::stdout();
-    let mut out = stdout.lock();
-
-    // Top border
-    write!(out, "  ╭").unwrap();
-    for (i, w) in widths.iter().enumerate() {
-        write!(out, "{}", "─".repeat(w + 2)).unwrap();
-        if i < widths.len() - 1 {
-            write!(out, "┬").unwrap();
+/// The aggregate shown in the header and footer of the results table: the
+/// exact sum of every project's reclaimable bytes, not of the rounded rows.
+pub fn table_total(projects: &[ScannedProject]) -> u64 {
+    projects.iter().map(|p| p.total_cleanable_bytes).sum()
+}
+
+/// A column of the results table, as named in `--columns`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Column {
+    /// The row number used when selecting projects to clean.
+    #[value(alias = "#")]
+    Index,
+    Project,
+    Type,
+    Cleanable,
+    Targets,
+    #[value(name = "modified")]
+    LastModified,
+    Path,
+}
+
+impl Column {
+    /// Every column, in the default order.
+    pub const ALL: [Column; 7] = [
+        Self::Index,
+        Self::Project,
+        Self::Type,
+        Self::Cleanable,
+        Self::Targets,
+        Self::LastModified,
+        Self::Path,
+    ];
+
+    fn header(self) -> &'static str {
+        match self {
+            Self::Index => "#",
+            Self::Project => "Project",
+            Self::Type => "Type",
+            Self::Cleanable => "Cleanable",
+            Self::Targets => "Targets",
+            Self::LastModified => "Last Modified",
+            Self::Path => "Path",
         }
     }
-    writeln!(out, "╮").unwrap();
 
-    // Header row
-    write!(out, "  │").unwrap();
-    for (i, header) in headers.iter().enumerate() {
-        let padded = if i == 0 || i == 3 {
-            pad_left(&bold(header), widths[i])
-        } else {
-            pad_right(&bold(header), widths[i])
-        };
-        write!(out, " {padded} │").unwrap();
+    /// The unstyled text of this column in `row`.
+    fn text(self, row: &TableRow) -> &str {
+        match self {
+            Self::Index => &row.index,
+            Self::Project => &row.name,
+            Self::Type => &row.kind,
+            Self::Cleanable => &row.size,
+            Self::Targets => &row.targets,
+            Self::LastModified => &row.last_modified,
+            Self::Path => &row.path,
+        }
     }
-    writeln!(out).unwrap();
 
-    // Header separator
-    write!(out, "  ├").unwrap();
-    for (i, w) in widths.iter().enumerate() {
-        write!(out, "{}", "─".repeat(w + 2)).unwrap();
-        if i < widths.len() - 1 {
-            write!(out, "┼").unwrap();
+    /// Numbers line up on the right; everything else on the left.
+    fn pad(self, text: &str, width: usize) -> String {
+        match self {
+            Self::Index | Self::Cleanable => pad_left(text, width),
+            _ => pad_right(text, width),
         }
     }
-    writeln!(out, "┤").unwrap();
+}
+
+/// Print a formatted table of scanned projects with all columns.
+pub fn print_results_table(projects: &[ScannedProject], opts: &DisplayOptions) {
+    print_results_table_with(projects, &Column::ALL, opts);
+}
+
+/// Print a formatted table of scanned projects, showing only `columns`, in
+/// that order (`--columns`).
+pub fn print_results_table_with(
+    projects: &[ScannedProject],
+    columns: &[Column],
+    opts: &DisplayOptions,
+) {
+    let stdout = io::stdout();
+    write_results_table(&mut stdout.lock(), projects, columns, opts).unwrap();
+}
+
+/// Write the results table (see [`print_results_table_with`]) to `out`.
+pub fn write_results_table(
+    out: &mut dyn Write,
+    projects: &[ScannedProject],
+    columns: &[Column],
+    opts: &DisplayOptions,
+) -> io::Result<()> {
+    if projects.is_empty() {
+        writeln!(
+            out,
+            "\n  {} No projects with cleanable artifacts found.\n",
+            blue("ℹ")
+        )?;
+        return Ok(());
+    }
+
+    let total_projects = projects.len();
+    let estimated = projects.iter().any(|p| p.is_estimate());
+    let total_size = opts.sizes.approx(table_total(projects), estimated);
+
+    writeln!(
+        out,
+        "\n  {} Found {} projects with {} of reclaimable space\n",
+        green_bold("✓"),
+        cyan_bold(&total_projects.to_string()),
+        yellow_bold(&total_size),
+    )?;
+
+    let rows = table_rows(projects, opts);
+    let total_targets: usize = projects.iter().map(|p| p.clean_targets.len()).sum();
+    let total_targets = format!("{total_targets} targets");
+
+    // Calculate column widths, counting the footer too
+    let widths: Vec<usize> = columns
+        .iter()
+        .map(|&col| {
+            let footer = match col {
+                Column::Project => "Total".len(),
+                Column::Cleanable => total_size.len(),
+                Column::Targets => total_targets.len(),
+                _ => 0,
+            };
+            let width = rows
+                .iter()
+                .map(|row| visible_len(col.text(row)))
+                .fold(col.header().len().max(footer), usize::max);
+            // Clamp columns to prevent insanely wide tables
+            match col {
+                Column::Targets => width.min(50),
+                Column::Path => width.min(45),
+                _ => width,
+            }
+        })
+        .collect();
+
+    let border = |out: &mut dyn Write, left: &str, mid: &str, right: &str| -> io::Result<()> {
+        write!(out, "  {left}")?;
+        for (i, w) in widths.iter().enumerate() {
+            write!(out, "{}", "─".repeat(w + 2))?;
+            if i < widths.len() - 1 {
+                write!(out, "{mid}")?;
+            }
+        }
+        writeln!(out, "{right}")
+    };
+    let line = |out: &mut dyn Write, fields: &[String]| -> io::Result<()> {
+        write!(out, "  │")?;
+        for field in fields {
+            write!(out, " {field} │")?;
+        }
+        writeln!(out)
+    };
+
+    // Header row
+    border(out, "╭", "┬", "╮")?;
+    let headers: Vec<String> = columns
+        .iter()
+        .zip(&widths)
+        .map(|(col, &w)| col.pad(&bold(col.header()), w))
+        .collect();
+    line(out, &headers)?;
+    border(out, "├", "┼", "┤")?;
 
     // Data rows
     for row in &rows {
-        let fields = [
-            pad_left(&dim(&row.index), widths[0]),
-            pad_right(&row.name, widths[1]),
-            pad_right(&cyan(&row.kind), widths[2]),
-            pad_left(&yellow(&row.size), widths[3]),
-            pad_right(&truncate(&row.targets, widths[4]), widths[4]),
-            pad_right(&dim(&row.last_modified), widths[5]),
-            pad_right(&dim(&truncate(&row.path, widths[6])), widths[6]),
-        ];
-
-        write!(out, "  │").unwrap();
-        for field in &fields {
-            write!(out, " {field} │").unwrap();
+        let fields: Vec<String> = columns
+            .iter()
+            .zip(&widths)
+            .map(|(&col, &w)| {
+                let text = col.text(row);
+                let styled = match col {
+                    Column::Index => dim(text),
+                    Column::Project => text.to_string(),
+                    Column::Type => cyan(text),
+                    Column::Cleanable => yellow(text),
+                    Column::Targets => truncate(text, w),
+                    Column::LastModified => dim(text),
+                    Column::Path => dim(&truncate(text, w)),
+                };
+                col.pad(&styled, w)
+            })
+            .collect();
+        line(out, &fields)?;
+    }
+
+    // Footer: the exact aggregate, the same figure as the header. Rows are
+    // rounded individually, so they needn't visibly add up to it.
+    border(out, "├", "┼", "┤")?;
+    let footer: Vec<String> = columns
+        .iter()
+        .zip(&widths)
+        .map(|(&col, &w)| match col {
+            Column::Project => pad_right(&bold("Total"), w),
+            Column::Cleanable => pad_left(&yellow_bold(&total_size), w),
+            Column::Targets => pad_right(&dim(&total_targets), w),
+            _ => " ".repeat(w),
+        })
+        .collect();
+    line(out, &footer)?;
+    border(out, "╰", "┴", "╯")?;
+
+    if projects.iter().any(|p| p.git_dirty == Some(true)) {
+        writeln!(out, "  {}", dim("* project has uncommitted git changes"))?;
+    }
+    if projects.iter().any(|p| p.needs_network_restore()) {
+        writeln!(
+            out,
+            "  {}",
+            dim("⚠ network restore required — only dependency caches to clean")
+        )?;
+    }
+
+    writeln!(out)
+}
+
+/// Render scanned projects as a GitHub-flavored Markdown table (no ANSI).
+pub fn render_markdown_table(projects: &[ScannedProject], opts: &DisplayOptions) -> String {
+    let mut out = String::from("| Project | Type | Cleanable | Path |\n");
+    out.push_str("| --- | --- | ---: | --- |\n");
+
+    for row in table_rows(projects, opts) {
+        out.push_str(&format!(
+            "| {} | {} | {} | `{}` |\n",
+            markdown_escape(&row.name),
+            markdown_escape(&row.kind),
+            row.size,
+            markdown_escape(&row.path),
+        ));
+    }
+
+    out
+}
+
+/// Render a one-line summary of a project for `--stream`, printed as soon as
+/// the project has been sized.
+pub fn render_stream_line(project: &ScannedProject, opts: &DisplayOptions) -> String {
+    let size = opts.sizes.approx(project.total_cleanable_bytes, project.is_estimate());
+    format!(
+        "  {}  {}  {}  {}",
+        yellow_bold(&pad_left(&size, 10)),
+        pad_right(&project.name, 24),
+        cyan(&pad_right(&project.kind_label(), 10)),
+        dim(&shorten_path(&project.path.display().to_string())),
+    )
+}
+
+/// Print the global (machine-wide) toolchain caches found, one per line.
+pub fn print_global_caches(caches: &[GlobalCache], opts: &DisplayOptions) {
+    if caches.is_empty() {
+        println!("\n  {} No global caches found.\n", blue("ℹ"));
+        return;
+    }
+    println!();
+    for cache in caches {
+        println!(
+            "  {}  {}  {}",
+            yellow_bold(&pad_left(&opts.sizes.bytes(cache.size_bytes), 10)),
+            pad_right(&cache.name, 18),
+            dim(&shorten_path(&cache.path.display().to_string())),
+        );
+    }
+    let total: u64 = caches.iter().map(|c| c.size_bytes).sum();
+    println!("\n  {} {} reclaimable\n", bold("Total:"), green_bold(&opts.sizes.bytes(total)));
+}
+
+/// Render one `size<TAB>absolute-path` line per clean target, like `du -h`.
+///
+/// No header and no colors, so the output can be piped into `sort -h`.
+pub fn render_du_lines(projects: &[ScannedProject]) -> String {
+    let mut out = String::new();
+    for target in projects.iter().flat_map(|p| &p.clean_targets) {
+        let path = std::path::absolute(&target.path).unwrap_or_else(|_| target.path.clone());
+        out.push_str(&format!(
+            "{}\t{}\n",
+            format_bytes_du(target.size_bytes),
+            path.display()
+        ));
+    }
+    out
+}
+
+/// Render each clean target's absolute path terminated by a NUL byte, with
+/// nothing else, for `xargs -0` (`--print0`). Paths are written as their raw
+/// bytes, so names with spaces, newlines, or invalid UTF-8 survive intact.
+pub fn render_print0(projects: &[ScannedProject]) -> Vec<u8> {
+    let mut out = Vec::new();
+    for target in projects.iter().flat_map(|p| &p.clean_targets) {
+        let path = std::path::absolute(&target.path).unwrap_or_else(|_| target.path.clone());
+        out.extend_from_slice(path.as_os_str().as_encoded_bytes());
+        out.push(0);
+    }
+    out
+}
+
+/// Build a d3-style hierarchy of reclaimable space: kinds, then projects, then
+/// individual targets.
+///
+/// Only leaves (targets) carry a `value`, so `d3.hierarchy(data).sum(d => d.value)`
+/// adds up correctly; every inner node has a precomputed `bytes` total instead.
+pub fn render_treemap(projects: &[ScannedProject]) -> serde_json::Value {
+    let kinds: Vec<serde_json::Value> = group_totals(projects, GroupBy::Kind)
+        .into_iter()
+        .map(|group| {
+            let children: Vec<serde_json::Value> = projects
+                .iter()
+                .filter(|p| p.kind_label() == group.key)
+                .map(|p| {
+                    let targets: Vec<serde_json::Value> = p
+                        .clean_targets
+                        .iter()
+                        .map(|t| {
+                            serde_json::json!({
+                                "name": t.name,
+                                "path": t.path,
+                                "value": t.size_bytes,
+                            })
+                        })
+                        .collect();
+                    serde_json::json!({
+                        "name": p.name,
+                        "path": p.path,
+                        "bytes": p.total_cleanable_bytes,
+                        "children": targets,
+                    })
+                })
+                .collect();
+            serde_json::json!({
+                "name": group.key,
+                "bytes": group.reclaimable_bytes,
+                "children": children,
+            })
+        })
+        .collect();
+
+    let total: u64 = projects.iter().map(|p| p.total_cleanable_bytes).sum();
+    serde_json::json!({
+        "name": "dev-sweep",
+        "bytes": total,
+        "children": kinds,
+    })
+}
+
+// ── Kind listing ────────────────────────────────────────────────────────────
+
+/// Each cleanable pattern of `kind`, labelled with how it's matched, and its
+/// category.
+fn kind_patterns(kind: ProjectKind) -> Vec<(String, TargetCategory)> {
+    let labelled = [
+        (kind.cleanable_dirs(), "/"),
+        (kind.recursive_dirs(), "/ (recursive)"),
+        (kind.cleanable_files(), " (file)"),
+    ];
+    labelled
+        .into_iter()
+        .flat_map(|(patterns, suffix)| {
+            patterns
+                .iter()
+                // Python lists its caches both at the top level and recursively.
+                .filter(move |p| suffix != "/" || !kind.recursive_dirs().contains(p))
+                .map(move |p| (format!("{p}{suffix}"), kind.target_category(p)))
+        })
+        .collect()
+}
+
+/// Serialize `value` for JSON output: pretty-printed by default, or on a
+/// single line with `compact` for piping large results elsewhere.
+pub fn render_json<T: serde::Serialize + ?Sized>(
+    value: &T,
+    compact: bool,
+) -> serde_json::Result<String> {
+    if compact {
+        serde_json::to_string(value)
+    } else {
+        serde_json::to_string_pretty(value)
+    }
+}
+
+/// Scan results as JSON. With `--estimate-restore-cost` (see
+/// [`DisplayOptions::restore_cost`]), every clean target also gets a
+/// `restore_cost`.
+pub fn scan_json<T: serde::Serialize + ?Sized>(
+    value: &T,
+    opts: &DisplayOptions,
+) -> serde_json::Result<serde_json::Value> {
+    let mut json = serde_json::to_value(value)?;
+    if opts.restore_cost {
+        add_restore_costs(&mut json);
+    }
+    Ok(json)
+}
+
+/// Give every object under `json` that has a `category` of a clean target
+/// the matching `restore_cost`.
+fn add_restore_costs(json: &mut serde_json::Value) {
+    match json {
+        serde_json::Value::Array(items) => items.iter_mut().for_each(add_restore_costs),
+        serde_json::Value::Object(fields) => {
+            let category = fields
+                .get("category")
+                .and_then(|c| serde_json::from_value::<TargetCategory>(c.clone()).ok());
+            if let Some(category) = category {
+                fields.insert("restore_cost".into(), category.restore_cost().into());
+            }
+            fields.values_mut().for_each(add_restore_costs);
         }
-        writeln!(out).unwrap();
+        _ => {}
     }
+}
+
+/// Describe what each of `kinds` is detected by and what cleaning it removes,
+/// as emitted by `kinds --json`.
+pub fn render_kinds_json(kinds: &[ProjectKind]) -> serde_json::Value {
+    let rules: Vec<KindRules> = kinds.iter().map(ProjectKind::rules).collect();
+    serde_json::to_value(rules).unwrap_or_default()
+}
+
+/// Print each of `kinds` with its marker files and what gets cleaned.
+pub fn print_kinds(kinds: &[ProjectKind]) {
+    let indent = " ".repeat(10);
+    println!(
+        "\n  {} project types\n",
+        cyan_bold(&kinds.len().to_string())
+    );
 
-    // Bottom border
-    write!(out, "  ╰").unwrap();
-    for (i, w) in widths.iter().enumerate() {
-        write!(out, "{}", "─".repeat(w + 2)).unwrap();
-        if i < widths.len() - 1 {
-            write!(out, "┴").unwrap();
+    for &kind in kinds {
+        println!(
+            "  {}  {}",
+            bold(&pad_right(&kind.to_string(), 10)),
+            dim(&kind.marker_files().join(", ")),
+        );
+        let patterns = kind_patterns(kind);
+        if patterns.is_empty() {
+            println!("  {indent}  {}", dim("(detected only, nothing to clean)"));
+        }
+        for (label, category) in patterns {
+            println!("  {indent}  {label} {}", dim(&format!("({category})")));
         }
     }
-    writeln!(out, "╯").unwrap();
+    println!();
+}
 
-    writeln!(out).unwrap();
+/// Escape characters that would break a Markdown table cell.
+fn markdown_escape(s: &str) -> String {
+    s.replace('|', "\\|")
 }
 
 /// Print a summary after cleaning.
-pub fn print_clean_summary(results: &[CleanResult], dry_run: bool) {
+pub fn print_clean_summary(
+    results: &[CleanResult],
+    dry_run: bool,
+    elapsed: Duration,
+    opts: &DisplayOptions,
+) {
+    print!("{}", render_clean_summary(results, dry_run, elapsed, opts));
+}
+
+/// Render the summary printed after cleaning (see [`print_clean_summary`]).
+/// Errors are listed for a dry run too, since `--check-perms` reports the
+/// targets a real clean couldn't delete as errors.
+pub fn render_clean_summary(
+    results: &[CleanResult],
+    dry_run: bool,
+    elapsed: Duration,
+    opts: &DisplayOptions,
+) -> String {
     let total_freed: u64 = results.iter().map(|r| r.bytes_freed).sum();
     let total_targets: usize = results.iter().map(|r| r.targets_cleaned).sum();
     let total_errors: usize = results.iter().map(|r| r.errors.len()).sum();
+    let total_protected: usize = results.iter().map(|r| r.protected.len()).sum();
 
+    let mut out = String::new();
     if dry_run {
-        println!(
-            "\n  {} Dry run complete. {} would be freed from {} targets across {} projects.",
+        out += &format!(
+            "\n  {} Dry run complete. {} would be freed from {} targets across {} projects.\n",
             bold("🔍"),
-            yellow_bold(&format_bytes(total_freed)),
+            yellow_bold(&opts.sizes.bytes(total_freed)),
             cyan(&total_targets.to_string()),
             cyan(&results.len().to_string()),
         );
-        println!(
+        out += &format!(
             "  {} Run without {} to actually clean.\n",
             dim("→"),
             green("--dry-run"),
         );
     } else {
-        println!(
-            "\n  {} Cleaned! {} freed from {} targets across {} projects.",
+        out += &format!(
+            "\n  {} Cleaned! {} freed from {} targets across {} projects.\n",
             bold("🧹"),
-            green_bold(&format_bytes(total_freed)),
+            green_bold(&opts.sizes.bytes(total_freed)),
             cyan(&total_targets.to_string()),
             cyan(&results.len().to_string()),
         );
+        out += &format!(
+            "  {} Took {}\n",
+            dim("⏱"),
+            format_throughput(total_freed, elapsed, opts.sizes)
+        );
+    }
 
-        if total_errors > 0 {
-            println!("  {} {} errors occurred:", yellow("⚠"), total_errors,);
-            for result in results {
-                for error in &result.errors {
-                    println!("    {} {}: {}", red("✗"), result.project_name, error);
-                }
-            }
+    if total_errors > 0 {
+        out += &format!("  {} {} errors occurred:\n", yellow("⚠"), total_errors);
+        for error in results.iter().flat_map(|r| &r.errors) {
+            out += &format!("    {} {}: {}\n", red("✗"), error.project, error);
         }
-        println!();
     }
+    out += &render_protected(results, total_protected);
+    out.push('\n');
+    out
 }
 
-// ── Prompt helpers ──────────────────────────────────────────────────────────
+/// List the targets a clean skipped because they matched `--protect`.
+fn render_protected(results: &[CleanResult], total: usize) -> String {
+    if total == 0 {
+        return String::new();
+    }
+    let mut out = format!("  {} {} protected targets left in place:\n", blue("🛡"), total);
+    for path in results.iter().flat_map(|r| &r.protected) {
+        out += &format!("    {} {}\n", dim("·"), shorten_path(&path.display().to_string()));
+    }
+    out
+}
 
-/// Display a multi-select prompt. Returns the indices selected.
-pub fn multi_select(prompt: &str, items: &[String]) -> anyhow::Result<Vec<usize>> {
-    println!("\n  {}", bold(prompt));
-    println!(
-        "  {}\n",
-        dim("Enter numbers separated by commas/spaces, ranges with dash (e.g. 1,3,5-8), or 'all'")
+// ── Clean progress ──────────────────────────────────────────────────────────
+
+/// How far along a clean is, from 0.0 to 1.0. An empty plan counts as done,
+/// and freeing more than planned (targets that grew) is capped at 1.0.
+pub fn progress_fraction(freed: u64, total: u64) -> f64 {
+    if total == 0 {
+        return 1.0;
+    }
+    (freed as f64 / total as f64).min(1.0)
+}
+
+/// Render a `[████░░░░]  45%  1.2 GB / 2.6 GB` progress line, with a bar
+/// `width` cells wide.
+pub fn render_progress_bar(freed: u64, total: u64, width: usize, opts: &DisplayOptions) -> String {
+    let fraction = progress_fraction(freed, total);
+    let filled = (fraction * width as f64).round() as usize;
+    format!(
+        "[{}{}] {:>3}%  {} / {}",
+        "█".repeat(filled),
+        "░".repeat(width - filled),
+        (fraction * 100.0).floor() as u64,
+        opts.sizes.bytes(freed),
+        opts.sizes.bytes(total),
+    )
+}
+
+/// Redraw the clean progress bar in place on stderr.
+pub fn print_clean_progress(freed: u64, total: u64, opts: &DisplayOptions) {
+    let mut err = io::stderr();
+    let _ = write!(err, "\r  {} {}", dim("→"), render_progress_bar(freed, total, 30, opts));
+    let _ = err.flush();
+}
+
+/// Erase the clean progress bar once cleaning is finished.
+pub fn clear_clean_progress() {
+    let mut err = io::stderr();
+    let _ = write!(err, "\r\x1b[2K");
+    let _ = err.flush();
+}
+
+/// Print a histogram of project counts by depth below the scan root.
+pub fn print_depth_report(histogram: &[(usize, usize)]) {
+    if histogram.is_empty() {
+        return;
+    }
+
+    let widest = histogram.iter().map(|&(_, n)| n).max().unwrap_or(0);
+    let bar_width = 30;
+
+    println!("  {}", dim("Projects by depth:"));
+    for &(depth, count) in histogram {
+        let len = (count * bar_width).div_ceil(widest.max(1));
+        println!(
+            "    {}  {} {}",
+            pad_left(&depth.to_string(), 3),
+            cyan(&"█".repeat(len)),
+            count,
+        );
+    }
+    println!();
+}
+
+/// List paths the scan couldn't read, so users know the sizes shown are
+/// incomplete. Goes to stderr, leaving stdout clean for `--json`.
+pub fn print_unreadable(unreadable: &[(PathBuf, String)]) {
+    if unreadable.is_empty() {
+        return;
+    }
+
+    eprintln!(
+        "\n  {} {} paths couldn't be read, so reported sizes may be incomplete:",
+        yellow("⚠"),
+        unreadable.len(),
     );
+    for (path, reason) in unreadable {
+        eprintln!("    {}  {}", path.display(), dim(reason));
+    }
+    eprintln!();
+}
 
-    for (i, item) in items.iter().enumerate() {
-        println!("    {}  {}", cyan_bold(&format!("{:>3}", i + 1)), item);
+// ── Prompt helpers ──────────────────────────────────────────────────────────
+
+/// Reads lines for prompts on a helper thread that lives as long as the
+/// reader, so a prompt can give up waiting (`--prompt-timeout`).
+///
+/// Lines are only read when a prompt asks for one. A prompt that times out
+/// leaves its read outstanding, and the next prompt waits on that same read
+/// rather than racing a second one for the input; a line that arrived in
+/// between was typed before the new prompt was shown and is dropped.
+pub struct PromptReader {
+    requests: mpsc::Sender<()>,
+    lines: mpsc::Receiver<io::Result<String>>,
+    pending: Cell<bool>,
+}
+
+impl PromptReader {
+    /// A reader for stdin.
+    pub fn stdin() -> Self {
+        Self::new(|| {
+            let mut input = String::new();
+            io::stdin().read_line(&mut input).map(|_| input)
+        })
     }
 
-    print!("\n  {} ", green_bold("❯"));
-    io::stdout().flush()?;
+    /// A reader calling `read_line` (which reads one line of input) on its
+    /// helper thread.
+    pub fn new(mut read_line: impl FnMut() -> io::Result<String> + Send + 'static) -> Self {
+        let (requests, asked) = mpsc::channel::<()>();
+        let (answer, lines) = mpsc::channel();
+        thread::spawn(move || {
+            for () in asked {
+                if answer.send(read_line()).is_err() {
+                    break;
+                }
+            }
+        });
+        Self {
+            requests,
+            lines,
+            pending: Cell::new(false),
+        }
+    }
+
+    /// Read one line, giving up after `timeout` (`None` waits forever).
+    /// Returns `None` on timeout.
+    pub fn read_line(&self, timeout: Option<Duration>) -> anyhow::Result<Option<String>> {
+        if self.pending.get() && self.lines.try_recv().is_ok() {
+            self.pending.set(false);
+        }
+        if !self.pending.replace(true) {
+            self.requests
+                .send(())
+                .map_err(|_| anyhow::anyhow!("Prompt reader stopped"))?;
+        }
+        let read = match timeout {
+            None => self.lines.recv().map_err(|_| anyhow::anyhow!("Prompt reader stopped"))?,
+            Some(timeout) => match self.lines.recv_timeout(timeout) {
+                Ok(read) => read,
+                Err(mpsc::RecvTimeoutError::Timeout) => return Ok(None),
+                Err(mpsc::RecvTimeoutError::Disconnected) => {
+                    anyhow::bail!("Prompt reader stopped")
+                }
+            },
+        };
+        self.pending.set(false);
+        Ok(Some(read?))
+    }
+}
+
+/// Items shown per page by [`multi_select`] when the list is longer.
+pub const SELECT_PAGE_SIZE: usize = 20;
+
+/// Display a multi-select prompt, reading answers from `read_line` and
+/// writing to `out`. Returns the indices selected.
+///
+/// Lists longer than [`SELECT_PAGE_SIZE`] are shown a page at a time: `n`
+/// and `p` move between pages, and anything else is the selection. Items
+/// keep their numbers across pages, so the selection means the same thing on
+/// any page.
+pub fn multi_select(
+    prompt: &str,
+    items: &[String],
+    read_line: &mut dyn FnMut() -> anyhow::Result<String>,
+    out: &mut dyn Write,
+) -> anyhow::Result<Vec<usize>> {
+    writeln!(out, "\n  {}", bold(prompt))?;
+    writeln!(
+        out,
+        "  {}\n",
+        dim("Enter numbers separated by commas/spaces, ranges with dash (e.g. 1,3,5-8), or 'all'")
+    )?;
+
+    let pages = items.len().div_ceil(SELECT_PAGE_SIZE).max(1);
+    let mut page = 0;
+    let input = loop {
+        let start = page * SELECT_PAGE_SIZE;
+        for (i, item) in items.iter().enumerate().skip(start).take(SELECT_PAGE_SIZE) {
+            writeln!(out, "    {}  {}", cyan_bold(&format!("{:>3}", i + 1)), item)?;
+        }
+        if pages > 1 {
+            writeln!(
+                out,
+                "\n  {}",
+                dim(&format!(
+                    "Page {} of {} ({} items) — 'n' next, 'p' previous",
+                    page + 1,
+                    pages,
+                    items.len()
+                ))
+            )?;
+        }
+
+        write!(out, "\n  {} ", green_bold("❯"))?;
+        out.flush()?;
 
-    let mut input = String::new();
-    io::stdin().read_line(&mut input)?;
+        let input = read_line()?;
+        match input.trim() {
+            "n" if pages > 1 => page = (page + 1).min(pages - 1),
+            "p" if pages > 1 => page = page.saturating_sub(1),
+            _ => break input,
+        }
+        writeln!(out)?;
+    };
     let input = input.trim();
 
     if input.is_empty() {
@@ -273,13 +909,43 @@ pub fn parse_selection(input: &str, max: usize) -> anyhow::Result<Vec<usize>> {
     Ok(selected)
 }
 
-/// Display a yes/no confirmation prompt.
-pub fn confirm(prompt: &str) -> anyhow::Result<bool> {
+/// Display a yes/no confirmation prompt, reading the answer from `read_line`.
+pub fn confirm(
+    prompt: &str,
+    read_line: &mut dyn FnMut() -> anyhow::Result<String>,
+) -> anyhow::Result<bool> {
     print!("  {} {} {} ", yellow("⚠"), prompt, dim("[y/N]"));
     io::stdout().flush()?;
 
-    let mut input = String::new();
-    io::stdin().read_line(&mut input)?;
+    let input = read_line()?;
 
     Ok(input.trim().eq_ignore_ascii_case("y") || input.trim().eq_ignore_ascii_case("yes"))
 }
+
+/// Display a confirmation prompt that only accepts typing `word` in full.
+///
+/// Stricter than [`confirm`] for destructive bulk operations, where a stray
+/// `y` keypress shouldn't be enough.
+pub fn confirm_word(
+    prompt: &str,
+    word: &str,
+    read_line: &mut dyn FnMut() -> anyhow::Result<String>,
+) -> anyhow::Result<bool> {
+    print!(
+        "  {} {} {} ",
+        yellow("⚠"),
+        prompt,
+        dim(&format!("Type '{word}' to confirm:"))
+    );
+    io::stdout().flush()?;
+
+    let input = read_line()?;
+
+    Ok(typed_word_matches(&input, word))
+}
+
+/// Whether `input` (a line read from the user) is exactly `word`, ignoring
+/// surrounding whitespace. Case matters, and `y`/`yes` are not accepted.
+pub fn typed_word_matches(input: &str, word: &str) -> bool {
+    input.trim() == word
+}
@@ -1,2 +1,3 @@
 pub mod display;
 pub mod colors;
+pub mod confirm;
@@ -1,8 +1,14 @@
+use std::io::BufRead;
+use std::path::PathBuf;
+use std::time::Duration;
+
 use anyhow::Result;
+use chrono::{DateTime, Local, NaiveDate, NaiveTime};
 
 /// Parse an age string like "30d", "3m", "1y" into a chrono TimeDelta.
 ///
 /// Supported units:
+/// - `h` — hours
 /// - `d` — days
 /// - `w` — weeks
 /// - `m` — months (30 days)
@@ -17,9 +23,11 @@ pub fn parse_age(s: &str) -> Result<chrono::TimeDelta> {
         (&s[..s.len() - 1], 'y')
     } else if s.ends_with('w') {
         (&s[..s.len() - 1], 'w')
+    } else if s.ends_with('h') {
+        (&s[..s.len() - 1], 'h')
     } else {
         anyhow::bail!(
-            "Invalid age format '{}'. Use e.g. '30d' (days), '4w' (weeks), '3m' (months), '1y' (years)",
+            "Invalid age format '{}'. Use e.g. '12h' (hours), '30d' (days), '4w' (weeks), '3m' (months), '1y' (years)",
             s
         );
     };
@@ -28,6 +36,11 @@ pub fn parse_age(s: &str) -> Result<chrono::TimeDelta> {
         .parse()
         .map_err(|_| anyhow::anyhow!("Invalid number in age string: '{}'", num_str))?;
 
+    if unit == 'h' {
+        return chrono::TimeDelta::try_hours(num)
+            .ok_or_else(|| anyhow::anyhow!("Duration too large"));
+    }
+
     let days = match unit {
         'd' => num,
         'w' => num * 7,
@@ -39,26 +52,213 @@ pub fn parse_age(s: &str) -> Result<chrono::TimeDelta> {
     chrono::TimeDelta::try_days(days).ok_or_else(|| anyhow::anyhow!("Duration too large"))
 }
 
-/// Format a byte count into a human-readable string (e.g. "1.5 GB").
+/// Resolve an `--older-than` value to the moment projects must predate.
+///
+/// An ISO date (`2024-01-01`) is an absolute cutoff at local midnight that
+/// day; anything else is a relative age (see [`parse_age`]) counted back from
+/// `now`.
+pub fn parse_cutoff(s: &str, now: DateTime<Local>) -> Result<DateTime<Local>> {
+    let Ok(date) = NaiveDate::parse_from_str(s.trim(), "%Y-%m-%d") else {
+        return Ok(now - parse_age(s)?);
+    };
+    date.and_time(NaiveTime::MIN)
+        .and_local_timezone(Local)
+        .earliest()
+        .ok_or_else(|| anyhow::anyhow!("Midnight on {date} doesn't exist in the local time zone"))
+}
+
+/// Parse a size like "500", "10MB", "1.5G", or "2 GiB" into bytes.
+///
+/// Units are binary (`K` = 1024), matching how sizes are displayed, and are
+/// case-insensitive with an optional `B`/`iB` suffix. A bare number is bytes.
+pub fn parse_size(s: &str) -> Result<u64> {
+    let s = s.trim();
+    let split = s
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(s.len());
+    let (num_str, unit) = s.split_at(split);
+
+    let multiplier: u64 = match unit.trim().to_ascii_lowercase().as_str() {
+        "" | "b" => 1,
+        "k" | "kb" | "kib" => 1 << 10,
+        "m" | "mb" | "mib" => 1 << 20,
+        "g" | "gb" | "gib" => 1 << 30,
+        "t" | "tb" | "tib" => 1 << 40,
+        _ => anyhow::bail!(
+            "Invalid size '{}'. Use e.g. '500' (bytes), '10MB', '1.5G', '2TB'",
+            s
+        ),
+    };
+
+    let num: f64 = num_str
+        .parse()
+        .map_err(|_| anyhow::anyhow!("Invalid number in size: '{}'", s))?;
+
+    let bytes = num * multiplier as f64;
+    if bytes >= u64::MAX as f64 {
+        anyhow::bail!("Size too large: '{}'", s);
+    }
+    Ok(bytes as u64)
+}
+
+/// Match a name against a simple wildcard pattern.
+///
+/// `*` matches any run of characters (including none) and `?` matches exactly
+/// one; everything else matches literally. Matching is case-sensitive.
+pub fn glob_match(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+
+    let (mut p, mut n) = (0, 0);
+    // Position of the last `*` seen, and the name index it was tried against.
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while n < name.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == name[n]) {
+            p += 1;
+            n += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, n));
+            p += 1;
+        } else if let Some((star, matched)) = backtrack {
+            // Let the last `*` swallow one more character and retry.
+            p = star + 1;
+            n = matched + 1;
+            backtrack = Some((star, n));
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// Read a list of paths, one per line.
+///
+/// Surrounding whitespace is trimmed; blank lines and `#` comments are skipped.
+pub fn read_path_list(reader: impl BufRead) -> Result<Vec<PathBuf>> {
+    let mut paths = Vec::new();
+    for line in reader.lines() {
+        let line = line?;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        paths.push(PathBuf::from(line));
+    }
+    Ok(paths)
+}
+
+/// How byte counts are shown: rounded to `precision` decimal places
+/// (`--precision`), or exactly (`--exact-bytes`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SizeFormat {
+    pub exact: bool,
+    pub precision: usize,
+}
+
+impl Default for SizeFormat {
+    fn default() -> Self {
+        Self {
+            exact: false,
+            precision: 1,
+        }
+    }
+}
+
+impl SizeFormat {
+    /// Format `bytes` with [`format_bytes_exact`] or [`format_bytes_prec`].
+    pub fn bytes(self, bytes: u64) -> String {
+        if self.exact {
+            format_bytes_exact(bytes)
+        } else {
+            format_bytes_prec(bytes, self.precision)
+        }
+    }
+
+    /// Format `bytes`, prefixed with `~` when it is an estimate (e.g. "~1.5 GB").
+    pub fn approx(self, bytes: u64, estimated: bool) -> String {
+        if estimated {
+            format!("~{}", self.bytes(bytes))
+        } else {
+            self.bytes(bytes)
+        }
+    }
+}
+
+/// Format a byte count exactly, with thousands separators (e.g. "1,536 B").
+pub fn format_bytes_exact(bytes: u64) -> String {
+    let digits = bytes.to_string();
+    let mut out = String::with_capacity(digits.len() + digits.len() / 3 + 2);
+    for (i, ch) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i) % 3 == 0 {
+            out.push(',');
+        }
+        out.push(ch);
+    }
+    out.push_str(" B");
+    out
+}
+
+/// Format a byte count into a human-readable string (e.g. "1.5 GB") with
+/// the default [`SizeFormat`].
 pub fn format_bytes(bytes: u64) -> String {
+    SizeFormat::default().bytes(bytes)
+}
+
+/// Format a byte count into a human-readable string with `precision` decimal
+/// places (e.g. "9.14 GB" at 2). Plain bytes never get decimals.
+pub fn format_bytes_prec(bytes: u64, precision: usize) -> String {
     const KB: u64 = 1024;
     const MB: u64 = 1024 * KB;
     const GB: u64 = 1024 * MB;
     const TB: u64 = 1024 * GB;
 
     if bytes >= TB {
-        format!("{:.1} TB", bytes as f64 / TB as f64)
+        format!("{:.precision$} TB", bytes as f64 / TB as f64)
     } else if bytes >= GB {
-        format!("{:.1} GB", bytes as f64 / GB as f64)
+        format!("{:.precision$} GB", bytes as f64 / GB as f64)
     } else if bytes >= MB {
-        format!("{:.1} MB", bytes as f64 / MB as f64)
+        format!("{:.precision$} MB", bytes as f64 / MB as f64)
     } else if bytes >= KB {
-        format!("{:.1} KB", bytes as f64 / KB as f64)
+        format!("{:.precision$} KB", bytes as f64 / KB as f64)
     } else {
         format!("{bytes} B")
     }
 }
 
+/// Format how long an operation took and its throughput, e.g. "8.0s (5.0 GB/s)".
+///
+/// Durations under a millisecond are rounded up so the rate stays finite.
+pub fn format_throughput(bytes: u64, elapsed: Duration, sizes: SizeFormat) -> String {
+    let secs = elapsed.as_secs_f64().max(0.001);
+    let rate = (bytes as f64 / secs) as u64;
+    format!("{:.1}s ({}/s)", elapsed.as_secs_f64(), sizes.bytes(rate))
+}
+
+/// Format a byte count the way `du -h` does (e.g. "9.1G", "512K", "12"), so
+/// the output sorts correctly with `sort -h`.
+pub fn format_bytes_du(bytes: u64) -> String {
+    const UNITS: [char; 4] = ['K', 'M', 'G', 'T'];
+
+    if bytes < 1024 {
+        return bytes.to_string();
+    }
+
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    if value < 10.0 {
+        format!("{:.1}{}", value, UNITS[unit])
+    } else {
+        format!("{:.0}{}", value, UNITS[unit])
+    }
+}
+
 /// Visible length of a string (strips ANSI escape sequences).
 pub fn visible_len(s: &str) -> usize {
     let mut len = 0;
@@ -0,0 +1,60 @@
+//! Tests for holding back the active virtualenv. `VIRTUAL_ENV` is read from
+//! the process environment, so these live in their own test binary.
+
+use std::fs;
+use std::path::PathBuf;
+
+use dev_sweep::cleaner::drop_active_venvs;
+use dev_sweep::scanner::ProjectKind;
+use dev_sweep::scanner::walk::analyze_project;
+
+/// Helper: create a fresh temp dir for a test.
+fn test_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("dev_sweep_test_{name}"));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[test]
+fn active_virtualenv_is_flagged_and_skipped() {
+    let dir = test_dir("active_venv");
+    let (app, other) = (dir.join("app"), dir.join("other"));
+    for project in [&app, &other] {
+        fs::create_dir_all(project.join(".venv/lib")).unwrap();
+        fs::create_dir_all(project.join("__pycache__")).unwrap();
+        fs::write(project.join("pyproject.toml"), "[project]").unwrap();
+        fs::write(project.join(".venv/lib/site.py"), "x".repeat(64)).unwrap();
+        fs::write(project.join("__pycache__/m.pyc"), "x".repeat(8)).unwrap();
+    }
+
+    // SAFETY: this is the only test in the binary, so nothing reads the
+    // environment concurrently.
+    unsafe { std::env::set_var("VIRTUAL_ENV", app.join(".venv")) };
+
+    let mut projects = vec![
+        analyze_project(&app, ProjectKind::Python).unwrap(),
+        analyze_project(&other, ProjectKind::Python).unwrap(),
+    ];
+    let venv = |i: usize| {
+        projects[i]
+            .clean_targets
+            .iter()
+            .find(|t| t.name == ".venv")
+            .map(|t| t.active)
+    };
+    assert_eq!(venv(0), Some(true));
+    assert_eq!(venv(1), Some(false));
+
+    let before: Vec<u64> = projects.iter().map(|p| p.total_cleanable_bytes).collect();
+
+    // Only the active one is held back; the rest of its project still cleans
+    let skipped = drop_active_venvs(&mut projects);
+    assert_eq!(skipped, vec![app.join(".venv")]);
+    assert_eq!(projects.len(), 2);
+    assert_eq!(projects[0].total_cleanable_bytes, before[0] - 64);
+    assert_eq!(projects[1].total_cleanable_bytes, before[1]);
+    assert!(projects[0].clean_targets.iter().all(|t| t.name != ".venv"));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
@@ -1,6 +1,7 @@
 //! Tests for the age string parser (e.g. "30d", "3m", "1y", "2w").
 
-use dev_sweep::util::parse_age;
+use chrono::{Local, TimeZone};
+use dev_sweep::util::{parse_age, parse_cutoff};
 
 // ── valid inputs ────────────────────────────────────────────────────────────
 
@@ -22,6 +23,12 @@ fn parse_weeks() {
     assert_eq!(d.num_days(), 14);
 }
 
+#[test]
+fn parse_hours() {
+    let d = parse_age("36h").unwrap();
+    assert_eq!(d.num_hours(), 36);
+}
+
 #[test]
 fn parse_months() {
     let d = parse_age("3m").unwrap();
@@ -108,3 +115,28 @@ fn parse_zero() {
     let d = parse_age("0d").unwrap();
     assert_eq!(d.num_days(), 0);
 }
+
+// ── parse_cutoff ────────────────────────────────────────────────────────────
+
+#[test]
+fn cutoff_from_relative_age_counts_back_from_now() {
+    let now = Local.with_ymd_and_hms(2024, 6, 15, 12, 0, 0).unwrap();
+    let cutoff = parse_cutoff("30d", now).unwrap();
+    assert_eq!(cutoff, Local.with_ymd_and_hms(2024, 5, 16, 12, 0, 0).unwrap());
+}
+
+#[test]
+fn cutoff_from_iso_date_is_local_midnight() {
+    let now = Local.with_ymd_and_hms(2024, 6, 15, 12, 0, 0).unwrap();
+    let cutoff = parse_cutoff("2024-01-01", now).unwrap();
+    assert_eq!(cutoff, Local.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap());
+    // Independent of `now`
+    assert_eq!(parse_cutoff(" 2024-01-01 ", Local::now()).unwrap(), cutoff);
+}
+
+#[test]
+fn cutoff_rejects_invalid_dates_and_ages() {
+    let now = Local::now();
+    assert!(parse_cutoff("2024-13-01", now).is_err());
+    assert!(parse_cutoff("yesterday", now).is_err());
+}
@@ -0,0 +1,146 @@
+//! Tests for global toolchain cache discovery and removal.
+
+use std::fs;
+use std::path::PathBuf;
+
+use dev_sweep::caches::{
+    GlobalCache, caches_from, clean_caches, go_cache_dirs, shared_cargo_target,
+};
+use dev_sweep::cleaner::make_owner_writable;
+use dev_sweep::scanner::TargetCategory;
+
+/// Helper: create a fresh temp dir for a test.
+fn test_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("dev_sweep_test_{name}"));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// Helper: recursively mark `path` and everything below it read-only, the
+/// way `go mod download` leaves the module cache.
+fn make_read_only(path: &std::path::Path) {
+    for entry in walkdir::WalkDir::new(path).contents_first(true) {
+        let entry = entry.unwrap();
+        let mut perms = entry.metadata().unwrap().permissions();
+        perms.set_readonly(true);
+        fs::set_permissions(entry.path(), perms).unwrap();
+    }
+}
+
+fn paths(dirs: &[(String, PathBuf, TargetCategory)]) -> Vec<PathBuf> {
+    dirs.iter().map(|(_, path, _)| path.clone()).collect()
+}
+
+#[test]
+fn go_caches_default_to_user_cache_and_home() {
+    let dirs = go_cache_dirs(
+        &|_| None,
+        Some(PathBuf::from("/home/u/.cache")),
+        Some(PathBuf::from("/home/u")),
+    );
+    assert_eq!(
+        paths(&dirs),
+        vec![
+            PathBuf::from("/home/u/.cache/go-build"),
+            PathBuf::from("/home/u/go/pkg/mod"),
+        ]
+    );
+    assert_eq!(dirs[0].2, TargetCategory::ToolCache);
+    assert_eq!(dirs[1].2, TargetCategory::DependencyCache);
+}
+
+#[test]
+fn go_caches_follow_environment_overrides() {
+    let env = |var: &str| match var {
+        "GOCACHE" => Some("/tmp/gocache".to_string()),
+        "GOPATH" => Some("/opt/go".to_string()),
+        _ => None,
+    };
+    let dirs = go_cache_dirs(&env, None, None);
+    assert_eq!(
+        paths(&dirs),
+        vec![PathBuf::from("/tmp/gocache"), PathBuf::from("/opt/go/pkg/mod")]
+    );
+
+    // GOMODCACHE wins over GOPATH, and GOCACHE=off disables the build cache
+    let env = |var: &str| match var {
+        "GOCACHE" => Some("off".to_string()),
+        "GOPATH" => Some("/opt/go".to_string()),
+        "GOMODCACHE" => Some("/srv/modcache".to_string()),
+        _ => None,
+    };
+    let dirs = go_cache_dirs(&env, Some(PathBuf::from("/home/u/.cache")), None);
+    assert_eq!(paths(&dirs), vec![PathBuf::from("/srv/modcache")]);
+}
+
+#[test]
+fn clean_caches_removes_read_only_module_cache() {
+    let dir = test_dir("caches_go_readonly");
+    let modcache = dir.join("pkg/mod");
+    let module = modcache.join("github.com/acme/lib@v1.2.3");
+    fs::create_dir_all(&module).unwrap();
+    fs::write(module.join("lib.go"), "package lib").unwrap(); // 11
+    fs::write(module.join("go.mod"), "module acme").unwrap(); // 11
+    make_read_only(&modcache);
+
+    let caches = [GlobalCache {
+        name: "Go module cache".to_string(),
+        path: modcache.clone(),
+        size_bytes: 22,
+        category: TargetCategory::DependencyCache,
+    }];
+
+    let dry = clean_caches(&caches, true);
+    assert_eq!(dry[0].bytes_freed, 22);
+    assert!(module.join("lib.go").exists());
+
+    let results = clean_caches(&caches, false);
+    assert!(results[0].errors.is_empty(), "{:?}", results[0].errors);
+    assert_eq!(results[0].bytes_freed, 22);
+    assert!(!modcache.exists());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[cfg(unix)]
+#[test]
+fn read_only_trees_are_made_writable_for_the_owner_only() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = test_dir("caches_owner_writable");
+    let module = dir.join("pkg/mod/lib@v1.0.0");
+    fs::create_dir_all(&module).unwrap();
+    fs::write(module.join("lib.go"), "package lib").unwrap();
+    fs::set_permissions(module.join("lib.go"), fs::Permissions::from_mode(0o444)).unwrap();
+    fs::set_permissions(&module, fs::Permissions::from_mode(0o555)).unwrap();
+
+    make_owner_writable(&dir);
+    let mode = |p: &std::path::Path| fs::metadata(p).unwrap().permissions().mode() & 0o777;
+    assert_eq!(mode(&module.join("lib.go")), 0o644);
+    assert_eq!(mode(&module), 0o755);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn shared_cargo_target_dir_is_reported_once() {
+    let dir = test_dir("caches_cargo_target");
+    let shared = dir.join("cargo-target");
+    fs::create_dir_all(shared.join("debug/deps")).unwrap();
+    fs::write(shared.join("debug/deps/libfoo.rlib"), "x".repeat(64)).unwrap();
+    let shared_str = shared.to_string_lossy().to_string();
+
+    let env = |var: &str| (var == "CARGO_TARGET_DIR").then(|| shared_str.clone());
+    let caches = caches_from(&env, None, None);
+    assert_eq!(caches.len(), 1);
+    assert_eq!(caches[0].path, shared);
+    assert_eq!(caches[0].size_bytes, 64);
+    assert_eq!(caches[0].category, TargetCategory::BuildOutput);
+
+    // A relative CARGO_TARGET_DIR depends on where cargo runs, so it's ignored
+    let relative = |var: &str| (var == "CARGO_TARGET_DIR").then(|| "build".to_string());
+    assert!(shared_cargo_target(&relative).is_none());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
@@ -3,9 +3,22 @@
 use std::fs;
 use std::path::{Path, PathBuf};
 
-use dev_sweep::cleaner::{clean_project, clean_projects};
-use dev_sweep::scanner::walk::analyze_project;
-use dev_sweep::scanner::ProjectKind;
+use dev_sweep::cleaner::{
+    CleanError, CleanManifest, CleanOptions, CleanPlan, CleanReport, TargetReport,
+    build_lock_held, check_deletable, clean_project, clean_project_with,
+    clean_projects, drop_latest_siblings, drop_protected, drop_smaller_than_source,
+    ensure_safe_clean_roots, is_inside_git_dir, is_protected, is_sweeping_root, largest_target,
+    targets_in_use,
+};
+use dev_sweep::cli::{OutputFormat, PurgeArgs};
+use dev_sweep::cli::CleanArgs;
+use dev_sweep::cli::commands::{ScanContext, clean_loop, cmd_clean, cmd_purge};
+use dev_sweep::config::{CustomKind, DevSweepConfig};
+use dev_sweep::scanner::walk::{analyze_project, analyze_project_with};
+use dev_sweep::scanner::{ProjectKind, TargetCategory};
+use dev_sweep::stamp::{read_stamp, write_stamp};
+use dev_sweep::tui::confirm::{AlwaysNo, AlwaysYes};
+use dev_sweep::tui::display::{Column, DisplayOptions};
 
 /// Helper: create a fresh temp dir for a test.
 fn test_dir(name: &str) -> PathBuf {
@@ -49,6 +62,51 @@ fn dry_run_does_not_delete() {
     fs::remove_dir_all(&dir).unwrap();
 }
 
+#[test]
+fn dry_run_json_enumerates_targets() {
+    let dir = test_dir("clean_dryrun_json");
+    fs::write(dir.join("package.json"), "{}").unwrap();
+    fs::create_dir_all(dir.join("node_modules/react")).unwrap();
+    fs::write(dir.join("node_modules/react/index.js"), "react").unwrap(); // 5
+    fs::create_dir_all(dir.join("dist")).unwrap();
+    fs::write(dir.join("dist/bundle.js"), "bundle!").unwrap(); // 7
+
+    let project = analyze_project(&dir, ProjectKind::Node).unwrap();
+    let results = clean_projects(&[&project], true);
+
+    let json = serde_json::to_string_pretty(&CleanReport::new(&results, true)).unwrap();
+    let report: CleanReport = serde_json::from_str(&json).unwrap();
+
+    assert!(report.dry_run);
+    assert_eq!(report.total_bytes_freed, 12);
+
+    let mut targets = report.targets;
+    targets.sort_by(|a, b| a.path.cmp(&b.path));
+    let name = project.name.clone();
+    assert_eq!(
+        targets,
+        vec![
+            TargetReport {
+                project: name.clone(),
+                path: dir.join("dist"),
+                size_bytes: 7,
+                category: TargetCategory::BuildOutput,
+            },
+            TargetReport {
+                project: name,
+                path: dir.join("node_modules"),
+                size_bytes: 5,
+                category: TargetCategory::DependencyCache,
+            },
+        ]
+    );
+
+    // Nothing was touched
+    assert!(dir.join("node_modules").exists());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
 // ── actual deletion ─────────────────────────────────────────────────────────
 
 #[test]
@@ -90,7 +148,80 @@ fn clean_nonexistent_target_reports_error() {
     assert_eq!(result.targets_cleaned, 0);
     assert_eq!(result.bytes_freed, 0);
     assert_eq!(result.errors.len(), 1);
-    assert!(result.errors[0].contains("target_does_not_exist"));
+    assert_eq!(result.errors[0].path, dir.join("target_does_not_exist"));
+    assert!(result.errors[0].to_string().contains("target_does_not_exist"));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn clean_json_reports_structured_errors() {
+    let dir = test_dir("clean_json_errors");
+    create_rust_project(&dir);
+
+    let mut project = analyze_project(&dir, ProjectKind::Rust).unwrap();
+    let broken = dir.join("target_gone");
+    project.clean_targets[0].path = broken.clone();
+
+    let results = [clean_project(&project, false).unwrap()];
+    let json = serde_json::to_string_pretty(&CleanReport::new(&results, false)).unwrap();
+    let report: CleanReport = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(report.errors.len(), 1);
+    let CleanError {
+        project: name,
+        path,
+        message,
+    } = &report.errors[0];
+    assert_eq!(name, &project.name);
+    assert_eq!(path, &broken);
+    assert!(!message.is_empty());
+
+    let raw: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert!(raw["errors"][0]["path"].is_string());
+    assert!(raw["errors"][0]["message"].is_string());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+// ── file targets ────────────────────────────────────────────────────────────
+
+#[test]
+fn clean_removes_file_targets() {
+    let dir = test_dir("clean_latex_files");
+    fs::write(dir.join("paper.tex"), "\\documentclass{article}").unwrap();
+    fs::write(dir.join("paper.aux"), "relax").unwrap();
+    fs::write(dir.join("paper.log"), "This is pdfTeX").unwrap();
+
+    let project = analyze_project(&dir, ProjectKind::Latex).unwrap();
+    let result = clean_project(&project, false).unwrap();
+
+    assert_eq!(result.targets_cleaned, 2);
+    assert_eq!(result.bytes_freed, 19);
+    assert!(result.errors.is_empty());
+    assert!(!dir.join("paper.aux").exists());
+    assert!(!dir.join("paper.log").exists());
+    assert!(dir.join("paper.tex").exists());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn clean_removes_mixed_file_and_dir_targets() {
+    let dir = test_dir("clean_mixed_targets");
+    fs::write(dir.join("setup.py"), "").unwrap();
+    fs::write(dir.join(".coverage"), "coverage data").unwrap();
+    fs::create_dir_all(dir.join(".venv/lib")).unwrap();
+    fs::write(dir.join(".venv/lib/site.py"), "import os").unwrap();
+
+    let project = analyze_project(&dir, ProjectKind::Python).unwrap();
+    let result = clean_project(&project, false).unwrap();
+
+    assert_eq!(result.targets_cleaned, 2);
+    assert!(result.errors.is_empty());
+    assert!(!dir.join(".coverage").exists());
+    assert!(!dir.join(".venv").exists());
+    assert!(dir.join("setup.py").exists());
 
     fs::remove_dir_all(&dir).unwrap();
 }
@@ -158,6 +289,63 @@ fn clean_multiple_projects_for_real() {
     fs::remove_dir_all(&root).unwrap();
 }
 
+#[test]
+fn shared_target_is_deleted_and_credited_once() {
+    let root = test_dir("clean_shared_target");
+    let proj1 = root.join("app1");
+    fs::create_dir_all(&proj1).unwrap();
+    create_rust_project(&proj1);
+    let proj2 = root.join("app2");
+    fs::create_dir_all(&proj2).unwrap();
+    fs::write(proj2.join("Cargo.toml"), "[package]").unwrap();
+
+    // As if app2's target/ were a symlink to app1's
+    let p1 = analyze_project(&proj1, ProjectKind::Rust).unwrap();
+    let mut p2 = p1.clone();
+    p2.name = "app2".to_string();
+    p2.path = proj2.clone();
+    let bytes = p1.total_cleanable_bytes;
+    assert!(bytes > 0);
+
+    for dry_run in [true, false] {
+        let results = clean_projects(&[&p1, &p2], dry_run);
+        assert!(results.iter().all(|r| r.errors.is_empty()), "{results:?}");
+        assert_eq!(results[0].targets_cleaned, 1);
+        assert_eq!(results[0].bytes_freed, bytes);
+        assert_eq!(results[1].targets_cleaned, 0);
+        assert_eq!(results[1].bytes_freed, 0);
+    }
+    assert!(!proj1.join("target").exists());
+
+    fs::remove_dir_all(&root).unwrap();
+}
+
+#[cfg(unix)]
+#[test]
+fn target_reached_through_a_symlinked_project_is_cleaned_once() {
+    let root = test_dir("clean_symlinked_project");
+    let real = root.join("app");
+    fs::create_dir_all(&real).unwrap();
+    create_rust_project(&real);
+    let link = root.join("app-link");
+    std::os::unix::fs::symlink(&real, &link).unwrap();
+
+    let direct = analyze_project(&real, ProjectKind::Rust).unwrap();
+    let via_link = analyze_project(&link, ProjectKind::Rust).unwrap();
+    let bytes = direct.total_cleanable_bytes;
+    assert_eq!(via_link.total_cleanable_bytes, bytes);
+
+    // Whichever comes first, the real directory is the one cleaned
+    let results = clean_projects(&[&via_link, &direct], false);
+    assert!(results.iter().all(|r| r.errors.is_empty()), "{results:?}");
+    assert_eq!(results[0].bytes_freed, 0);
+    assert_eq!(results[1].bytes_freed, bytes);
+    assert_eq!(results[1].cleaned[0].path, real.join("target"));
+    assert!(!real.join("target").exists());
+
+    fs::remove_dir_all(&root).unwrap();
+}
+
 // ── edge case: project with no clean targets ────────────────────────────────
 
 #[test]
@@ -175,3 +363,1019 @@ fn clean_project_with_no_targets() {
 
     fs::remove_dir_all(&dir).unwrap();
 }
+
+// ── single project by path ──────────────────────────────────────────────────
+
+#[test]
+fn clean_force_removes_single_project_target() {
+    let dir = test_dir("clean_force_single");
+    create_rust_project(&dir);
+
+    let roots = vec![dir.clone()];
+    let config = DevSweepConfig::default();
+    let args = CleanArgs {
+        force: true,
+        ..Default::default()
+    };
+    // --force never prompts, so a confirmer that always refuses can't stop it
+    cmd_clean(&scan_context(&roots, &config), &args, &AlwaysNo).unwrap();
+
+    assert!(!dir.join("target").exists());
+    assert!(dir.join("Cargo.toml").exists());
+    assert!(dir.join("src/main.rs").exists());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn clean_force_rejects_non_project() {
+    let dir = test_dir("clean_force_not_project");
+    fs::create_dir_all(dir.join("target")).unwrap();
+    fs::write(dir.join("target/bin"), "data").unwrap();
+
+    let roots = vec![dir.clone()];
+    let config = DevSweepConfig::default();
+    let args = CleanArgs {
+        force: true,
+        ..Default::default()
+    };
+    let err = cmd_clean(&scan_context(&roots, &config), &args, &AlwaysYes).unwrap_err();
+    assert!(err.to_string().contains("Not a project root"));
+    assert!(dir.join("target/bin").exists());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn clean_force_needs_exactly_one_path() {
+    let a = test_dir("clean_force_two_a");
+    let b = test_dir("clean_force_two_b");
+    create_rust_project(&a);
+    create_rust_project(&b);
+
+    let roots = vec![a.clone(), b.clone()];
+    let config = DevSweepConfig::default();
+    let args = CleanArgs {
+        force: true,
+        ..Default::default()
+    };
+    let err = cmd_clean(&scan_context(&roots, &config), &args, &AlwaysYes).unwrap_err();
+    assert!(err.to_string().contains("single project"));
+    assert!(a.join("target").exists() && b.join("target").exists());
+
+    fs::remove_dir_all(&a).unwrap();
+    fs::remove_dir_all(&b).unwrap();
+}
+
+// ── prune_empty ─────────────────────────────────────────────────────────────
+
+#[test]
+fn prune_empty_removes_wrapper_but_keeps_project_root() {
+    let dir = test_dir("clean_prune_empty");
+    fs::write(dir.join("build.sbt"), "name := \"app\"").unwrap();
+    fs::create_dir_all(dir.join("project/target/streams")).unwrap();
+    fs::write(dir.join("project/target/streams/out"), "data").unwrap();
+
+    let project = analyze_project(&dir, ProjectKind::Scala).unwrap();
+    let opts = CleanOptions {
+        prune_empty: true,
+        ..Default::default()
+    };
+    let result = clean_project_with(&project, &opts).unwrap();
+
+    assert!(result.errors.is_empty());
+    // The now-empty `project/` wrapper is pruned...
+    assert!(!dir.join("project").exists());
+    // ...but the project root itself is never removed
+    assert!(dir.exists());
+    assert!(dir.join("build.sbt").exists());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn prune_empty_stops_at_non_empty_dir() {
+    let dir = test_dir("clean_prune_nonempty");
+    fs::write(dir.join("build.sbt"), "name := \"app\"").unwrap();
+    fs::create_dir_all(dir.join("project/target")).unwrap();
+    fs::write(dir.join("project/target/out"), "data").unwrap();
+    fs::write(dir.join("project/plugins.sbt"), "addSbtPlugin()").unwrap();
+
+    let project = analyze_project(&dir, ProjectKind::Scala).unwrap();
+    let opts = CleanOptions {
+        prune_empty: true,
+        ..Default::default()
+    };
+    clean_project_with(&project, &opts).unwrap();
+
+    assert!(!dir.join("project/target").exists());
+    assert!(dir.join("project/plugins.sbt").exists());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn protected_target_is_never_removed_even_when_selected() {
+    let dir = test_dir("clean_protect");
+    fs::write(dir.join("package.json"), "{}").unwrap();
+    fs::create_dir_all(dir.join("node_modules/lib")).unwrap();
+    fs::write(dir.join("node_modules/lib/index.js"), "data").unwrap();
+    fs::create_dir_all(dir.join("dist")).unwrap();
+    fs::write(dir.join("dist/app.js"), "bundle").unwrap();
+
+    let project = analyze_project(&dir, ProjectKind::Node).unwrap();
+    let opts = CleanOptions {
+        protect: vec!["**/dist/**".to_string()],
+        ..Default::default()
+    };
+    let result = clean_project_with(&project, &opts).unwrap();
+
+    assert!(!dir.join("node_modules").exists());
+    assert!(dir.join("dist/app.js").exists());
+    assert_eq!(result.protected, vec![dir.join("dist")]);
+    assert_eq!(result.targets_cleaned, 1);
+    assert_eq!(result.bytes_freed, 4);
+
+    let report = CleanReport::new(&[result], false);
+    assert_eq!(report.protected, vec![dir.join("dist")]);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn target_inside_git_dir_is_refused_without_override() {
+    let dir = test_dir("clean_inside_git");
+    fs::write(dir.join("package.json"), "{}").unwrap();
+    fs::create_dir_all(dir.join("dist")).unwrap();
+    fs::write(dir.join("dist/app.js"), "bundle").unwrap();
+    fs::create_dir_all(dir.join(".git/objects/ab")).unwrap();
+    fs::write(dir.join(".git/objects/ab/cdef"), "blob").unwrap();
+
+    // As if a misconfigured pattern had matched inside the repository
+    let mut project = analyze_project(&dir, ProjectKind::Node).unwrap();
+    let mut git_target = project.clean_targets[0].clone();
+    git_target.path = dir.join(".git/objects");
+    git_target.name = ".git/objects".to_string();
+    project.clean_targets.push(git_target);
+
+    for dry_run in [true, false] {
+        let opts = CleanOptions {
+            dry_run,
+            ..Default::default()
+        };
+        let result = clean_project_with(&project, &opts).unwrap();
+        assert_eq!(result.targets_cleaned, 1);
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(result.errors[0].path, dir.join(".git/objects"));
+        assert!(result.errors[0].message.contains("--confirm-delete-git"));
+        assert!(dir.join(".git/objects/ab/cdef").exists());
+    }
+
+    let opts = CleanOptions {
+        allow_git_dir: true,
+        ..Default::default()
+    };
+    // dist/ is already gone, so only the .git target is left to remove
+    project.clean_targets.retain(|t| t.name == ".git/objects");
+    let result = clean_project_with(&project, &opts).unwrap();
+    assert!(result.errors.is_empty());
+    assert!(!dir.join(".git/objects").exists());
+
+    assert!(is_inside_git_dir(Path::new("/src/app/.git")));
+    assert!(!is_inside_git_dir(Path::new("/src/app/.github/dist")));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[cfg(unix)]
+#[test]
+fn dry_run_check_perms_reports_targets_under_a_read_only_parent() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = test_dir("clean_check_perms");
+    fs::write(dir.join("package.json"), "{}").unwrap();
+    fs::create_dir_all(dir.join("node_modules/pkg")).unwrap();
+    fs::write(dir.join("node_modules/pkg/index.js"), "module.exports = 1").unwrap();
+    let project = analyze_project(&dir, ProjectKind::Node).unwrap();
+
+    fs::set_permissions(&dir, fs::Permissions::from_mode(0o555)).unwrap();
+    // Permissions don't restrict root, so there may be nothing to observe.
+    let enforced = fs::File::create(dir.join("probe")).is_err();
+    let opts = CleanOptions {
+        dry_run: true,
+        check_perms: true,
+        ..Default::default()
+    };
+    let checked = clean_project_with(&project, &opts).unwrap();
+    let unchecked = clean_project_with(&project, &CleanOptions {
+        dry_run: true,
+        ..Default::default()
+    })
+    .unwrap();
+    fs::set_permissions(&dir, fs::Permissions::from_mode(0o755)).unwrap();
+
+    assert!(unchecked.errors.is_empty());
+    assert_eq!(unchecked.targets_cleaned, 1);
+    if enforced {
+        assert_eq!(checked.targets_cleaned, 0);
+        assert_eq!(checked.errors.len(), 1);
+        assert_eq!(checked.errors[0].path, dir.join("node_modules"));
+        assert!(checked.errors[0].message.contains("not writable"));
+    } else {
+        assert!(checked.errors.is_empty());
+    }
+    // Nothing is deleted, and the probe leaves nothing behind
+    assert!(dir.join("node_modules/pkg/index.js").exists());
+    let _ = fs::remove_file(dir.join("probe"));
+    let leftovers: Vec<_> = fs::read_dir(&dir)
+        .unwrap()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_name().to_string_lossy().starts_with(".dev-sweep-probe"))
+        .collect();
+    assert!(leftovers.is_empty());
+    assert!(check_deletable(&dir.join("node_modules")).is_ok());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn ambiguous_build_is_cleaned_unless_safe() {
+    let dir = test_dir("clean_ambiguous_build");
+    fs::write(dir.join("build.gradle"), "plugins {}").unwrap();
+    fs::create_dir_all(dir.join("build")).unwrap();
+    fs::write(dir.join("build/.gitkeep"), "\n").unwrap();
+
+    let roots = vec![dir.clone()];
+    let config = DevSweepConfig::default();
+    let args = CleanArgs {
+        all: true,
+        safe: true,
+        ..Default::default()
+    };
+    cmd_clean(&scan_context(&roots, &config), &args, &AlwaysYes).unwrap();
+    assert!(dir.join("build/.gitkeep").exists());
+
+    // Without --safe it's only flagged
+    let args = CleanArgs {
+        all: true,
+        ..Default::default()
+    };
+    cmd_clean(&scan_context(&roots, &config), &args, &AlwaysYes).unwrap();
+    assert!(!dir.join("build").exists());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+/// Hold (or with `op = LOCK_UN`, release) `file`'s lock the way Cargo does.
+#[cfg(unix)]
+fn flock(file: &fs::File, op: libc::c_int) {
+    use std::os::fd::AsRawFd;
+    assert_eq!(unsafe { libc::flock(file.as_raw_fd(), op) }, 0);
+}
+
+#[cfg(unix)]
+#[test]
+fn target_with_a_held_build_lock_is_flagged_and_skipped_under_safe() {
+    let dir = test_dir("clean_build_lock");
+    create_rust_project(&dir);
+    let lock_path = dir.join("target/debug/.cargo-lock");
+    let lock = fs::File::create(&lock_path).unwrap();
+    let projects = vec![analyze_project(&dir, ProjectKind::Rust).unwrap()];
+    // A lock file nobody holds is just left over from an earlier build
+    assert!(targets_in_use(&projects).is_empty());
+
+    flock(&lock, libc::LOCK_EX);
+    assert_eq!(build_lock_held(&dir.join("target")), Some(lock_path.clone()));
+    assert_eq!(targets_in_use(&projects), [(dir.join("target"), lock_path)]);
+
+    let roots = vec![dir.clone()];
+    let config = DevSweepConfig::default();
+    let args = CleanArgs {
+        all: true,
+        safe: true,
+        ..Default::default()
+    };
+    cmd_clean(&scan_context(&roots, &config), &args, &AlwaysYes).unwrap();
+    assert!(dir.join("target/debug/app").exists());
+
+    // --force and purge honor --safe too
+    let args = CleanArgs {
+        force: true,
+        safe: true,
+        ..Default::default()
+    };
+    cmd_clean(&scan_context(&roots, &config), &args, &AlwaysYes).unwrap();
+    assert!(dir.join("target/debug/app").exists());
+    let args = PurgeArgs {
+        safe: true,
+        ..Default::default()
+    };
+    cmd_purge(&scan_context(&roots, &config), &args).unwrap();
+    assert!(dir.join("target/debug/app").exists());
+
+    flock(&lock, libc::LOCK_UN);
+    assert_eq!(build_lock_held(&dir.join("target")), None);
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[cfg(unix)]
+#[test]
+fn build_locks_are_only_checked_in_cargo_profile_dirs() {
+    let dir = test_dir("clean_build_lock_scope");
+    let triple = dir.join("target/x86_64-unknown-linux-gnu");
+    fs::create_dir_all(triple.join("release")).unwrap();
+    fs::create_dir_all(dir.join("target/doc")).unwrap();
+    fs::create_dir_all(dir.join("dist")).unwrap();
+
+    // Cross-compiled profiles are covered, whichever piece of target/ is cleaned
+    let cross_lock = triple.join("release/.cargo-lock");
+    let cross = fs::File::create(&cross_lock).unwrap();
+    flock(&cross, libc::LOCK_EX);
+    assert_eq!(build_lock_held(&dir.join("target")), Some(cross_lock.clone()));
+    assert_eq!(build_lock_held(&triple), Some(cross_lock.clone()));
+    assert_eq!(build_lock_held(&triple.join("release")), Some(cross_lock));
+    flock(&cross, libc::LOCK_UN);
+
+    // A lock outside a profile dir, or outside target/ entirely, isn't Cargo's
+    let doc = fs::File::create(dir.join("target/doc/.cargo-lock")).unwrap();
+    let dist = fs::File::create(dir.join("dist/.cargo-lock")).unwrap();
+    flock(&doc, libc::LOCK_EX);
+    flock(&dist, libc::LOCK_EX);
+    assert_eq!(build_lock_held(&dir.join("target")), None);
+    assert_eq!(build_lock_held(&dir.join("target/doc")), None);
+    assert_eq!(build_lock_held(&dir.join("dist")), None);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn protect_patterns_cover_the_directory_and_its_contents() {
+    let protect = vec!["**/release/**".to_string()];
+    assert!(is_protected(Path::new("/src/app/target/release"), &protect));
+    assert!(is_protected(Path::new("/src/app/target/release/deps"), &protect));
+    assert!(!is_protected(Path::new("/src/app/target/debug"), &protect));
+    assert!(!is_protected(Path::new("/src/app/target"), &[]));
+}
+
+#[test]
+fn no_pruning_by_default() {
+    let dir = test_dir("clean_prune_default");
+    fs::write(dir.join("build.sbt"), "name := \"app\"").unwrap();
+    fs::create_dir_all(dir.join("project/target")).unwrap();
+    fs::write(dir.join("project/target/out"), "data").unwrap();
+
+    let project = analyze_project(&dir, ProjectKind::Scala).unwrap();
+    clean_project(&project, false).unwrap();
+
+    assert!(!dir.join("project/target").exists());
+    assert!(dir.join("project").exists());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+// ── keep_latest ─────────────────────────────────────────────────────────────
+
+/// Helper: set a directory's mtime to `secs_ago` seconds in the past.
+fn set_age(path: &Path, secs_ago: u64) {
+    let time = std::time::SystemTime::now() - std::time::Duration::from_secs(secs_ago);
+    fs::File::open(path).unwrap().set_modified(time).unwrap();
+}
+
+#[test]
+fn keep_latest_cleans_only_older_glob_sibling() {
+    let dir = test_dir("clean_keep_latest");
+    fs::write(dir.join("CMakeLists.txt"), "project(app)").unwrap();
+    for build in ["cmake-build-debug", "cmake-build-release"] {
+        fs::create_dir_all(dir.join(build)).unwrap();
+        fs::write(dir.join(build).join("app"), "binary").unwrap();
+    }
+    fs::create_dir_all(dir.join("build")).unwrap();
+    fs::write(dir.join("build/app"), "binary").unwrap();
+
+    set_age(&dir.join("cmake-build-debug"), 3600);
+    set_age(&dir.join("cmake-build-release"), 60);
+
+    let mut project = analyze_project(&dir, ProjectKind::CMake).unwrap();
+    assert_eq!(project.clean_targets.len(), 3);
+
+    drop_latest_siblings(&mut project);
+    assert_eq!(project.total_cleanable_bytes, 12);
+
+    clean_project(&project, false).unwrap();
+
+    assert!(!dir.join("cmake-build-debug").exists());
+    assert!(dir.join("cmake-build-release").exists());
+    // `build` comes from an exact pattern, so it has no siblings and is cleaned
+    assert!(!dir.join("build").exists());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn keep_latest_ignores_single_glob_match() {
+    let dir = test_dir("clean_keep_latest_single");
+    fs::write(dir.join("CMakeLists.txt"), "project(app)").unwrap();
+    fs::create_dir_all(dir.join("cmake-build-debug")).unwrap();
+    fs::write(dir.join("cmake-build-debug/app"), "binary").unwrap();
+
+    let mut project = analyze_project(&dir, ProjectKind::CMake).unwrap();
+    drop_latest_siblings(&mut project);
+
+    assert_eq!(project.clean_targets.len(), 1);
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+// ── purge ───────────────────────────────────────────────────────────────────
+
+/// Helper: a workspace with build outputs and dependency caches side by side.
+fn create_purge_workspace(root: &Path) {
+    fs::create_dir_all(root.join("rs")).unwrap();
+    create_rust_project(&root.join("rs"));
+
+    fs::create_dir_all(root.join("cpp/build")).unwrap();
+    fs::write(root.join("cpp/CMakeLists.txt"), "project(x)").unwrap();
+    fs::write(root.join("cpp/build/app.o"), "object").unwrap();
+
+    fs::create_dir_all(root.join("web/node_modules/react")).unwrap();
+    fs::write(root.join("web/package.json"), "{}").unwrap();
+    fs::write(root.join("web/node_modules/react/index.js"), "code").unwrap();
+
+    fs::create_dir_all(root.join("gem/vendor/bundle")).unwrap();
+    fs::write(root.join("gem/Gemfile"), "source 'x'").unwrap();
+    fs::write(root.join("gem/vendor/bundle/rack.rb"), "code").unwrap();
+}
+
+fn scan_context<'a>(roots: &'a [PathBuf], config: &'a DevSweepConfig) -> ScanContext<'a> {
+    ScanContext {
+        roots,
+        max_depth: None,
+        older_than: None,
+        name: None,
+        modified_since: None,
+        json: true,
+        compact: false,
+        format: OutputFormat::Table,
+        columns: &Column::ALL,
+        display: DisplayOptions::default(),
+        print0: false,
+        count_only: false,
+        split_output: None,
+        from: None,
+        fail_on_found: None,
+        allow_sweeping_root: false,
+        depth_report: false,
+        stream: false,
+        report_unreadable: false,
+        stamp: None,
+        config,
+    }
+}
+
+#[test]
+fn purge_removes_build_outputs_but_keeps_dependency_caches() {
+    let dir = test_dir("purge_build_only");
+    create_purge_workspace(&dir);
+
+    let roots = vec![dir.clone()];
+    let config = DevSweepConfig::default();
+    cmd_purge(&scan_context(&roots, &config), &PurgeArgs::default()).unwrap();
+
+    assert!(!dir.join("rs/target").exists());
+    assert!(!dir.join("cpp/build").exists());
+    assert!(dir.join("web/node_modules/react/index.js").exists());
+    assert!(dir.join("gem/vendor/bundle/rack.rb").exists());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn purge_dry_run_deletes_nothing() {
+    let dir = test_dir("purge_dry_run");
+    create_purge_workspace(&dir);
+
+    let roots = vec![dir.clone()];
+    let config = DevSweepConfig::default();
+    let args = PurgeArgs {
+        dry_run: true,
+        ..Default::default()
+    };
+    cmd_purge(&scan_context(&roots, &config), &args).unwrap();
+
+    assert!(dir.join("rs/target").exists());
+    assert!(dir.join("cpp/build").exists());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn purge_leaves_custom_kind_targets_alone() {
+    let dir = test_dir("purge_custom_kind");
+    create_purge_workspace(&dir);
+    fs::create_dir_all(dir.join("mine/.mycache")).unwrap();
+    fs::write(dir.join("mine/BUILD.mine"), "").unwrap();
+    fs::write(dir.join("mine/.mycache/blob"), "data").unwrap();
+
+    let roots = vec![dir.clone()];
+    let config = DevSweepConfig {
+        custom_kinds: vec![CustomKind {
+            name: "MyBuild".to_string(),
+            marker_files: vec!["BUILD.mine".to_string()],
+            cleanable_dirs: vec![".mycache".to_string()],
+        }],
+        ..Default::default()
+    };
+    cmd_purge(&scan_context(&roots, &config), &PurgeArgs::default()).unwrap();
+
+    assert!(!dir.join("rs/target").exists());
+    assert!(dir.join("mine/.mycache/blob").exists());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+// ── sweeping-root guard ─────────────────────────────────────────────────────
+
+#[test]
+fn filesystem_root_and_home_are_sweeping_roots() {
+    assert!(is_sweeping_root(Path::new("/")));
+    if let Some(home) = dirs::home_dir() {
+        assert!(is_sweeping_root(&home));
+    }
+    let dir = test_dir("guard_not_root");
+    assert!(!is_sweeping_root(&dir));
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn clean_at_filesystem_root_errors_without_override() {
+    let roots = vec![PathBuf::from("/")];
+    let config = DevSweepConfig::default();
+    let args = CleanArgs {
+        all: true,
+        dry_run: true,
+        ..Default::default()
+    };
+
+    let err = cmd_clean(&scan_context(&roots, &config), &args, &AlwaysNo).unwrap_err();
+    assert!(err.to_string().contains("--i-know-what-im-doing"));
+
+    assert!(ensure_safe_clean_roots(&roots, true).is_ok());
+}
+
+#[test]
+fn clean_in_temp_dir_passes_guard() {
+    let dir = test_dir("guard_temp_dir");
+    create_rust_project(&dir);
+
+    let roots = vec![dir.clone()];
+    let config = DevSweepConfig::default();
+    let args = CleanArgs {
+        all: true,
+        dry_run: true,
+        ..Default::default()
+    };
+    cmd_clean(&scan_context(&roots, &config), &args, &AlwaysNo).unwrap();
+    assert!(dir.join("target").exists());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+// ── Confirmer ───────────────────────────────────────────────────────────────
+
+#[test]
+fn clean_all_with_always_yes_proceeds() {
+    let dir = test_dir("confirm_always_yes");
+    create_rust_project(&dir);
+
+    let roots = vec![dir.clone()];
+    let config = DevSweepConfig::default();
+    let args = CleanArgs {
+        all: true,
+        confirm_word: true,
+        ..Default::default()
+    };
+    cmd_clean(&scan_context(&roots, &config), &args, &AlwaysYes).unwrap();
+    assert!(!dir.join("target").exists());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn clean_with_max_targets_still_removes_every_target() {
+    let dir = test_dir("clean_max_targets");
+    fs::write(dir.join("setup.py"), "").unwrap();
+    for i in 0..3 {
+        let egg = dir.join(format!("pkg{i}.egg-info"));
+        fs::create_dir_all(&egg).unwrap();
+        fs::write(egg.join("PKG-INFO"), "x".repeat(i + 1)).unwrap();
+    }
+
+    let roots = vec![dir.clone()];
+    let config = DevSweepConfig {
+        max_targets: Some(1),
+        ..Default::default()
+    };
+    let args = CleanArgs {
+        all: true,
+        ..Default::default()
+    };
+    cmd_clean(&scan_context(&roots, &config), &args, &AlwaysYes).unwrap();
+    for i in 0..3 {
+        assert!(!dir.join(format!("pkg{i}.egg-info")).exists());
+    }
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn clean_all_with_always_no_aborts() {
+    let dir = test_dir("confirm_always_no");
+    create_rust_project(&dir);
+
+    let roots = vec![dir.clone()];
+    let config = DevSweepConfig::default();
+    let args = CleanArgs {
+        all: true,
+        ..Default::default()
+    };
+    cmd_clean(&scan_context(&roots, &config), &args, &AlwaysNo).unwrap();
+    assert!(dir.join("target/debug/app").exists());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn selection_goes_through_the_confirmer() {
+    let dir = test_dir("confirm_selection");
+    for name in ["a", "b"] {
+        fs::create_dir_all(dir.join(name)).unwrap();
+        create_rust_project(&dir.join(name));
+    }
+
+    let roots = vec![dir.clone()];
+    let config = DevSweepConfig::default();
+    // Without --all, projects are picked from a list; neither run reads stdin
+    let args = CleanArgs::default();
+    cmd_clean(&scan_context(&roots, &config), &args, &AlwaysNo).unwrap();
+    assert!(dir.join("a/target").exists() && dir.join("b/target").exists());
+
+    cmd_clean(&scan_context(&roots, &config), &args, &AlwaysYes).unwrap();
+    assert!(!dir.join("a/target").exists() && !dir.join("b/target").exists());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+// ── drop_smaller_than_source ────────────────────────────────────────────────
+
+#[test]
+fn skip_smaller_than_source_keeps_partial_builds_only() {
+    let dir = test_dir("skip_smaller_than_source");
+    let (partial, built) = (dir.join("partial"), dir.join("built"));
+    for (project, source, artifacts) in [(&partial, 4096, 16), (&built, 16, 4096)] {
+        fs::create_dir_all(project.join("src")).unwrap();
+        fs::create_dir_all(project.join("target/debug")).unwrap();
+        fs::write(project.join("Cargo.toml"), "[package]").unwrap(); // 9
+        fs::write(project.join("src/main.rs"), "x".repeat(source)).unwrap();
+        fs::write(project.join("target/debug/app"), "x".repeat(artifacts)).unwrap();
+    }
+
+    let config = DevSweepConfig {
+        skip_smaller_than_source: true,
+        ..Default::default()
+    };
+    let mut projects = vec![
+        analyze_project_with(&partial, ProjectKind::Rust, &config).unwrap(),
+        analyze_project_with(&built, ProjectKind::Rust, &config).unwrap(),
+    ];
+    // Source excludes the target/ being cleaned, and stops being counted
+    // once it outweighs the artifacts
+    let partial_source = projects[0].source_bytes.unwrap();
+    assert!(partial_source > 16 && partial_source <= 4096 + 9, "{partial_source}");
+    assert_eq!(projects[1].source_bytes, Some(16 + 9));
+
+    assert_eq!(drop_smaller_than_source(&mut projects), 1);
+    assert_eq!(projects.len(), 1);
+    assert_eq!(projects[0].name, "built");
+
+    // Without the option the source isn't measured, so nothing is skipped
+    let mut unmeasured = vec![analyze_project(&partial, ProjectKind::Rust).unwrap()];
+    assert_eq!(unmeasured[0].source_bytes, None);
+    assert_eq!(drop_smaller_than_source(&mut unmeasured), 0);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+// ── drop_protected ──────────────────────────────────────────────────────────
+
+#[test]
+fn protect_newer_than_keeps_recent_projects_out_of_clean_all() {
+    let dir = test_dir("protect_newer_than");
+    let (old, recent) = (dir.join("old"), dir.join("recent"));
+    for project in [&old, &recent] {
+        fs::create_dir_all(project).unwrap();
+        create_rust_project(project);
+    }
+    let ten_days_ago = std::time::SystemTime::now() - std::time::Duration::from_secs(10 * 86400);
+    fs::File::options()
+        .write(true)
+        .open(old.join("Cargo.toml"))
+        .unwrap()
+        .set_modified(ten_days_ago)
+        .unwrap();
+
+    let mut projects = vec![
+        analyze_project(&old, ProjectKind::Rust).unwrap(),
+        analyze_project(&recent, ProjectKind::Rust).unwrap(),
+    ];
+    let cutoff = chrono::Local::now() - chrono::TimeDelta::try_days(1).unwrap();
+    assert_eq!(drop_protected(&mut projects, cutoff), 1);
+    assert_eq!(projects.len(), 1);
+    assert_eq!(projects[0].name, "old");
+
+    let roots = vec![dir.clone()];
+    let config = DevSweepConfig::default();
+    let args = CleanArgs {
+        all: true,
+        protect_newer_than: Some("1d".to_string()),
+        ..Default::default()
+    };
+    cmd_clean(&scan_context(&roots, &config), &args, &AlwaysYes).unwrap();
+    assert!(!old.join("target").exists());
+    assert!(recent.join("target/debug/app").exists());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+// ── targets_matching ────────────────────────────────────────────────────────
+
+#[test]
+fn targets_matching_cleans_only_matching_dirs_across_projects() {
+    let dir = test_dir("targets_matching");
+    for name in ["web", "site"] {
+        let project = dir.join(name);
+        for sub in ["node_modules/react", "dist"] {
+            fs::create_dir_all(project.join(sub)).unwrap();
+            fs::write(project.join(sub).join("index.js"), "code").unwrap();
+        }
+        fs::write(project.join("package.json"), "{}").unwrap();
+    }
+    fs::create_dir_all(dir.join("rs")).unwrap();
+    create_rust_project(&dir.join("rs"));
+
+    let roots = vec![dir.clone()];
+    let config = DevSweepConfig::default();
+    let args = CleanArgs {
+        all: true,
+        targets_matching: Some("dist".to_string()),
+        ..Default::default()
+    };
+    cmd_clean(&scan_context(&roots, &config), &args, &AlwaysYes).unwrap();
+
+    for name in ["web", "site"] {
+        assert!(!dir.join(name).join("dist").exists());
+        assert!(dir.join(name).join("node_modules/react/index.js").exists());
+    }
+    assert!(dir.join("rs/target/debug/app").exists());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+// ── plan / apply ────────────────────────────────────────────────────────────
+
+#[test]
+fn plan_round_trips_and_applies_in_temp_tree() {
+    let dir = test_dir("plan_round_trip");
+    let tree = dir.join("tree");
+    for name in ["one", "two"] {
+        fs::create_dir_all(tree.join(name)).unwrap();
+        create_rust_project(&tree.join(name));
+    }
+    let plan_file = dir.join("plan.json");
+
+    // Writing the plan deletes nothing and asks for no confirmation.
+    let roots = vec![tree.clone()];
+    let config = DevSweepConfig::default();
+    let plan_args = CleanArgs {
+        all: true,
+        plan: Some(plan_file.clone()),
+        ..Default::default()
+    };
+    cmd_clean(&scan_context(&roots, &config), &plan_args, &AlwaysNo).unwrap();
+    assert!(tree.join("one/target/debug/app").exists());
+    assert!(tree.join("two/target/debug/app").exists());
+
+    let plan = CleanPlan::load(&plan_file).unwrap();
+    assert_eq!(plan.projects.len(), 2);
+    assert!(plan.total_bytes() > 0);
+
+    // Since planning, one target vanished and another grew.
+    fs::remove_dir_all(tree.join("two/target")).unwrap();
+    fs::write(tree.join("one/target/debug/extra"), "more").unwrap();
+    let check = plan.validate(&config);
+    assert_eq!(check.projects.len(), 1);
+    assert_eq!(check.skipped.len(), 1);
+    assert_eq!(check.skipped[0].0, tree.join("two/target"));
+    assert_eq!(check.resized.len(), 1);
+
+    let apply_args = CleanArgs {
+        apply: Some(plan_file.clone()),
+        ..Default::default()
+    };
+    cmd_clean(&scan_context(&roots, &config), &apply_args, &AlwaysNo).unwrap();
+    assert!(!tree.join("one/target").exists());
+    assert!(tree.join("one/Cargo.toml").exists());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn resume_skips_projects_the_manifest_lists_as_cleaned() {
+    let dir = test_dir("clean_resume");
+    let tree = dir.join("tree");
+    for name in ["one", "two", "three"] {
+        fs::create_dir_all(tree.join(name)).unwrap();
+        create_rust_project(&tree.join(name));
+    }
+    // As if a clean had been interrupted right after finishing "one"
+    let manifest_file = dir.join("manifest.json");
+    let interrupted = CleanManifest {
+        completed: vec![tree.join("one")],
+        ..Default::default()
+    };
+    interrupted.save(&manifest_file).unwrap();
+
+    let roots = vec![tree.clone()];
+    let config = DevSweepConfig::default();
+    let args = CleanArgs {
+        all: true,
+        resume: Some(manifest_file.clone()),
+        ..Default::default()
+    };
+    cmd_clean(&scan_context(&roots, &config), &args, &AlwaysYes).unwrap();
+
+    // "one" wasn't attempted again; the rest were cleaned and recorded
+    assert!(tree.join("one/target/debug/app").exists());
+    assert!(!tree.join("two/target").exists());
+    assert!(!tree.join("three/target").exists());
+    let mut completed = CleanManifest::load(&manifest_file).unwrap().completed;
+    completed.sort();
+    assert_eq!(completed, vec![tree.join("one"), tree.join("three"), tree.join("two")]);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn plan_apply_skips_paths_that_are_not_clean_targets() {
+    let dir = test_dir("plan_tampered");
+    create_rust_project(&dir);
+
+    let mut project = analyze_project(&dir, ProjectKind::Rust).unwrap();
+    let mut sneaky = project.clean_targets[0].clone();
+    sneaky.path = dir.join("src");
+    project.clean_targets.push(sneaky);
+    let plan = CleanPlan::new(&[&project]);
+
+    let check = plan.validate(&DevSweepConfig::default());
+    assert_eq!(
+        check.skipped,
+        vec![(
+            dir.join("src"),
+            "no longer a clean target of its project".to_string()
+        )]
+    );
+    assert_eq!(check.projects[0].clean_targets.len(), 1);
+    assert_eq!(check.projects[0].clean_targets[0].path, dir.join("target"));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn since_last_clean_skips_projects_untouched_since_the_stamp() {
+    let dir = test_dir("since_last_clean");
+    let tree = dir.join("tree");
+    let stamp = dir.join(".devclean-stamp");
+    let pause = || std::thread::sleep(std::time::Duration::from_millis(50));
+
+    fs::create_dir_all(tree.join("old")).unwrap();
+    create_rust_project(&tree.join("old"));
+    pause();
+    write_stamp(&stamp, chrono::Local::now()).unwrap();
+    pause();
+    fs::create_dir_all(tree.join("new")).unwrap();
+    create_rust_project(&tree.join("new"));
+
+    let last_clean = read_stamp(&stamp).unwrap();
+    let roots = vec![tree.clone()];
+    let config = DevSweepConfig::default();
+    let ctx = ScanContext {
+        modified_since: Some(last_clean),
+        stamp: Some(&stamp),
+        ..scan_context(&roots, &config)
+    };
+    let args = CleanArgs {
+        all: true,
+        ..Default::default()
+    };
+    cmd_clean(&ctx, &args, &AlwaysYes).unwrap();
+
+    assert!(tree.join("old/target").exists());
+    assert!(!tree.join("new/target").exists());
+    // The clean itself moved the stamp forward.
+    assert!(read_stamp(&stamp).unwrap() > last_clean);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+// ── largest_target ──────────────────────────────────────────────────────────
+
+#[test]
+fn largest_target_picks_biggest_across_projects_and_kinds() {
+    let dir = test_dir("largest_target");
+
+    let rs = dir.join("rs");
+    fs::create_dir_all(&rs).unwrap();
+    create_rust_project(&rs); // target/ = 23 bytes
+
+    let web = dir.join("web");
+    fs::create_dir_all(web.join("node_modules")).unwrap();
+    fs::create_dir_all(web.join("dist")).unwrap();
+    fs::write(web.join("package.json"), "{}").unwrap();
+    fs::write(web.join("node_modules/big.js"), "x".repeat(500)).unwrap();
+    fs::write(web.join("dist/app.js"), "x".repeat(50)).unwrap();
+
+    let tex = dir.join("paper");
+    fs::create_dir_all(&tex).unwrap();
+    fs::write(tex.join("paper.tex"), "").unwrap();
+    fs::write(tex.join("paper.log"), "x".repeat(100)).unwrap();
+
+    let projects = [
+        analyze_project(&rs, ProjectKind::Rust).unwrap(),
+        analyze_project(&web, ProjectKind::Node).unwrap(),
+        analyze_project(&tex, ProjectKind::Latex).unwrap(),
+    ];
+
+    let largest = largest_target(&projects).unwrap();
+    assert_eq!(largest.path, web.join("node_modules"));
+    assert_eq!(largest.size_bytes, 500);
+
+    assert!(largest_target(&projects[..0]).is_none());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+// ── clean --loop ────────────────────────────────────────────────────────────
+
+#[test]
+fn clean_loop_stops_when_no_projects_remain() {
+    let dir = test_dir("clean_loop_drains");
+    create_rust_project(&dir);
+    let project = analyze_project(&dir, ProjectKind::Rust).unwrap();
+
+    // Each rescan finds one project fewer, as if the last pass cleaned one.
+    let mut remaining = vec![vec![], vec![project.clone()], vec![project.clone(), project]];
+    let mut scans = 0;
+    let mut seen = Vec::new();
+
+    let passes = clean_loop(
+        || {
+            scans += 1;
+            Ok(remaining.pop().unwrap())
+        },
+        |projects| {
+            seen.push(projects.len());
+            Ok(true)
+        },
+    )
+    .unwrap();
+
+    assert_eq!(passes, 2);
+    assert_eq!(scans, 3);
+    assert_eq!(seen, vec![2, 1]);
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn clean_loop_stops_when_user_quits() {
+    let dir = test_dir("clean_loop_quit");
+    create_rust_project(&dir);
+    let project = analyze_project(&dir, ProjectKind::Rust).unwrap();
+
+    let mut scans = 0;
+    let passes = clean_loop(
+        || {
+            scans += 1;
+            Ok(vec![project.clone()])
+        },
+        |_| Ok(false),
+    )
+    .unwrap();
+
+    assert_eq!(passes, 1);
+    assert_eq!(scans, 1);
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn clean_loop_runs_no_pass_on_empty_first_scan() {
+    let passes = clean_loop(|| Ok(Vec::new()), |_| panic!("nothing to clean")).unwrap();
+    assert_eq!(passes, 0);
+}
@@ -1,9 +1,12 @@
 //! Tests for configuration: defaults, serialization round-trip, save/load.
 
+use std::ffi::OsString;
 use std::fs;
 use std::path::PathBuf;
 
-use dev_sweep::config::DevSweepConfig;
+use clap::Parser;
+use dev_sweep::cli::{Cli, Commands, expand_arg_files};
+use dev_sweep::config::{DefaultCommand, DevSweepConfig, strip_json_comments};
 use dev_sweep::scanner::ProjectKind;
 
 #[test]
@@ -13,6 +16,8 @@ fn default_config_is_empty() {
     assert!(config.exclude_kinds.is_empty());
     assert!(config.default_roots.is_empty());
     assert!(config.max_depth.is_none());
+    assert!(!config.git);
+    assert_eq!(config.default_command, DefaultCommand::Scan);
 }
 
 #[test]
@@ -22,6 +27,9 @@ fn config_serialization_round_trip() {
         exclude_kinds: vec![ProjectKind::Go, ProjectKind::Terraform],
         default_roots: vec![PathBuf::from("~/projects")],
         max_depth: Some(5),
+        project_depth: Some(2),
+        git: true,
+        ..Default::default()
     };
 
     let json = serde_json::to_string(&config).unwrap();
@@ -31,6 +39,8 @@ fn config_serialization_round_trip() {
     assert_eq!(deserialized.exclude_kinds, config.exclude_kinds);
     assert_eq!(deserialized.default_roots, config.default_roots);
     assert_eq!(deserialized.max_depth, config.max_depth);
+    assert_eq!(deserialized.project_depth, config.project_depth);
+    assert_eq!(deserialized.git, config.git);
 }
 
 #[test]
@@ -52,6 +62,54 @@ fn config_deserializes_empty_object() {
     assert!(config.exclude_kinds.is_empty());
 }
 
+#[test]
+fn config_deserializes_custom_kinds() {
+    let json = r#"{
+        "custom_kinds": [
+            {"name": "MyBuild", "marker_files": ["BUILD.mine"], "cleanable_dirs": [".mycache"]},
+            {"name": "Bare", "marker_files": ["*.bare"]}
+        ]
+    }"#;
+    let config: DevSweepConfig = serde_json::from_str(json).unwrap();
+
+    assert_eq!(config.custom_kinds.len(), 2);
+    assert_eq!(config.custom_kinds[0].name, "MyBuild");
+    assert_eq!(config.custom_kinds[0].marker_files, vec!["BUILD.mine"]);
+    assert_eq!(config.custom_kinds[0].cleanable_dirs, vec![".mycache"]);
+    assert!(config.custom_kinds[1].cleanable_dirs.is_empty());
+}
+
+#[test]
+fn default_command_summary_runs_summary_without_subcommand() {
+    let config = DevSweepConfig::parse(r#"{"default_command": "summary"}"#).unwrap();
+    assert_eq!(config.default_command, DefaultCommand::Summary);
+
+    let cli = Cli::try_parse_from(["dev-sweep"]).unwrap();
+    assert!(cli.command.is_none());
+    let command = cli
+        .command
+        .unwrap_or_else(|| config.default_command.into());
+    let Commands::Summary(args) = command else {
+        panic!("expected the summary command");
+    };
+    assert_eq!(args.stale_after, "30d");
+
+    // An explicit subcommand still wins.
+    let cli = Cli::try_parse_from(["dev-sweep", "scan"]).unwrap();
+    let command = cli
+        .command
+        .unwrap_or_else(|| config.default_command.into());
+    assert!(matches!(command, Commands::Scan));
+}
+
+#[test]
+fn config_rejects_unknown_default_command() {
+    let err = DevSweepConfig::parse(r#"{"default_command": "sumary"}"#).unwrap_err();
+    let msg = err.to_string();
+    assert!(msg.contains("sumary"));
+    assert!(msg.contains("summary"));
+}
+
 #[test]
 fn config_save_and_load() {
     // Use a temp file to avoid polluting the real config
@@ -65,6 +123,7 @@ fn config_save_and_load() {
         exclude_kinds: vec![ProjectKind::Ruby],
         default_roots: vec![PathBuf::from("~/code")],
         max_depth: Some(10),
+        ..Default::default()
     };
 
     // Save
@@ -92,3 +151,87 @@ fn config_load_returns_default_when_missing() {
     let _ = config.ignore_paths;
     let _ = config.max_depth;
 }
+
+#[test]
+fn args_file_expands_like_inline_arguments() {
+    let dir = std::env::temp_dir().join("dev_sweep_test_args_file");
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    let file = dir.join("args.txt");
+    fs::write(
+        &file,
+        "--protect\n**/release/**\n\n--protect\nmy app/dist\r\n--protect=**/.venv\n",
+    )
+    .unwrap();
+
+    let inline = [
+        "dev-sweep",
+        "clean",
+        "--protect",
+        "**/release/**",
+        "--protect",
+        "my app/dist",
+        "--protect=**/.venv",
+        "--all",
+    ];
+    let from_file = vec![
+        OsString::from("dev-sweep"),
+        OsString::from("clean"),
+        OsString::from(format!("@{}", file.display())),
+        OsString::from("--all"),
+    ];
+    let expanded = expand_arg_files(from_file).unwrap();
+    assert_eq!(expanded, inline.map(OsString::from));
+
+    let cli = Cli::try_parse_from(expanded).unwrap();
+    assert_eq!(cli.protect, Cli::try_parse_from(inline).unwrap().protect);
+    assert_eq!(cli.protect, ["**/release/**", "my app/dist", "**/.venv"]);
+
+    // A missing file is an error, not a literal argument
+    let missing = expand_arg_files([OsString::from(format!("@{}", dir.join("nope").display()))]);
+    assert!(missing.unwrap_err().to_string().contains("nope"));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn config_with_comments_loads() {
+    let contents = r#"{
+        // Where I keep everything
+        "default_roots": ["~/code"],
+        /* Shared drives: "//" inside strings is not a comment,
+           and neither is the * / here */
+        "ignore_paths": ["//nas/share/projects", "/tmp/a/*"],
+        "max_depth": 4, // deep enough
+        "default_command": "summary"
+    }"#;
+    let config = DevSweepConfig::parse(contents).unwrap();
+    assert_eq!(config.default_roots, vec![PathBuf::from("~/code")]);
+    assert_eq!(
+        config.ignore_paths,
+        vec![PathBuf::from("//nas/share/projects"), PathBuf::from("/tmp/a/*")]
+    );
+    assert_eq!(config.max_depth, Some(4));
+    assert_eq!(config.default_command, DefaultCommand::Summary);
+
+    // Comments are blanked, not removed, so error lines still match the file
+    let stripped = strip_json_comments("{\n/* a\nb */ \"x\": \"\\\"//\"\n}");
+    assert_eq!(stripped, "{\n\n  \"x\": \"\\\"//\"\n}");
+    let err = DevSweepConfig::parse("{\n  // fine\n  \"max_depth\": \"deep\"\n}").unwrap_err();
+    assert!(err.to_string().contains("line 3"), "{err}");
+}
+
+#[test]
+fn watch_threshold_accepts_human_sizes() {
+    let threshold = |value: &str| {
+        let cli = Cli::try_parse_from(["dev-sweep", "watch", "--threshold", value]).unwrap();
+        let Some(Commands::Watch { threshold, .. }) = cli.command else {
+            panic!("expected the watch command");
+        };
+        threshold
+    };
+    assert_eq!(threshold("20GB"), 20 << 30);
+    assert_eq!(threshold("1.5k"), 1536);
+    assert_eq!(threshold("4096"), 4096);
+    assert!(Cli::try_parse_from(["dev-sweep", "watch", "--threshold", "lots"]).is_err());
+}
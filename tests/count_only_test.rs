@@ -0,0 +1,49 @@
+//! Tests for `--count-only`, run through the binary since the point is what
+//! ends up on stdout.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Helper: create a fresh temp dir for a test.
+fn test_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("dev_sweep_test_{name}"));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// Helper: create a Rust project with some build output.
+fn create_rust_project(root: &Path) {
+    fs::create_dir_all(root.join("target")).unwrap();
+    fs::write(root.join("Cargo.toml"), "[package]").unwrap();
+    fs::write(root.join("target/bin"), "x".repeat(64)).unwrap();
+}
+
+#[test]
+fn count_only_prints_just_the_number() {
+    let base = test_dir("count_only");
+    let projects = base.join("projects");
+    for name in ["a", "b", "c"] {
+        create_rust_project(&projects.join(name));
+    }
+    // Nothing to clean, so not counted
+    fs::create_dir_all(projects.join("clean")).unwrap();
+    fs::write(projects.join("clean/Cargo.toml"), "[package]").unwrap();
+
+    for command in [&["--count-only"][..], &["summary", "--count-only"]] {
+        // A private config dir, so the user's config can't change the result
+        let output = Command::new(env!("CARGO_BIN_EXE_dev-sweep"))
+            .args(command)
+            .arg(&projects)
+            .env("HOME", &base)
+            .env("XDG_CONFIG_HOME", base.join("config"))
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "{command:?}");
+        assert_eq!(String::from_utf8_lossy(&output.stdout), "3\n", "{command:?}");
+        assert_eq!(String::from_utf8_lossy(&output.stderr), "", "{command:?}");
+    }
+
+    fs::remove_dir_all(&base).unwrap();
+}
@@ -0,0 +1,73 @@
+//! Tests for disk usage parsing and before/after-clean percentage math.
+
+use dev_sweep::disk::{DiskUsage, implausible_total_warning, parse_df_output};
+use dev_sweep::util::SizeFormat;
+
+fn usage(total: u64, used: u64, available: u64) -> DiskUsage {
+    DiskUsage {
+        mount: "/".to_string(),
+        total_bytes: total,
+        used_bytes: used,
+        available_bytes: available,
+    }
+}
+
+#[test]
+fn percent_used_before_and_after_clean() {
+    let disk = usage(1000, 910, 90);
+    assert_eq!(disk.percent_used(), 91.0);
+    assert_eq!(disk.percent_used_after(170), 74.0);
+    assert_eq!(disk.projection(170), "bringing disk from 91% to 74% full");
+}
+
+#[test]
+fn percent_ignores_root_reserved_blocks() {
+    // 50 bytes reserved for root: df reports used / (used + available)
+    let disk = usage(1050, 500, 500);
+    assert_eq!(disk.percent_used(), 50.0);
+}
+
+#[test]
+fn percent_after_never_goes_negative() {
+    let disk = usage(100, 10, 90);
+    assert_eq!(disk.percent_used_after(1_000), 0.0);
+    assert_eq!(usage(0, 0, 0).percent_used(), 0.0);
+}
+
+#[test]
+fn parse_df_posix_output() {
+    let out = "Filesystem     1024-blocks     Used Available Capacity Mounted on\n\
+               /dev/vda         264212084 19068048  81734228      19% /\n";
+    let disk = parse_df_output(out).unwrap();
+    assert_eq!(disk.mount, "/");
+    assert_eq!(disk.total_bytes, 264_212_084 * 1024);
+    assert_eq!(disk.used_bytes, 19_068_048 * 1024);
+    assert_eq!(disk.available_bytes, 81_734_228 * 1024);
+}
+
+#[test]
+fn parse_df_handles_spaces_in_names() {
+    let out = "Filesystem 1024-blocks Used Available Capacity Mounted on\n\
+               map auto_home 100 40 60 40% /Volumes/My Disk\n";
+    let disk = parse_df_output(out).unwrap();
+    assert_eq!(disk.mount, "/Volumes/My Disk");
+    assert_eq!(disk.used_bytes, 40 * 1024);
+}
+
+#[test]
+fn parse_df_rejects_garbage() {
+    assert!(parse_df_output("").is_none());
+    assert!(parse_df_output("Filesystem\nnot a df line\n").is_none());
+}
+
+#[test]
+fn reclaimable_above_capacity_warns_about_double_counting() {
+    let disk = usage(1 << 30, 512 << 20, 512 << 20);
+    assert_eq!(implausible_total_warning(1 << 30, &disk, SizeFormat::default()), None);
+    assert_eq!(implausible_total_warning(200 << 20, &disk, SizeFormat::default()), None);
+
+    let warning = implausible_total_warning(5 << 40, &disk, SizeFormat::default()).unwrap();
+    assert!(warning.contains("5.0 TB"), "{warning}");
+    assert!(warning.contains("1.0 GB"), "{warning}");
+    assert!(warning.contains("--one-file-system"), "{warning}");
+}
@@ -1,8 +1,22 @@
-//! Tests for display utilities: byte formatting, ANSI handling, padding, age formatting, truncation.
+//! Tests for display utilities: byte formatting, ANSI handling, padding, age formatting, truncation,
+//! throughput, Markdown/du/treemap output rendering, and the kind listing.
 
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use dev_sweep::cleaner::{CleanError, CleanResult};
+use dev_sweep::scanner::{KindRules, ProjectKind};
+use dev_sweep::scanner::walk::analyze_project;
 use dev_sweep::tui::colors::{blue, bold, cyan, dim, green, green_bold, red, yellow};
+use dev_sweep::tui::display::{
+    Column, DisplayOptions, progress_fraction, render_clean_summary, render_du_lines, render_json,
+    render_kinds_json, render_markdown_table, render_print0, render_progress_bar, render_treemap,
+    table_total, write_results_table,
+};
 use dev_sweep::util::{
-    format_age, format_bytes, pad_left, pad_right, shorten_path, truncate, visible_len,
+    SizeFormat, format_age, format_bytes, format_bytes_du, format_bytes_exact, format_bytes_prec,
+    format_throughput, pad_left, pad_right, shorten_path, truncate, visible_len,
 };
 
 // ── format_bytes ────────────────────────────────────────────────────────────
@@ -51,6 +65,64 @@ fn format_bytes_boundary() {
     assert_eq!(format_bytes(1023), "1023 B");
 }
 
+// ── format_bytes_du ─────────────────────────────────────────────────────────
+
+#[test]
+fn format_bytes_prec_controls_decimal_places() {
+    let bytes = 9_814_073_344; // 9.14 GB
+    assert_eq!(format_bytes_prec(bytes, 0), "9 GB");
+    assert_eq!(format_bytes_prec(bytes, 1), "9.1 GB");
+    assert_eq!(format_bytes_prec(bytes, 2), "9.14 GB");
+    // The default matches precision 1, and plain bytes never get decimals
+    assert_eq!(format_bytes(bytes), format_bytes_prec(bytes, 1));
+    assert_eq!(format_bytes_prec(512, 2), "512 B");
+}
+
+#[test]
+fn format_bytes_exact_groups_thousands() {
+    assert_eq!(format_bytes_exact(0), "0 B");
+    assert_eq!(format_bytes_exact(999), "999 B");
+    assert_eq!(format_bytes_exact(1000), "1,000 B");
+    assert_eq!(format_bytes_exact(1_073_741_824), "1,073,741,824 B");
+    assert_eq!(format_bytes_exact(u64::MAX), "18,446,744,073,709,551,615 B");
+}
+
+#[test]
+fn format_bytes_du_matches_du_style() {
+    assert_eq!(format_bytes_du(0), "0");
+    assert_eq!(format_bytes_du(1023), "1023");
+    assert_eq!(format_bytes_du(1536), "1.5K");
+    assert_eq!(format_bytes_du(512 * 1024), "512K");
+    assert_eq!(format_bytes_du(9_771_050_598), "9.1G");
+}
+
+// ── format_throughput ───────────────────────────────────────────────────────
+
+#[test]
+fn format_throughput_reports_elapsed_and_rate() {
+    let freed = 40 * 1024 * 1024 * 1024;
+    assert_eq!(
+        format_throughput(freed, Duration::from_secs(8), SizeFormat::default()),
+        "8.0s (5.0 GB/s)"
+    );
+    assert_eq!(
+        format_throughput(3 * 1024 * 1024, Duration::from_millis(1500), SizeFormat::default()),
+        "1.5s (2.0 MB/s)"
+    );
+}
+
+#[test]
+fn format_throughput_zero_duration_stays_finite() {
+    assert_eq!(
+        format_throughput(1024, Duration::ZERO, SizeFormat::default()),
+        "0.0s (1000.0 KB/s)"
+    );
+    assert_eq!(
+        format_throughput(0, Duration::from_secs(2), SizeFormat::default()),
+        "2.0s (0 B/s)"
+    );
+}
+
 // ── visible_len ─────────────────────────────────────────────────────────────
 
 #[test]
@@ -284,3 +356,386 @@ fn ansi_helpers_wrap_correctly() {
     assert!(dim("x").contains("x"));
     assert!(blue("x").contains("x"));
 }
+
+// ── table_total ─────────────────────────────────────────────────────────────
+
+#[test]
+fn table_total_is_exact_sum_not_sum_of_rounded_rows() {
+    let base = std::env::temp_dir().join("dev_sweep_test_table_total");
+    let _ = fs::remove_dir_all(&base);
+
+    let mut projects = Vec::new();
+    for name in ["a", "b", "c"] {
+        let dir = base.join(name);
+        fs::create_dir_all(dir.join("target")).unwrap();
+        fs::write(dir.join("Cargo.toml"), "[package]").unwrap();
+        fs::write(dir.join("target/bin"), "x".repeat(1100)).unwrap();
+        projects.push(analyze_project(&dir, ProjectKind::Rust).unwrap());
+    }
+
+    let raw: u64 = projects.iter().map(|p| p.total_cleanable_bytes).sum();
+    assert_eq!(table_total(&projects), raw);
+    assert_eq!(raw, 3300);
+    // Each row shows "1.1 KB", but the footer shows the exact aggregate
+    assert_eq!(format_bytes(projects[0].total_cleanable_bytes), "1.1 KB");
+    assert_eq!(format_bytes(table_total(&projects)), "3.2 KB");
+
+    fs::remove_dir_all(&base).unwrap();
+}
+
+// ── write_results_table ─────────────────────────────────────────────────────
+
+#[test]
+fn results_table_shows_only_the_chosen_columns_in_order() {
+    let dir = std::env::temp_dir().join("dev_sweep_test_table_columns");
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join("target")).unwrap();
+    fs::write(dir.join("Cargo.toml"), "[package]").unwrap();
+    fs::write(dir.join("target/bin"), "x".repeat(2048)).unwrap();
+    let projects = [analyze_project(&dir, ProjectKind::Rust).unwrap()];
+    fs::remove_dir_all(&dir).unwrap();
+
+    let cells = |line: &str| -> Vec<String> {
+        line.trim().trim_matches('│').split('│').map(|c| c.trim().to_string()).collect()
+    };
+    let opts = DisplayOptions::default();
+    let mut out = Vec::new();
+    write_results_table(&mut out, &projects, &[Column::Project, Column::Cleanable], &opts).unwrap();
+    let table = String::from_utf8(out).unwrap();
+    let rows: Vec<&str> = table.lines().filter(|l| l.contains('│')).collect();
+
+    assert_eq!(cells(rows[0]), [bold("Project"), bold("Cleanable")]);
+    assert_eq!(cells(rows[1]), [projects[0].name.clone(), yellow("2.0 KB")]);
+    assert!(rows.iter().all(|row| row.matches('│').count() == 3));
+    assert_eq!(table.matches('┬').count(), 1);
+
+    // Every column by default, in the usual order
+    let mut out = Vec::new();
+    write_results_table(&mut out, &projects, &Column::ALL, &opts).unwrap();
+    let table = String::from_utf8(out).unwrap();
+    let header = table.lines().find(|l| l.contains('│')).unwrap();
+    let headers = ["#", "Project", "Type", "Cleanable", "Targets", "Last Modified", "Path"];
+    assert_eq!(cells(header), headers.map(bold));
+}
+
+#[test]
+fn results_table_lists_the_largest_targets_past_max_targets() {
+    let dir = std::env::temp_dir().join("dev_sweep_test_table_max_targets");
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("setup.py"), "").unwrap();
+    for i in 0..5 {
+        let egg = dir.join(format!("p{i}.egg-info"));
+        fs::create_dir_all(&egg).unwrap();
+        fs::write(egg.join("PKG-INFO"), "x".repeat(1024 * (i + 1))).unwrap();
+    }
+    let projects = [analyze_project(&dir, ProjectKind::Python).unwrap()];
+    fs::remove_dir_all(&dir).unwrap();
+
+    let table = |max_targets| {
+        let opts = DisplayOptions {
+            max_targets: Some(max_targets),
+            ..Default::default()
+        };
+        let mut out = Vec::new();
+        write_results_table(&mut out, &projects, &[Column::Targets], &opts).unwrap();
+        String::from_utf8(out).unwrap()
+    };
+
+    let one = table(1);
+    assert!(one.contains("p4.egg-info (5.0 KB), ..."), "{one}");
+    assert!(!one.contains("p3.egg-info"), "{one}");
+    let none = table(0);
+    assert!(none.contains("... and 5 more targets (15.0 KB total)"), "{none}");
+    assert_eq!(projects[0].clean_targets.len(), 5);
+}
+
+// ── render_markdown_table ───────────────────────────────────────────────────
+
+#[test]
+fn markdown_table_has_separator_and_one_row_per_project() {
+    let base = std::env::temp_dir().join("dev_sweep_test_markdown_table");
+    let _ = fs::remove_dir_all(&base);
+
+    let mut projects = Vec::new();
+    for name in ["alpha", "beta"] {
+        let dir = base.join(name);
+        fs::create_dir_all(dir.join("target")).unwrap();
+        fs::write(dir.join("Cargo.toml"), "[package]").unwrap();
+        fs::write(dir.join("target/bin"), "x".repeat(2048)).unwrap();
+        projects.push(analyze_project(&dir, ProjectKind::Rust).unwrap());
+    }
+
+    let md = render_markdown_table(&projects, &DisplayOptions::default());
+    let lines: Vec<&str> = md.lines().collect();
+
+    assert_eq!(lines[0], "| Project | Type | Cleanable | Path |");
+    assert_eq!(lines[1], "| --- | --- | ---: | --- |");
+    assert_eq!(lines.len(), 2 + projects.len());
+    assert!(lines[2].starts_with("| alpha | Rust | 2.0 KB |"));
+    assert!(lines[3].starts_with("| beta | Rust | 2.0 KB |"));
+    assert!(!md.contains('\x1b'));
+
+    fs::remove_dir_all(&base).unwrap();
+}
+
+#[test]
+fn markdown_table_escapes_pipes_in_custom_kind_names() {
+    let dir = std::env::temp_dir().join("dev_sweep_test_markdown_kind");
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join("target")).unwrap();
+    fs::write(dir.join("Cargo.toml"), "[package]").unwrap();
+    fs::write(dir.join("target/bin"), "x".repeat(2048)).unwrap();
+    let mut project = analyze_project(&dir, ProjectKind::Rust).unwrap();
+    project.custom_kind = Some("C|C++".to_string());
+    fs::remove_dir_all(&dir).unwrap();
+
+    let md = render_markdown_table(&[project], &DisplayOptions::default());
+    let row = md.lines().nth(2).unwrap();
+    assert!(row.contains("| C\\|C++ | 2.0 KB |"), "{row}");
+}
+
+// ── render_du_lines ─────────────────────────────────────────────────────────
+
+#[test]
+fn du_lines_one_per_target_across_projects() {
+    let base = std::env::temp_dir().join("dev_sweep_test_du_lines");
+    let _ = fs::remove_dir_all(&base);
+
+    let rust = base.join("rs");
+    fs::create_dir_all(rust.join("target")).unwrap();
+    fs::write(rust.join("Cargo.toml"), "[package]").unwrap();
+    fs::write(rust.join("target/bin"), "x".repeat(2048)).unwrap();
+
+    let node = base.join("web");
+    for dir in ["node_modules", "dist"] {
+        fs::create_dir_all(node.join(dir)).unwrap();
+        fs::write(node.join(dir).join("index.js"), "code").unwrap();
+    }
+    fs::write(node.join("package.json"), "{}").unwrap();
+
+    let projects = vec![
+        analyze_project(&rust, ProjectKind::Rust).unwrap(),
+        analyze_project(&node, ProjectKind::Node).unwrap(),
+    ];
+    let total_targets: usize = projects.iter().map(|p| p.clean_targets.len()).sum();
+    assert_eq!(total_targets, 3);
+
+    let out = render_du_lines(&projects);
+    let lines: Vec<&str> = out.lines().collect();
+    assert_eq!(lines.len(), total_targets);
+    assert_eq!(lines[0], format!("2.0K\t{}", rust.join("target").display()));
+    assert!(lines.iter().all(|l| l.split('\t').count() == 2));
+    assert!(!out.contains('\x1b'));
+
+    fs::remove_dir_all(&base).unwrap();
+}
+
+// ── render_print0 ───────────────────────────────────────────────────────────
+
+#[test]
+fn print0_separates_paths_with_nul_only() {
+    let base = std::env::temp_dir().join("dev_sweep_test_print0");
+    let _ = fs::remove_dir_all(&base);
+
+    // Spaces and a newline in a name would break newline-separated output
+    let node = base.join("my web\napp");
+    for dir in ["node_modules", "dist"] {
+        fs::create_dir_all(node.join(dir)).unwrap();
+        fs::write(node.join(dir).join("index.js"), "code").unwrap();
+    }
+    fs::write(node.join("package.json"), "{}").unwrap();
+    let projects = vec![analyze_project(&node, ProjectKind::Node).unwrap()];
+
+    let out = render_print0(&projects);
+    assert_eq!(out.last(), Some(&0));
+    let paths: Vec<&[u8]> = out[..out.len() - 1].split(|&b| b == 0).collect();
+    let expected: Vec<String> = projects[0]
+        .clean_targets
+        .iter()
+        .map(|t| t.path.display().to_string())
+        .collect();
+    assert_eq!(paths.len(), 2);
+    assert_eq!(paths, expected.iter().map(|p| p.as_bytes()).collect::<Vec<_>>());
+    // No newline after the last path, and nothing besides the paths
+    assert!(!out.ends_with(b"\n"));
+    assert!(!out.contains(&b'\t'));
+
+    fs::remove_dir_all(&base).unwrap();
+}
+
+// ── render_treemap ──────────────────────────────────────────────────────────
+
+#[test]
+fn treemap_groups_every_kind_with_aggregate_bytes() {
+    let base = std::env::temp_dir().join("dev_sweep_test_treemap");
+    let _ = fs::remove_dir_all(&base);
+
+    let mut projects = Vec::new();
+    for (name, size) in [("rs1", 100), ("rs2", 50)] {
+        let dir = base.join(name);
+        fs::create_dir_all(dir.join("target")).unwrap();
+        fs::write(dir.join("Cargo.toml"), "[package]").unwrap();
+        fs::write(dir.join("target/bin"), "x".repeat(size)).unwrap();
+        projects.push(analyze_project(&dir, ProjectKind::Rust).unwrap());
+    }
+    let web = base.join("web");
+    for (dir, size) in [("node_modules", 30), ("dist", 7)] {
+        fs::create_dir_all(web.join(dir)).unwrap();
+        fs::write(web.join(dir).join("index.js"), "x".repeat(size)).unwrap();
+    }
+    fs::write(web.join("package.json"), "{}").unwrap();
+    projects.push(analyze_project(&web, ProjectKind::Node).unwrap());
+
+    let tree = render_treemap(&projects);
+    assert_eq!(tree["bytes"], 187);
+
+    let kinds = tree["children"].as_array().unwrap();
+    let kind_bytes: Vec<(&str, u64)> = kinds
+        .iter()
+        .map(|k| (k["name"].as_str().unwrap(), k["bytes"].as_u64().unwrap()))
+        .collect();
+    assert_eq!(kind_bytes, vec![("Rust", 150), ("Node.js", 37)]);
+
+    // Leaves carry `value`, and sum to each kind's total
+    for kind in kinds {
+        let leaves: u64 = kind["children"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .flat_map(|p| p["children"].as_array().unwrap())
+            .map(|t| t["value"].as_u64().unwrap())
+            .sum();
+        assert_eq!(leaves, kind["bytes"].as_u64().unwrap());
+        assert!(kind.get("value").is_none());
+    }
+
+    fs::remove_dir_all(&base).unwrap();
+}
+
+// ── render_json ─────────────────────────────────────────────────────────────
+
+#[test]
+fn compact_json_is_one_line_with_the_same_content() {
+    let dir = std::env::temp_dir().join("dev_sweep_test_render_json");
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join("node_modules")).unwrap();
+    fs::write(dir.join("package.json"), "{}").unwrap();
+    fs::write(dir.join("node_modules/index.js"), "x".repeat(64)).unwrap();
+    let projects = vec![analyze_project(&dir, ProjectKind::Node).unwrap()];
+
+    let pretty = render_json(&projects, false).unwrap();
+    let compact = render_json(&projects, true).unwrap();
+    assert!(pretty.contains("\n  "));
+    assert!(!compact.contains('\n'));
+    assert!(!compact.contains("  "));
+    assert!(compact.len() < pretty.len());
+
+    let pretty: serde_json::Value = serde_json::from_str(&pretty).unwrap();
+    let compact: serde_json::Value = serde_json::from_str(&compact).unwrap();
+    assert_eq!(pretty, compact);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+// ── render_clean_summary ────────────────────────────────────────────────────
+
+#[test]
+fn dry_run_summary_lists_targets_that_could_not_be_deleted() {
+    let result = CleanResult {
+        project_name: "app".to_string(),
+        targets_cleaned: 1,
+        bytes_freed: 2048,
+        errors: vec![CleanError {
+            project: "app".to_string(),
+            path: PathBuf::from("/work/app/node_modules"),
+            message: "Cannot delete /work/app/node_modules: parent is not writable".to_string(),
+        }],
+        cleaned: Vec::new(),
+        protected: Vec::new(),
+    };
+
+    let summary = render_clean_summary(&[result], true, Duration::ZERO, &DisplayOptions::default());
+    assert!(summary.contains("Dry run complete"));
+    assert!(summary.contains("1 errors occurred"));
+    assert!(summary.contains("/work/app/node_modules: Cannot delete"));
+    assert!(summary.contains("parent is not writable"));
+}
+
+// ── clean progress ──────────────────────────────────────────────────────────
+
+#[test]
+fn progress_fraction_tracks_freed_against_total() {
+    assert_eq!(progress_fraction(0, 200), 0.0);
+    assert_eq!(progress_fraction(50, 200), 0.25);
+    assert_eq!(progress_fraction(200, 200), 1.0);
+}
+
+#[test]
+fn progress_fraction_handles_empty_and_overshooting_plans() {
+    assert_eq!(progress_fraction(0, 0), 1.0);
+    // A target that grew since the scan frees more than planned
+    assert_eq!(progress_fraction(300, 200), 1.0);
+}
+
+#[test]
+fn progress_bar_fills_proportionally() {
+    let bar = render_progress_bar(512, 1024, 10, &DisplayOptions::default());
+    assert!(bar.starts_with("[█████░░░░░]"));
+    assert!(bar.contains(" 50%"));
+    assert!(bar.ends_with("512 B / 1.0 KB"));
+
+    let done = render_progress_bar(2048, 1024, 10, &DisplayOptions::default());
+    assert!(done.starts_with("[██████████]"));
+    assert!(done.contains("100%"));
+}
+
+// ── render_kinds_json ───────────────────────────────────────────────────────
+
+#[test]
+fn kinds_listing_covers_every_kind_with_markers_and_targets() {
+    let listing = render_kinds_json(ProjectKind::all());
+    let entries = listing.as_array().unwrap();
+    assert_eq!(entries.len(), ProjectKind::all().len());
+
+    for (entry, kind) in entries.iter().zip(ProjectKind::all()) {
+        assert_eq!(entry["name"], kind.to_string());
+        let markers: Vec<&str> = entry["marker_files"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|m| m.as_str().unwrap())
+            .collect();
+        assert_eq!(markers, kind.marker_files());
+        let dirs: Vec<&str> = entry["cleanable_dirs"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|d| d["pattern"].as_str().unwrap())
+            .collect();
+        assert_eq!(dirs, kind.cleanable_dirs());
+    }
+
+    let node = &entries[2];
+    assert_eq!(node["kind"], "Node");
+    assert_eq!(node["cleanable_dirs"][0]["pattern"], "node_modules");
+    assert_eq!(node["cleanable_dirs"][0]["category"], "dependency_cache");
+}
+
+#[test]
+fn kinds_listing_round_trips_as_typed_rules() {
+    let listing = render_kinds_json(ProjectKind::all());
+    let rules: Vec<KindRules> = serde_json::from_value(listing).unwrap();
+    assert!(rules.len() >= 17);
+    assert_eq!(rules.len(), ProjectKind::all().len());
+
+    for rule in &rules {
+        assert_eq!(*rule, rule.kind.rules());
+        assert!(!rule.marker_files.is_empty(), "{} has no markers", rule.name);
+        let cleans_something = !(rule.cleanable_dirs.is_empty()
+            && rule.recursive_dirs.is_empty()
+            && rule.cleanable_files.is_empty());
+        // Go's module caches are shared, so a Go project has nothing of its own
+        assert_eq!(cleans_something, rule.kind != ProjectKind::Go, "{}", rule.name);
+    }
+}
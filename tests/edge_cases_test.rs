@@ -8,7 +8,7 @@ use std::path::PathBuf;
 use dev_sweep::config::DevSweepConfig;
 use dev_sweep::scanner::ProjectKind;
 use dev_sweep::scanner::walk::{
-    analyze_project, detect_project_kind, dir_size, scan_directory,
+    analyze_project, detect_project_kind, device_id, dir_size, on_device, scan_directory,
 };
 use dev_sweep::cleaner::{clean_project, clean_projects};
 use dev_sweep::util::{format_bytes, parse_age};
@@ -229,6 +229,58 @@ fn nested_project_inside_another_project() {
     fs::remove_dir_all(&root).unwrap();
 }
 
+#[test]
+fn ignore_vcs_roots_collapses_monorepo_packages() {
+    let root = test_dir("edge_vcs_roots");
+
+    let repo = root.join("monorepo");
+    fs::create_dir_all(repo.join(".git")).unwrap();
+    fs::write(repo.join("package.json"), "{}").unwrap();
+    fs::create_dir_all(repo.join("node_modules")).unwrap();
+    fs::write(repo.join("node_modules/root.js"), "root").unwrap(); // 4
+
+    for (pkg, data) in [("a", "aaaaa"), ("b", "bbbbbbb")] {
+        let dir = repo.join("packages").join(pkg);
+        fs::create_dir_all(dir.join("node_modules")).unwrap();
+        fs::write(dir.join("package.json"), "{}").unwrap();
+        fs::write(dir.join("node_modules/index.js"), data).unwrap(); // 5, 7
+    }
+
+    // A project outside any repo is left alone
+    let loose = root.join("loose");
+    fs::create_dir_all(loose.join("target")).unwrap();
+    fs::write(loose.join("Cargo.toml"), "[package]").unwrap();
+    fs::write(loose.join("target/bin"), "data").unwrap();
+
+    let config = DevSweepConfig::default();
+    assert_eq!(scan_directory(&root, None, &config).unwrap().len(), 4);
+
+    let config = DevSweepConfig {
+        ignore_vcs_roots: true,
+        ..Default::default()
+    };
+    let projects = scan_directory(&root, None, &config).unwrap();
+    assert_eq!(projects.len(), 2);
+
+    let mono = projects.iter().find(|p| p.path == repo).unwrap();
+    assert_eq!(mono.name, "monorepo");
+    assert_eq!(mono.kind, ProjectKind::Node);
+    assert_eq!(mono.clean_targets.len(), 3);
+    assert_eq!(mono.total_cleanable_bytes, 16);
+
+    let mut names: Vec<&str> = mono.clean_targets.iter().map(|t| t.name.as_str()).collect();
+    names.sort();
+    assert_eq!(
+        names,
+        vec![
+            "node_modules",
+            "packages/a/node_modules",
+            "packages/b/node_modules"
+        ]
+    );
+    fs::remove_dir_all(&root).unwrap();
+}
+
 #[test]
 fn project_with_multiple_marker_files_detected_once() {
     // A Java project with both pom.xml and build.gradle — should only be
@@ -281,20 +333,38 @@ fn deeply_nested_project_found() {
 }
 
 #[test]
-fn max_depth_zero_finds_nothing() {
-    // max_depth 0 means only the root itself — typically won't be a project
+fn max_depth_zero_checks_only_the_root() {
+    // max_depth 0 yields only the root entry itself, so a project at the
+    // root is found and nothing below it is visited.
     let root = test_dir("edge_depth_zero");
     fs::write(root.join("Cargo.toml"), "[package]").unwrap();
     fs::create_dir_all(root.join("target")).unwrap();
     fs::write(root.join("target/bin"), "data").unwrap();
 
-    // depth 0 = only the root entry itself, no children
-    // The root IS a project, so it should be found at depth 0
+    let child = root.join("child");
+    fs::create_dir_all(child.join("node_modules")).unwrap();
+    fs::write(child.join("package.json"), "{}").unwrap();
+    fs::write(child.join("node_modules/m.js"), "x").unwrap();
+
     let config = DevSweepConfig::default();
     let projects = scan_directory(&root, Some(0), &config).unwrap();
-    // WalkDir max_depth(0) yields only the root, so detect_project_kind
-    // will check the root dir itself
-    assert!(projects.len() <= 1);
+    assert_eq!(projects.len(), 1);
+    assert_eq!(projects[0].path, root);
+    assert_eq!(projects[0].kind, ProjectKind::Rust);
+    fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+fn max_depth_zero_finds_nothing_when_root_is_not_a_project() {
+    let root = test_dir("edge_depth_zero_noproj");
+    let child = root.join("app");
+    fs::create_dir_all(child.join("target")).unwrap();
+    fs::write(child.join("Cargo.toml"), "[package]").unwrap();
+    fs::write(child.join("target/bin"), "data").unwrap();
+
+    let config = DevSweepConfig::default();
+    let projects = scan_directory(&root, Some(0), &config).unwrap();
+    assert!(projects.is_empty());
     fs::remove_dir_all(&root).unwrap();
 }
 
@@ -323,6 +393,47 @@ fn max_depth_one_finds_only_immediate_children() {
     fs::remove_dir_all(&root).unwrap();
 }
 
+#[test]
+fn project_depth_counts_from_enclosing_project() {
+    let root = test_dir("edge_project_depth");
+
+    // Outer project buried deep under the scan root
+    let outer = root.join("a/b/c/outer");
+    fs::create_dir_all(outer.join("target")).unwrap();
+    fs::write(outer.join("Cargo.toml"), "[package]").unwrap();
+    fs::write(outer.join("target/bin"), "data").unwrap();
+
+    // Nested project one level below the outer project
+    let near = outer.join("web");
+    fs::create_dir_all(near.join("node_modules")).unwrap();
+    fs::write(near.join("package.json"), "{}").unwrap();
+    fs::write(near.join("node_modules/m.js"), "x").unwrap();
+
+    // Nested project three levels below the outer project
+    let far = outer.join("tools/gen/far");
+    fs::create_dir_all(far.join("node_modules")).unwrap();
+    fs::write(far.join("package.json"), "{}").unwrap();
+    fs::write(far.join("node_modules/m.js"), "x").unwrap();
+
+    let config = DevSweepConfig {
+        project_depth: Some(1),
+        ..Default::default()
+    };
+    let projects = scan_directory(&root, None, &config).unwrap();
+    let mut paths: Vec<PathBuf> = projects.iter().map(|p| p.path.clone()).collect();
+    paths.sort();
+    assert_eq!(paths, vec![outer.clone(), near]);
+
+    let config = DevSweepConfig {
+        project_depth: Some(0),
+        ..Default::default()
+    };
+    let projects = scan_directory(&root, None, &config).unwrap();
+    assert_eq!(projects.len(), 1);
+    assert_eq!(projects[0].path, outer);
+    fs::remove_dir_all(&root).unwrap();
+}
+
 // ══════════════════════════════════════════════════════════════════════════════
 // Python __pycache__ edge cases
 // ══════════════════════════════════════════════════════════════════════════════
@@ -343,7 +454,8 @@ fn pycache_at_project_root_not_double_counted() {
         .filter(|t| t.name.contains("__pycache__"))
         .collect();
     // Should be found (either via cleanable_dirs or find_pycache_recursive) but not duplicated
-    assert!(!pycache_targets.is_empty());
+    assert_eq!(pycache_targets.len(), 1);
+    assert_eq!(project.total_cleanable_bytes, 8);
     fs::remove_dir_all(&dir).unwrap();
 }
 
@@ -520,6 +632,18 @@ fn glob_cleanable_dir_multiple_egg_info() {
 // Nested-path marker (Unity)
 // ══════════════════════════════════════════════════════════════════════════════
 
+#[test]
+fn file_named_like_cleanable_dir_is_not_a_target() {
+    // A `build` script must never be mistaken for a `build/` output dir
+    let dir = test_dir("edge_build_script");
+    fs::write(dir.join("CMakeLists.txt"), "project(x)").unwrap();
+    fs::write(dir.join("build"), "#!/bin/sh\ncmake -B out").unwrap();
+
+    let project = analyze_project(&dir, ProjectKind::CMake).unwrap();
+    assert!(project.clean_targets.is_empty());
+    fs::remove_dir_all(&dir).unwrap();
+}
+
 #[test]
 fn unity_incomplete_marker_not_detected() {
     // ProjectSettings/ exists but ProjectVersion.txt is missing
@@ -690,11 +814,52 @@ fn scala_project_target_and_nested_target() {
     fs::write(dir.join("target/scala-2.13/app.jar"), "jar").unwrap();
     fs::create_dir_all(dir.join("project/target")).unwrap();
     fs::write(dir.join("project/target/resolution.json"), "{}").unwrap();
+    for tool_dir in [".bloop", ".bsp", ".metals"] {
+        fs::create_dir_all(dir.join(tool_dir)).unwrap();
+        fs::write(dir.join(tool_dir).join("state.json"), "{}").unwrap();
+    }
 
     let project = analyze_project(&dir, ProjectKind::Scala).unwrap();
     let names: Vec<&str> = project.clean_targets.iter().map(|t| t.name.as_str()).collect();
     assert!(names.contains(&"target"));
     assert!(names.contains(&"project/target"));
-    assert_eq!(project.clean_targets.len(), 2);
+    assert!(names.contains(&".bloop"));
+    assert!(names.contains(&".bsp"));
+    assert!(names.contains(&".metals"));
+    assert_eq!(project.clean_targets.len(), 5);
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// Filesystem boundaries (--one-file-system)
+// ══════════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn subtree_on_a_different_device_is_skipped() {
+    let dir = test_dir("edge_one_fs_device");
+    let dev = device_id(&dir).expect("unix exposes device ids");
+
+    assert!(on_device(&dir, Some(dev)));
+    // Pretend the scan root lives on another device
+    assert!(!on_device(&dir, Some(dev.wrapping_add(1))));
+    // Without a root device the check is a no-op
+    assert!(on_device(&dir, None));
     fs::remove_dir_all(&dir).unwrap();
 }
+
+#[test]
+fn one_file_system_still_finds_projects_on_the_same_device() {
+    let root = test_dir("edge_one_fs_scan");
+    let proj = root.join("app");
+    fs::create_dir_all(proj.join("target")).unwrap();
+    fs::write(proj.join("Cargo.toml"), "[package]").unwrap();
+    fs::write(proj.join("target/bin"), "data").unwrap();
+
+    let config = DevSweepConfig {
+        one_file_system: true,
+        ..Default::default()
+    };
+    let projects = scan_directory(&root, None, &config).unwrap();
+    assert_eq!(projects.len(), 1);
+    fs::remove_dir_all(&root).unwrap();
+}
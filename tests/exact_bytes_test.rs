@@ -0,0 +1,51 @@
+//! Tests for `--exact-bytes` and `--precision`, which reach every renderer
+//! through [`DisplayOptions`].
+
+use std::fs;
+
+use dev_sweep::scanner::ProjectKind;
+use dev_sweep::scanner::walk::analyze_project;
+use dev_sweep::tui::display::{DisplayOptions, render_markdown_table};
+use dev_sweep::util::{SizeFormat, format_bytes};
+
+#[test]
+fn exact_bytes_switches_every_size_to_raw_counts() {
+    let exact = SizeFormat {
+        exact: true,
+        ..Default::default()
+    };
+    assert_eq!(exact.bytes(1_536_000), "1,536,000 B");
+    assert_eq!(exact.approx(1_536_000, true), "~1,536,000 B");
+    // The default format is untouched by another one being in use
+    assert_eq!(format_bytes(1_536_000), "1.5 MB");
+    assert_eq!(SizeFormat::default().approx(1_536_000, true), "~1.5 MB");
+}
+
+#[test]
+fn renderers_follow_the_options_they_are_given() {
+    let dir = std::env::temp_dir().join("dev_sweep_test_exact_bytes_table");
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join("target")).unwrap();
+    fs::write(dir.join("Cargo.toml"), "[package]").unwrap();
+    fs::write(dir.join("target/bin"), "x".repeat(1536)).unwrap();
+    let projects = vec![analyze_project(&dir, ProjectKind::Rust).unwrap()];
+    fs::remove_dir_all(&dir).unwrap();
+
+    let exact = DisplayOptions {
+        sizes: SizeFormat {
+            exact: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let two_places = DisplayOptions {
+        sizes: SizeFormat {
+            precision: 2,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    assert!(render_markdown_table(&projects, &exact).contains("| 1,536 B |"));
+    assert!(render_markdown_table(&projects, &two_places).contains("| 1.50 KB |"));
+    assert!(render_markdown_table(&projects, &DisplayOptions::default()).contains("| 1.5 KB |"));
+}
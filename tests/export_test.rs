@@ -0,0 +1,58 @@
+//! Tests for writing scan results out as one JSON file per project.
+
+use std::fs;
+use std::path::PathBuf;
+
+use dev_sweep::config::DevSweepConfig;
+use dev_sweep::export::{split_output_file_name, write_split_output};
+use dev_sweep::scanner::ScannedProject;
+use dev_sweep::scanner::walk::scan_directory;
+use dev_sweep::tui::display::DisplayOptions;
+
+/// Helper: create a fresh temp dir for a test.
+fn test_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("dev_sweep_test_{name}"));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[test]
+fn split_output_writes_one_parseable_file_per_project() {
+    let dir = test_dir("split_output");
+    let root = dir.join("projects");
+    // Same name in two places: the path hash keeps the files apart
+    for name in ["a/app", "b/app"] {
+        let project = root.join(name);
+        fs::create_dir_all(project.join("node_modules/react")).unwrap();
+        fs::write(project.join("package.json"), "{}").unwrap();
+        fs::write(project.join("node_modules/react/index.js"), "code").unwrap();
+    }
+
+    let config = DevSweepConfig {
+        no_spinner: true,
+        ..Default::default()
+    };
+    let projects = scan_directory(&root, None, &config).unwrap();
+    assert_eq!(projects.len(), 2);
+    let out = dir.join("out");
+    assert_eq!(write_split_output(&out, &projects, true, &DisplayOptions::default()).unwrap(), 2);
+
+    let mut written: Vec<PathBuf> = fs::read_dir(&out)
+        .unwrap()
+        .map(|e| e.unwrap().path())
+        .collect();
+    written.sort();
+    assert_eq!(written.len(), 2);
+    for path in &written {
+        let name = path.file_name().unwrap().to_string_lossy();
+        assert!(name.starts_with("app-") && name.ends_with(".json"), "{name}");
+        let parsed: ScannedProject =
+            serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap();
+        assert_eq!(parsed.name, "app");
+        assert_eq!(name, split_output_file_name(&parsed));
+        assert_eq!(parsed.total_cleanable_bytes, 4);
+    }
+
+    fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,53 @@
+//! Tests for `DEVCLEAN_EXTRA_TARGETS`. It is read from the process
+//! environment, so these live in their own test binary.
+
+use std::fs;
+use std::path::PathBuf;
+
+use dev_sweep::scanner::walk::{EXTRA_TARGETS_VAR, analyze_project, parse_extra_targets};
+use dev_sweep::scanner::{ProjectKind, TargetCategory};
+
+/// Helper: create a fresh temp dir for a test.
+fn test_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("dev_sweep_test_{name}"));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[test]
+fn extra_targets_from_env_are_cleaned_in_every_project() {
+    let dir = test_dir("extra_targets_env");
+    fs::create_dir_all(dir.join("target")).unwrap();
+    fs::create_dir_all(dir.join("tmp")).unwrap();
+    fs::create_dir_all(dir.join("scratch")).unwrap();
+    fs::write(dir.join("Cargo.toml"), "[package]").unwrap();
+    fs::write(dir.join("target/bin"), "x".repeat(16)).unwrap();
+    fs::write(dir.join("tmp/dump.bin"), "x".repeat(32)).unwrap();
+
+    let before = analyze_project(&dir, ProjectKind::Rust).unwrap();
+    assert!(before.clean_targets.iter().all(|t| t.name != "tmp"));
+
+    // SAFETY: this binary's only test that touches the environment.
+    unsafe { std::env::set_var(EXTRA_TARGETS_VAR, " tmp, scratch ,target,../up") };
+    let project = analyze_project(&dir, ProjectKind::Rust);
+    unsafe { std::env::remove_var(EXTRA_TARGETS_VAR) };
+    let project = project.unwrap();
+    fs::remove_dir_all(&dir).unwrap();
+
+    let tmp = project.clean_targets.iter().find(|t| t.name == "tmp").unwrap();
+    assert_eq!(tmp.size_bytes, 32);
+    assert_eq!(tmp.category, TargetCategory::Unclassified);
+    // Empty dirs aren't targets, and target/ is listed once
+    assert!(project.clean_targets.iter().all(|t| t.name != "scratch"));
+    assert_eq!(project.clean_targets.iter().filter(|t| t.name == "target").count(), 1);
+    assert_eq!(project.total_cleanable_bytes, 48);
+}
+
+#[test]
+fn extra_target_names_must_be_plain_directory_names() {
+    assert_eq!(parse_extra_targets(".cache,tmp"), [".cache", "tmp"]);
+    assert_eq!(parse_extra_targets(" a , ,b,"), ["a", "b"]);
+    assert!(parse_extra_targets("../x,/etc,a/b,..,.,").is_empty());
+    assert!(parse_extra_targets("").is_empty());
+}
@@ -0,0 +1,45 @@
+//! Tests for the wildcard matcher used by cleanable patterns.
+
+use dev_sweep::util::glob_match;
+
+#[test]
+fn glob_literal() {
+    assert!(glob_match("target", "target"));
+    assert!(!glob_match("target", "targets"));
+    assert!(!glob_match("target", "Target"));
+}
+
+#[test]
+fn glob_suffix() {
+    assert!(glob_match("*.egg-info", "mypkg.egg-info"));
+    assert!(glob_match("*.egg-info", ".egg-info"));
+    assert!(!glob_match("*.egg-info", "mypkg.egg-info.bak"));
+}
+
+#[test]
+fn glob_prefix() {
+    assert!(glob_match("cmake-build-*", "cmake-build-debug"));
+    assert!(glob_match("cmake-build-*", "cmake-build-"));
+    assert!(!glob_match("cmake-build-*", "build"));
+}
+
+#[test]
+fn glob_infix_and_multiple_stars() {
+    assert!(glob_match("build-*-Desktop*", "build-MyApp-Desktop_Qt_6_5"));
+    assert!(glob_match("*a*b*", "xxaxxbxx"));
+    assert!(!glob_match("*a*b*", "xxbxxaxx"));
+}
+
+#[test]
+fn glob_question_mark() {
+    assert!(glob_match("v?.log", "v1.log"));
+    assert!(!glob_match("v?.log", "v10.log"));
+}
+
+#[test]
+fn glob_star_only_and_empty() {
+    assert!(glob_match("*", ""));
+    assert!(glob_match("*", "anything"));
+    assert!(glob_match("", ""));
+    assert!(!glob_match("", "x"));
+}
@@ -0,0 +1,57 @@
+//! Tests for `--progress-json`, run through the binary to capture stderr.
+
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+
+use serde_json::Value;
+
+/// Helper: create a fresh temp dir for a test.
+fn test_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("dev_sweep_test_{name}"));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[test]
+fn progress_json_emits_walk_analyze_and_done_events() {
+    let base = test_dir("progress_json");
+    let projects = base.join("projects");
+    for name in ["a", "b"] {
+        let root = projects.join(name);
+        fs::create_dir_all(root.join("target")).unwrap();
+        fs::write(root.join("Cargo.toml"), "[package]").unwrap();
+        fs::write(root.join("target/bin"), "x".repeat(64)).unwrap();
+    }
+
+    let output = Command::new(env!("CARGO_BIN_EXE_dev-sweep"))
+        .args(["--progress-json", "--json"])
+        .arg(&projects)
+        .env("HOME", &base)
+        .env("XDG_CONFIG_HOME", base.join("config"))
+        .output()
+        .unwrap();
+    fs::remove_dir_all(&base).unwrap();
+    assert!(output.status.success());
+
+    // Every stderr line is an event; stdout still holds just the scan
+    let events: Vec<Value> = String::from_utf8_lossy(&output.stderr)
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    let phases: Vec<&str> = events.iter().map(|e| e["phase"].as_str().unwrap()).collect();
+    assert!(phases.contains(&"walk"), "{phases:?}");
+    assert_eq!(phases.last(), Some(&"done"));
+
+    let analyze: Vec<&Value> = events.iter().filter(|e| e["phase"] == "analyze").collect();
+    assert_eq!(analyze.len(), 2);
+    assert!(analyze.iter().all(|e| e["total"] == 2));
+    assert_eq!(analyze.last().unwrap()["done"], 2);
+    let walk = events.iter().find(|e| e["phase"] == "walk").unwrap();
+    // projects/, a/ and b/; target/ is never descended into
+    assert_eq!(walk["dirs"], 3);
+
+    let scan: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(scan.as_array().map(Vec::len), Some(2));
+}
@@ -0,0 +1,42 @@
+//! Tests for parsing project kinds from strings.
+
+use dev_sweep::scanner::ProjectKind;
+
+#[test]
+fn every_kind_round_trips_through_display() {
+    for &kind in ProjectKind::all().iter().chain([&ProjectKind::Custom]) {
+        let parsed: ProjectKind = kind.to_string().parse().unwrap();
+        assert_eq!(parsed, kind, "{kind} did not round-trip");
+    }
+}
+
+#[test]
+fn parse_accepts_variant_names_and_any_case() {
+    assert_eq!(
+        "DotNet".parse::<ProjectKind>().unwrap(),
+        ProjectKind::DotNet
+    );
+    assert_eq!("RUST".parse::<ProjectKind>().unwrap(), ProjectKind::Rust);
+    assert_eq!(
+        " latex ".parse::<ProjectKind>().unwrap(),
+        ProjectKind::Latex
+    );
+}
+
+#[test]
+fn parse_accepts_common_aliases() {
+    for alias in ["node", "nodejs", "Node.js"] {
+        assert_eq!(alias.parse::<ProjectKind>().unwrap(), ProjectKind::Node);
+    }
+    for alias in ["dotnet", ".net", "c#", "C#"] {
+        assert_eq!(alias.parse::<ProjectKind>().unwrap(), ProjectKind::DotNet);
+    }
+    assert_eq!("golang".parse::<ProjectKind>().unwrap(), ProjectKind::Go);
+}
+
+#[test]
+fn parse_rejects_unknown_kind() {
+    let err = "cobol".parse::<ProjectKind>().unwrap_err();
+    assert!(err.to_string().contains("cobol"));
+    assert!(err.to_string().contains("Node.js"));
+}
@@ -0,0 +1,63 @@
+//! Tests for `--estimate-restore-cost`.
+
+use std::fs;
+
+use dev_sweep::scanner::walk::analyze_project;
+use dev_sweep::scanner::{ProjectKind, ScannedProject, TargetCategory};
+use dev_sweep::tui::display::{DisplayOptions, scan_json, target_label};
+
+#[test]
+fn categories_map_to_restore_cost_labels() {
+    let labels = [
+        (TargetCategory::BuildOutput, "local rebuild"),
+        (TargetCategory::DependencyCache, "network restore"),
+        (TargetCategory::ToolCache, "regenerated automatically"),
+        (TargetCategory::CoverageArtifacts, "rerun tests"),
+        (TargetCategory::CompilerCache, "slower next build"),
+        (TargetCategory::Unclassified, "unknown"),
+    ];
+    for (category, label) in labels {
+        assert_eq!(category.restore_cost(), label, "{category}");
+    }
+}
+
+#[test]
+fn restore_cost_shows_in_table_and_json_only_when_asked() {
+    let dir = std::env::temp_dir().join("dev_sweep_test_restore_cost");
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join("node_modules/react")).unwrap();
+    fs::create_dir_all(dir.join("dist")).unwrap();
+    fs::write(dir.join("package.json"), "{}").unwrap();
+    fs::write(dir.join("node_modules/react/index.js"), "code").unwrap();
+    fs::write(dir.join("dist/app.js"), "bundle").unwrap();
+    let projects = vec![analyze_project(&dir, ProjectKind::Node).unwrap()];
+    fs::remove_dir_all(&dir).unwrap();
+
+    let plain = DisplayOptions::default();
+    let json = scan_json(&projects, &plain).unwrap();
+    assert!(json[0]["clean_targets"][0].get("restore_cost").is_none());
+    let labels = |projects: &[ScannedProject], opts: &DisplayOptions| {
+        let mut labels: Vec<String> =
+            projects[0].clean_targets.iter().map(|t| target_label(t, opts)).collect();
+        labels.sort();
+        labels
+    };
+    assert_eq!(labels(&projects, &plain), ["dist (6 B)", "node_modules (4 B)"]);
+
+    let costs = DisplayOptions {
+        restore_cost: true,
+        ..Default::default()
+    };
+    let json = scan_json(&projects, &costs).unwrap();
+    let table = labels(&projects, &costs);
+
+    for target in json[0]["clean_targets"].as_array().unwrap() {
+        let expected = match target["name"].as_str().unwrap() {
+            "node_modules" => "network restore",
+            "dist" => "local rebuild",
+            other => panic!("unexpected target {other}"),
+        };
+        assert_eq!(target["restore_cost"], expected);
+    }
+    assert_eq!(table, ["dist (6 B, local rebuild)", "node_modules (4 B, network restore)"]);
+}
@@ -1,13 +1,21 @@
 //! Tests for project analysis, directory sizing, pycache discovery, and full scanning.
 
+use std::cell::Cell;
 use std::fs;
 use std::path::PathBuf;
+use std::sync::Mutex;
 
-use dev_sweep::config::DevSweepConfig;
-use dev_sweep::scanner::ProjectKind;
+use dev_sweep::cleaner::{
+    CleanOptions, clean_project, clean_project_with, drop_ambiguous_builds, mark_ambiguous_builds,
+};
+use dev_sweep::config::{CustomKind, DevSweepConfig};
+use dev_sweep::scanner::{ProjectKind, TargetCategory, TargetKind};
 use dev_sweep::scanner::walk::{
-    analyze_project, dir_size, find_pycache_recursive, scan_directory, should_visit,
+    BoundedSize, analyze_path, analyze_paths, analyze_project, analyze_project_with, dir_size,
+    dir_size_at_least, estimate_dir_size, find_pycache_recursive, scan_directory,
+    scan_directory_each, scan_directory_to, should_visit, should_visit_with, sum_sizes_until,
 };
+use dev_sweep::util::read_path_list;
 
 /// Helper: create a fresh temp dir for a test.
 fn test_dir(name: &str) -> PathBuf {
@@ -46,6 +54,81 @@ fn dir_size_nested() {
     fs::remove_dir_all(&dir).unwrap();
 }
 
+// ── estimate_dir_size ───────────────────────────────────────────────────────
+
+#[test]
+fn estimate_dir_size_is_exact_for_few_subdirs() {
+    let dir = test_dir("estimate_few");
+    fs::write(dir.join("top"), "x".repeat(10)).unwrap();
+    fs::create_dir_all(dir.join("a/b")).unwrap();
+    fs::write(dir.join("a/b/c"), "x".repeat(32)).unwrap();
+    assert_eq!(estimate_dir_size(&dir).unwrap(), dir_size(&dir).unwrap());
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn estimate_dir_size_within_factor_of_exact() {
+    let dir = test_dir("estimate_many");
+    fs::write(dir.join(".package-lock.json"), "x".repeat(500)).unwrap();
+    for i in 0..60 {
+        let pkg = dir.join(format!("pkg{i:02}/lib"));
+        fs::create_dir_all(&pkg).unwrap();
+        fs::write(pkg.join("index.js"), "x".repeat(100 + (i * 37) % 400)).unwrap();
+    }
+
+    let exact = dir_size(&dir).unwrap();
+    let estimate = estimate_dir_size(&dir).unwrap();
+    assert!(
+        estimate * 2 >= exact && estimate <= exact * 2,
+        "estimate {estimate} too far from exact {exact}"
+    );
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn dir_size_at_least_stops_once_over_threshold() {
+    let dir = test_dir("size_at_least");
+    for i in 0..100 {
+        fs::write(dir.join(format!("f{i:03}")), "x".repeat(10)).unwrap();
+    }
+
+    assert_eq!(dir_size_at_least(&dir, 2000), BoundedSize::Exact(1000));
+    let bounded = dir_size_at_least(&dir, 50);
+    assert_eq!(bounded, BoundedSize::AtLeast(50));
+    assert_eq!(bounded.bytes(), 50);
+
+    // Count how many files the early exit actually looked at
+    let visited = Cell::new(0);
+    let sizes = fs::read_dir(&dir)
+        .unwrap()
+        .map(|e| e.unwrap().metadata().unwrap().len())
+        .inspect(|_| visited.set(visited.get() + 1));
+    assert_eq!(sum_sizes_until(sizes, 50), BoundedSize::AtLeast(50));
+    assert_eq!(visited.get(), 5);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn analyze_fast_marks_targets_estimated() {
+    let dir = test_dir("analyze_fast");
+    fs::write(dir.join("Cargo.toml"), "[package]").unwrap();
+    fs::create_dir_all(dir.join("target/debug")).unwrap();
+    fs::write(dir.join("target/debug/bin"), "data").unwrap();
+
+    let config = DevSweepConfig {
+        fast: true,
+        ..Default::default()
+    };
+    let project = analyze_project_with(&dir, ProjectKind::Rust, &config).unwrap();
+    assert!(project.is_estimate());
+    assert_eq!(project.total_cleanable_bytes, 4);
+
+    let exact = analyze_project(&dir, ProjectKind::Rust).unwrap();
+    assert!(!exact.is_estimate());
+    fs::remove_dir_all(&dir).unwrap();
+}
+
 // ── should_visit ────────────────────────────────────────────────────────────
 
 #[test]
@@ -104,6 +187,43 @@ fn should_visit_allows_src() {
     fs::remove_dir_all(&dir).unwrap();
 }
 
+#[test]
+fn should_visit_with_no_skip_dirs_only_skips_git() {
+    let dir = test_dir("visit_no_skip");
+    for name in [".git", ".cache", "node_modules", "target"] {
+        fs::create_dir_all(dir.join(name)).unwrap();
+    }
+
+    for entry in walkdir::WalkDir::new(&dir).min_depth(1).max_depth(1) {
+        let entry = entry.unwrap();
+        let visit = should_visit_with(&entry, true);
+        assert_eq!(visit, entry.file_name() != ".git", "{:?}", entry.file_name());
+    }
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn scan_with_no_skip_dirs_finds_project_inside_skipped_dir() {
+    let dir = test_dir("scan_no_skip_dirs");
+    let buried = dir.join("node_modules/vendored-tool");
+    fs::create_dir_all(buried.join("target/debug")).unwrap();
+    fs::write(buried.join("Cargo.toml"), "[package]").unwrap();
+    fs::write(buried.join("target/debug/bin"), "data").unwrap();
+
+    let default = DevSweepConfig::default();
+    let found = scan_directory_to(&dir, None, &default, &mut std::io::sink()).unwrap();
+    assert!(found.iter().all(|p| p.path != buried));
+
+    let config = DevSweepConfig {
+        no_skip_dirs: true,
+        ..Default::default()
+    };
+    let found = scan_directory_to(&dir, None, &config, &mut std::io::sink()).unwrap();
+    assert!(found.iter().any(|p| p.path == buried && p.kind == ProjectKind::Rust));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
 // ── analyze_project ─────────────────────────────────────────────────────────
 
 #[test]
@@ -135,6 +255,97 @@ fn analyze_node_with_node_modules() {
     fs::remove_dir_all(&dir).unwrap();
 }
 
+#[test]
+fn analyze_node_finds_coverage_artifacts() {
+    let dir = test_dir("analyze_node_coverage");
+    fs::write(dir.join("package.json"), "{}").unwrap();
+    fs::create_dir_all(dir.join("coverage/lcov-report")).unwrap();
+    fs::write(dir.join("coverage/lcov-report/index.html"), "<html>").unwrap();
+    fs::create_dir_all(dir.join(".nyc_output")).unwrap();
+    fs::write(dir.join(".nyc_output/run.json"), "{}").unwrap();
+    fs::write(dir.join("lcov.info"), "TN:").unwrap();
+
+    let project = analyze_project(&dir, ProjectKind::Node).unwrap();
+    let category = |name: &str| {
+        project
+            .clean_targets
+            .iter()
+            .find(|t| t.name == name)
+            .map(|t| (t.category, t.kind))
+    };
+    assert_eq!(
+        category("coverage"),
+        Some((TargetCategory::CoverageArtifacts, TargetKind::Dir))
+    );
+    assert_eq!(
+        category(".nyc_output"),
+        Some((TargetCategory::CoverageArtifacts, TargetKind::Dir))
+    );
+    assert_eq!(
+        category("lcov.info"),
+        Some((TargetCategory::CoverageArtifacts, TargetKind::File))
+    );
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn analyze_node_finds_frontend_tool_caches() {
+    let dir = test_dir("analyze_node_frontend_caches");
+    fs::write(dir.join("package.json"), "{}").unwrap();
+    fs::create_dir_all(dir.join(".turbo/cache")).unwrap();
+    fs::write(dir.join(".turbo/cache/abc.tar.zst"), "turbo").unwrap(); // 5
+    fs::create_dir_all(dir.join(".vite/deps")).unwrap();
+    fs::write(dir.join(".vite/deps/react.js"), "vite!!").unwrap(); // 6
+    fs::create_dir_all(dir.join(".parcel-cache")).unwrap(); // empty — skipped
+
+    let project = analyze_project(&dir, ProjectKind::Node).unwrap();
+    let found = |name: &str| project.clean_targets.iter().find(|t| t.name == name);
+    assert_eq!(found(".turbo").map(|t| t.category), Some(TargetCategory::ToolCache));
+    assert_eq!(found(".vite").map(|t| t.category), Some(TargetCategory::ToolCache));
+    assert!(found(".parcel-cache").is_none());
+    assert_eq!(project.total_cleanable_bytes, 11);
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn analyze_python_coverage_data_file_but_not_coveragerc() {
+    let dir = test_dir("analyze_python_coverage");
+    fs::write(dir.join("pyproject.toml"), "[project]").unwrap();
+    fs::write(dir.join(".coverage"), "sqlite").unwrap();
+    fs::write(dir.join(".coveragerc"), "[run]").unwrap();
+    fs::create_dir_all(dir.join("htmlcov")).unwrap();
+    fs::write(dir.join("htmlcov/index.html"), "<html>").unwrap();
+
+    let project = analyze_project(&dir, ProjectKind::Python).unwrap();
+    let mut names: Vec<&str> = project.clean_targets.iter().map(|t| t.name.as_str()).collect();
+    names.sort();
+    assert_eq!(names, vec![".coverage", "htmlcov"]);
+    assert!(
+        project
+            .clean_targets
+            .iter()
+            .all(|t| t.category == TargetCategory::CoverageArtifacts)
+    );
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn analyze_bun_modules_and_cache() {
+    let dir = test_dir("analyze_bun");
+    fs::write(dir.join("bun.lockb"), "").unwrap();
+    for sub in ["node_modules/hono", ".bun/install"] {
+        fs::create_dir_all(dir.join(sub)).unwrap();
+        fs::write(dir.join(sub).join("index.js"), "x").unwrap();
+    }
+
+    let project = analyze_project(&dir, ProjectKind::Bun).unwrap();
+    let mut names: Vec<&str> = project.clean_targets.iter().map(|t| t.name.as_str()).collect();
+    names.sort();
+    assert_eq!(names, vec![".bun", "node_modules"]);
+    assert!(project.needs_network_restore());
+    fs::remove_dir_all(&dir).unwrap();
+}
+
 #[test]
 fn analyze_node_with_multiple_targets() {
     let dir = test_dir("analyze_node_multi");
@@ -156,6 +367,33 @@ fn analyze_node_with_multiple_targets() {
     fs::remove_dir_all(&dir).unwrap();
 }
 
+#[test]
+fn analyze_target_last_modified_tracks_newest_file() {
+    let dir = test_dir("analyze_target_mtime");
+    fs::write(dir.join("package.json"), "{}").unwrap();
+    fs::create_dir_all(dir.join("node_modules/old")).unwrap();
+    fs::create_dir_all(dir.join("dist")).unwrap();
+    let stale = dir.join("node_modules/old/index.js");
+    fs::write(&stale, "old").unwrap();
+    let week_ago = std::time::SystemTime::now() - std::time::Duration::from_secs(7 * 86400);
+    fs::File::options()
+        .write(true)
+        .open(&stale)
+        .unwrap()
+        .set_modified(week_ago)
+        .unwrap();
+    fs::write(dir.join("dist/app.js"), "fresh").unwrap();
+
+    let project = analyze_project(&dir, ProjectKind::Node).unwrap();
+    let age = |name: &str| {
+        let target = project.clean_targets.iter().find(|t| t.name == name).unwrap();
+        chrono::Local::now() - target.last_modified
+    };
+    assert!(age("dist") < chrono::TimeDelta::try_minutes(1).unwrap());
+    assert!(age("node_modules") > chrono::TimeDelta::try_days(6).unwrap());
+    fs::remove_dir_all(&dir).unwrap();
+}
+
 #[test]
 fn analyze_project_no_artifacts() {
     let dir = test_dir("analyze_clean");
@@ -193,6 +431,582 @@ fn analyze_dotnet_bin_obj() {
     fs::remove_dir_all(&dir).unwrap();
 }
 
+#[test]
+fn analyze_latex_aux_files() {
+    let dir = test_dir("analyze_latex");
+    fs::write(dir.join("paper.tex"), "\\documentclass{article}").unwrap();
+    fs::write(dir.join("paper.aux"), "relax").unwrap(); // 5
+    fs::write(dir.join("paper.log"), "This is pdfTeX").unwrap(); // 14
+    fs::write(dir.join("paper.toc"), "").unwrap(); // empty — skipped
+    fs::write(dir.join("paper.pdf"), "%PDF").unwrap(); // output — kept
+
+    let project = analyze_project(&dir, ProjectKind::Latex).unwrap();
+    let mut names: Vec<&str> = project.clean_targets.iter().map(|t| t.name.as_str()).collect();
+    names.sort();
+    assert_eq!(names, vec!["paper.aux", "paper.log"]);
+    assert!(project.clean_targets.iter().all(|t| t.kind == TargetKind::File));
+    assert_eq!(project.total_cleanable_bytes, 19);
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn analyze_docker_build_cache() {
+    let dir = test_dir("analyze_docker");
+    fs::write(dir.join("Dockerfile"), "FROM alpine").unwrap();
+    fs::create_dir_all(dir.join(".docker/buildx")).unwrap();
+    fs::write(dir.join(".docker/buildx/layer"), "cached layer").unwrap(); // 12
+
+    let project = analyze_project(&dir, ProjectKind::Docker).unwrap();
+    assert_eq!(project.clean_targets.len(), 1);
+    assert_eq!(project.clean_targets[0].name, ".docker");
+    assert_eq!(project.clean_targets[0].category, TargetCategory::ToolCache);
+    assert_eq!(project.total_cleanable_bytes, 12);
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn hand_written_makefiles_next_to_a_pro_file_survive_a_clean() {
+    let dir = test_dir("analyze_qt_hand_written_makefile");
+    fs::write(dir.join("MyApp.pro"), "QT += widgets").unwrap();
+    fs::write(dir.join(".qmake.stash"), "x").unwrap();
+    fs::write(dir.join("Makefile"), "# Makefile for building: MyApp\n# Generated by qmake\n")
+        .unwrap();
+    fs::write(dir.join("Makefile.am"), "bin_PROGRAMS = tool\n").unwrap();
+    fs::write(dir.join("Makefile.in"), "# Makefile.in generated by automake\n").unwrap();
+    fs::write(dir.join("Makefile.local"), "all:\n\tqmake && make\n").unwrap();
+
+    let project = analyze_project(&dir, ProjectKind::Qt).unwrap();
+    let result = clean_project(&project, false).unwrap();
+
+    assert!(result.errors.is_empty());
+    assert!(!dir.join("Makefile").exists());
+    assert!(!dir.join(".qmake.stash").exists());
+    for name in ["Makefile.am", "Makefile.in", "Makefile.local", "MyApp.pro"] {
+        assert!(dir.join(name).exists(), "{name}");
+    }
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn analyze_qt_shadow_builds_and_qmake_output() {
+    let dir = test_dir("analyze_qt");
+    fs::write(dir.join("MyApp.pro"), "QT += widgets").unwrap();
+    fs::write(dir.join("main.cpp"), "int main() {}").unwrap();
+    fs::create_dir_all(dir.join("build-MyApp-Desktop")).unwrap();
+    fs::write(dir.join("build-MyApp-Desktop/MyApp"), "binary").unwrap(); // 6
+    fs::write(dir.join("Makefile"), "# Generated by qmake (3.1)\nall:").unwrap(); // 31
+    fs::write(dir.join(".qmake.stash"), "x").unwrap(); // 1
+    fs::write(dir.join("moc_window.cpp"), "moc").unwrap(); // 3
+    fs::write(dir.join("main.o"), "obj").unwrap(); // 3
+
+    let project = analyze_project(&dir, ProjectKind::Qt).unwrap();
+    let mut names: Vec<&str> = project.clean_targets.iter().map(|t| t.name.as_str()).collect();
+    names.sort();
+    assert_eq!(
+        names,
+        vec![".qmake.stash", "Makefile", "build-MyApp-Desktop", "main.o", "moc_window.cpp"]
+    );
+    let shadow = project
+        .clean_targets
+        .iter()
+        .find(|t| t.name == "build-MyApp-Desktop")
+        .unwrap();
+    assert_eq!(shadow.kind, TargetKind::Dir);
+    assert_eq!(shadow.category, TargetCategory::BuildOutput);
+    assert_eq!(project.total_cleanable_bytes, 44);
+    assert!(dir.join("main.cpp").exists());
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn analyze_godot_csharp_cleans_mono_and_msbuild_output() {
+    let dir = test_dir("analyze_godot_cs");
+    fs::write(dir.join("project.godot"), "").unwrap();
+    fs::write(dir.join("Game.csproj"), "<Project>").unwrap();
+    for sub in [".godot/imported", ".mono/temp", "bin/Debug", "obj"] {
+        fs::create_dir_all(dir.join(sub)).unwrap();
+    }
+    fs::write(dir.join(".godot/imported/icon.ctex"), "tex").unwrap();
+    fs::write(dir.join(".mono/temp/Game.dll"), "dll").unwrap();
+    fs::write(dir.join("bin/Debug/Game.dll"), "dll").unwrap();
+    fs::write(dir.join("obj/project.assets.json"), "{}").unwrap();
+
+    let project = analyze_project(&dir, ProjectKind::Godot).unwrap();
+    let mut names: Vec<&str> = project.clean_targets.iter().map(|t| t.name.as_str()).collect();
+    names.sort();
+    assert_eq!(names, [".godot", ".mono", "bin", "obj"]);
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn analyze_godot_without_csharp_keeps_bin() {
+    let dir = test_dir("analyze_godot_gdext");
+    fs::write(dir.join("project.godot"), "").unwrap();
+    fs::create_dir_all(dir.join(".godot")).unwrap();
+    fs::write(dir.join(".godot/uid_cache.bin"), "uid").unwrap();
+    // GDExtension libraries the game loads at runtime.
+    fs::create_dir_all(dir.join("bin")).unwrap();
+    fs::write(dir.join("bin/libgame.so"), "elf").unwrap();
+
+    let project = analyze_project(&dir, ProjectKind::Godot).unwrap();
+    let names: Vec<&str> = project.clean_targets.iter().map(|t| t.name.as_str()).collect();
+    assert_eq!(names, [".godot"]);
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn analyze_scala_bloop_is_a_tool_cache() {
+    let dir = test_dir("analyze_scala_bloop");
+    fs::write(dir.join("build.sbt"), "").unwrap();
+    fs::create_dir_all(dir.join(".bloop/root/bloop-bsp-clients-classes")).unwrap();
+    fs::write(dir.join(".bloop/root.json"), "{}").unwrap(); // 2
+    fs::write(dir.join(".bloop/root/bloop-bsp-clients-classes/A.class"), "cafe").unwrap(); // 4
+
+    let project = analyze_project(&dir, ProjectKind::Scala).unwrap();
+    assert_eq!(project.clean_targets.len(), 1);
+    assert_eq!(project.clean_targets[0].name, ".bloop");
+    assert_eq!(project.clean_targets[0].category, TargetCategory::ToolCache);
+    assert_eq!(project.total_cleanable_bytes, 6);
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn analyze_conan_build_and_cache() {
+    let dir = test_dir("analyze_conan");
+    fs::write(dir.join("conanfile.txt"), "[requires]").unwrap();
+    fs::create_dir_all(dir.join("build")).unwrap();
+    fs::write(dir.join("build/app.o"), "object").unwrap(); // 6
+    fs::create_dir_all(dir.join(".conan/data")).unwrap();
+    fs::write(dir.join(".conan/data/zlib"), "pkg").unwrap(); // 3
+
+    let project = analyze_project(&dir, ProjectKind::Conan).unwrap();
+    let category = |name: &str| {
+        project
+            .clean_targets
+            .iter()
+            .find(|t| t.name == name)
+            .map(|t| t.category)
+            .unwrap()
+    };
+    assert_eq!(project.clean_targets.len(), 2);
+    assert_eq!(category("build"), TargetCategory::BuildOutput);
+    assert_eq!(category(".conan"), TargetCategory::DependencyCache);
+    assert_eq!(project.total_cleanable_bytes, 9);
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn analyze_terraform_finds_nested_terragrunt_caches() {
+    let dir = test_dir("analyze_terragrunt");
+    fs::write(dir.join("main.tf"), "").unwrap();
+    fs::create_dir_all(dir.join(".terraform")).unwrap();
+    fs::write(dir.join(".terraform/provider"), "bin").unwrap(); // 3
+    for module in ["", "modules/vpc", "live/prod/db"] {
+        let cache = dir.join(module).join(".terragrunt-cache/abc");
+        fs::create_dir_all(&cache).unwrap();
+        fs::write(cache.join("main.tf"), "src").unwrap(); // 3 each
+    }
+    fs::create_dir_all(dir.join("live/stage/.terragrunt-cache")).unwrap(); // empty — skipped
+
+    let project = analyze_project(&dir, ProjectKind::Terraform).unwrap();
+    let mut names: Vec<&str> = project.clean_targets.iter().map(|t| t.name.as_str()).collect();
+    names.sort();
+    assert_eq!(
+        names,
+        vec![
+            ".terraform",
+            ".terragrunt-cache",
+            "live/prod/db/.terragrunt-cache",
+            "modules/vpc/.terragrunt-cache",
+        ]
+    );
+    assert!(
+        project
+            .clean_targets
+            .iter()
+            .all(|t| t.category == TargetCategory::DependencyCache)
+    );
+    assert_eq!(project.total_cleanable_bytes, 12);
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn analyze_mkdocs_site() {
+    let dir = test_dir("analyze_mkdocs");
+    fs::write(dir.join("mkdocs.yml"), "site_name: x").unwrap();
+    fs::create_dir_all(dir.join("site/assets")).unwrap();
+    fs::write(dir.join("site/index.html"), "<html>").unwrap(); // 6
+    fs::write(dir.join("site/assets/app.css"), "body{}").unwrap(); // 6
+
+    let project = analyze_project(&dir, ProjectKind::Docs).unwrap();
+    assert_eq!(project.clean_targets.len(), 1);
+    assert_eq!(project.clean_targets[0].name, "site");
+    assert_eq!(project.total_cleanable_bytes, 12);
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn analyze_sphinx_nested_build() {
+    let dir = test_dir("analyze_sphinx");
+    fs::create_dir_all(dir.join("docs/_build/html")).unwrap();
+    fs::write(dir.join("docs/conf.py"), "project = 'x'").unwrap();
+    fs::write(dir.join("docs/_build/html/index.html"), "<html>").unwrap(); // 6
+
+    let project = analyze_project(&dir, ProjectKind::Docs).unwrap();
+    assert_eq!(project.clean_targets.len(), 1);
+    assert_eq!(project.clean_targets[0].name, "docs/_build");
+    assert_eq!(project.clean_targets[0].category, TargetCategory::BuildOutput);
+    assert_eq!(project.total_cleanable_bytes, 6);
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn analyze_rust_granular_lists_profiles_separately() {
+    let dir = test_dir("analyze_rust_granular");
+    fs::write(dir.join("Cargo.toml"), "[package]").unwrap();
+    fs::create_dir_all(dir.join("target/debug/deps")).unwrap();
+    fs::create_dir_all(dir.join("target/release")).unwrap();
+    fs::write(dir.join("target/debug/deps/libfoo.rlib"), "debug-build").unwrap(); // 11
+    fs::write(dir.join("target/release/app"), "fast").unwrap(); // 4
+    fs::write(dir.join("target/CACHEDIR.TAG"), "Signature").unwrap();
+
+    let whole = analyze_project(&dir, ProjectKind::Rust).unwrap();
+    assert_eq!(whole.clean_targets.len(), 1);
+    assert_eq!(whole.clean_targets[0].name, "target");
+
+    let config = DevSweepConfig {
+        granular: true,
+        ..Default::default()
+    };
+    let project = analyze_project_with(&dir, ProjectKind::Rust, &config).unwrap();
+    let names: Vec<&str> = project.clean_targets.iter().map(|t| t.name.as_str()).collect();
+    assert_eq!(names, vec!["target/debug", "target/release"]);
+    assert_eq!(project.clean_targets[0].path, dir.join("target/debug"));
+    assert_eq!(project.clean_targets[0].size_bytes, 11);
+    assert_eq!(project.clean_targets[1].size_bytes, 4);
+    assert_eq!(project.total_cleanable_bytes, 15);
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn analyze_drops_targets_below_target_min_size() {
+    let dir = test_dir("analyze_target_min_size");
+    fs::write(dir.join("package.json"), "{}").unwrap();
+    fs::create_dir_all(dir.join(".cache")).unwrap();
+    fs::create_dir_all(dir.join("node_modules/big")).unwrap();
+    fs::write(dir.join(".cache/tiny"), "0123456789").unwrap(); // 10
+    fs::write(dir.join("node_modules/big/index.js"), "x".repeat(4096)).unwrap();
+
+    let all = analyze_project(&dir, ProjectKind::Node).unwrap();
+    assert_eq!(all.clean_targets.len(), 2);
+
+    let config = DevSweepConfig {
+        target_min_size: Some(1024),
+        ..Default::default()
+    };
+    let project = analyze_project_with(&dir, ProjectKind::Node, &config).unwrap();
+    assert_eq!(project.clean_targets.len(), 1);
+    assert_eq!(project.clean_targets[0].name, "node_modules");
+    assert!(project.clean_targets[0].at_least);
+    assert_eq!(project.total_cleanable_bytes, 4096);
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn analyze_stops_sizing_a_target_once_it_reaches_target_min_size() {
+    let dir = test_dir("analyze_target_min_size_bounded");
+    fs::write(dir.join("package.json"), "{}").unwrap();
+    fs::create_dir_all(dir.join("node_modules")).unwrap();
+    for i in 0..100 {
+        fs::write(dir.join(format!("node_modules/f{i:03}")), "x".repeat(10)).unwrap();
+    }
+
+    let config = DevSweepConfig {
+        target_min_size: Some(50),
+        ..Default::default()
+    };
+    let project = analyze_project_with(&dir, ProjectKind::Node, &config).unwrap();
+    let target = &project.clean_targets[0];
+
+    // Equal-sized files, so 50 bytes counted means only 5 of 100 were visited
+    assert!(target.at_least);
+    assert_eq!(target.size_bytes, 50);
+    assert!(project.is_estimate());
+
+    let exact = analyze_project(&dir, ProjectKind::Node).unwrap();
+    assert!(!exact.clean_targets[0].at_least);
+    assert_eq!(exact.clean_targets[0].size_bytes, 1000);
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn analyze_keeps_every_target_past_max_targets() {
+    let dir = test_dir("analyze_max_targets");
+    fs::write(dir.join("setup.py"), "").unwrap();
+    for i in 0..100 {
+        let egg = dir.join(format!("pkg{i}.egg-info"));
+        fs::create_dir_all(&egg).unwrap();
+        fs::write(egg.join("PKG-INFO"), "x".repeat(i + 1)).unwrap();
+    }
+
+    let config = DevSweepConfig {
+        max_targets: Some(10),
+        ..Default::default()
+    };
+    let project = analyze_project_with(&dir, ProjectKind::Python, &config).unwrap();
+
+    // The cap only shortens the table; the scan keeps everything for cleaning
+    assert_eq!(project.clean_targets.len(), 100);
+    assert_eq!(project.total_cleanable_bytes, (1..=100).sum::<u64>());
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn analyze_glob_dir_pattern_skips_files() {
+    // A file named like a directory glob is not that directory
+    let dir = test_dir("analyze_egg_info_file");
+    fs::write(dir.join("setup.py"), "").unwrap();
+    fs::write(dir.join("pkg.egg-info"), "Metadata-Version: 1.0").unwrap();
+    fs::create_dir_all(dir.join("other.egg-info")).unwrap();
+    fs::write(dir.join("other.egg-info/PKG-INFO"), "info").unwrap(); // 4
+
+    let project = analyze_project(&dir, ProjectKind::Python).unwrap();
+
+    assert_eq!(project.clean_targets.len(), 1);
+    let dir_target = &project.clean_targets[0];
+    assert_eq!(dir_target.name, "other.egg-info");
+    assert_eq!(dir_target.kind, TargetKind::Dir);
+    assert_eq!(dir_target.size_bytes, 4);
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn qt_shadow_build_glob_skips_a_build_script() {
+    let dir = test_dir("analyze_qt_build_script");
+    fs::write(dir.join("MyApp.pro"), "QT += widgets").unwrap();
+    fs::write(dir.join("build-foo.sh"), "#!/bin/sh\nqmake && make\n").unwrap();
+    fs::create_dir_all(dir.join("build-MyApp-Debug")).unwrap();
+    fs::write(dir.join("build-MyApp-Debug/MyApp"), "binary").unwrap();
+
+    let project = analyze_project(&dir, ProjectKind::Qt).unwrap();
+    let names: Vec<&str> = project.clean_targets.iter().map(|t| t.name.as_str()).collect();
+    assert_eq!(names, ["build-MyApp-Debug"]);
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn analyze_assigns_target_categories() {
+    let dir = test_dir("analyze_categories");
+    fs::write(dir.join("pyproject.toml"), "[project]").unwrap();
+    for sub in [".venv", ".mypy_cache", "pkg.egg-info", "src/__pycache__"] {
+        fs::create_dir_all(dir.join(sub)).unwrap();
+        fs::write(dir.join(sub).join("f"), "data").unwrap();
+    }
+
+    let project = analyze_project(&dir, ProjectKind::Python).unwrap();
+    let category = |name: &str| {
+        project
+            .clean_targets
+            .iter()
+            .find(|t| t.name == name)
+            .map(|t| t.category)
+            .unwrap()
+    };
+
+    assert_eq!(category(".venv"), TargetCategory::DependencyCache);
+    assert_eq!(category(".mypy_cache"), TargetCategory::ToolCache);
+    assert_eq!(category("pkg.egg-info"), TargetCategory::BuildOutput);
+    assert_eq!(category("src/__pycache__"), TargetCategory::ToolCache);
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn analyze_finds_python_tool_caches_at_any_depth() {
+    let dir = test_dir("analyze_nested_python_caches");
+    fs::write(dir.join("pyproject.toml"), "[project]").unwrap();
+    for sub in [
+        ".pytest_cache",
+        "packages/api/.pytest_cache/v/cache",
+        "packages/api/.mypy_cache",
+        "packages/web/.ruff_cache",
+        // Skipped: inside a skip dir, or empty
+        ".venv/lib/.pytest_cache",
+        "packages/empty/.ruff_cache",
+    ] {
+        fs::create_dir_all(dir.join(sub)).unwrap();
+    }
+    for file in [
+        ".pytest_cache/README.md",
+        "packages/api/.pytest_cache/v/cache/lastfailed",
+        "packages/api/.mypy_cache/meta.json",
+        "packages/web/.ruff_cache/CACHEDIR.TAG",
+        ".venv/lib/.pytest_cache/x",
+    ] {
+        fs::write(dir.join(file), "data").unwrap();
+    }
+
+    let project = analyze_project(&dir, ProjectKind::Python).unwrap();
+    let mut names: Vec<&str> = project
+        .clean_targets
+        .iter()
+        .filter(|t| t.category == TargetCategory::ToolCache)
+        .map(|t| t.name.as_str())
+        .collect();
+    names.sort();
+    assert_eq!(
+        names,
+        [
+            ".pytest_cache",
+            "packages/api/.mypy_cache",
+            "packages/api/.pytest_cache",
+            "packages/web/.ruff_cache",
+        ]
+    );
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn generic_caches_flag_reports_dot_cache_in_any_project() {
+    let base = test_dir("analyze_generic_caches");
+    let dir = base.join("ml");
+    fs::create_dir_all(dir.join(".cache/huggingface")).unwrap();
+    fs::write(dir.join("pyproject.toml"), "[project]").unwrap();
+    fs::write(dir.join(".cache/huggingface/model.bin"), "x".repeat(64)).unwrap();
+    // Outside any project, a .cache is never reported
+    fs::create_dir_all(base.join(".cache")).unwrap();
+    fs::write(base.join(".cache/stray"), "x".repeat(32)).unwrap();
+
+    let default = analyze_project(&dir, ProjectKind::Python).unwrap();
+    assert!(default.clean_targets.iter().all(|t| t.name != ".cache"));
+
+    let config = DevSweepConfig {
+        generic_caches: true,
+        no_spinner: true,
+        ..Default::default()
+    };
+    let project = analyze_project_with(&dir, ProjectKind::Python, &config).unwrap();
+    let cache = project.clean_targets.iter().find(|t| t.name == ".cache").unwrap();
+    assert_eq!(cache.category, TargetCategory::ToolCache);
+    // Same category as the `.cache` a Node project reports on its own
+    assert_eq!(ProjectKind::Node.target_category(".cache"), cache.category);
+    assert_eq!(cache.size_bytes, 64);
+
+    let projects = scan_directory(&base, None, &config).unwrap();
+    assert_eq!(projects.len(), 1);
+    assert_eq!(projects[0].total_cleanable_bytes, 64);
+
+    fs::remove_dir_all(&base).unwrap();
+}
+
+#[test]
+fn ide_caches_flag_reports_idea_and_vs_but_not_vscode() {
+    let dir = test_dir("analyze_ide_caches");
+    fs::write(dir.join("pom.xml"), "<project/>").unwrap();
+    fs::create_dir_all(dir.join(".idea/caches")).unwrap();
+    fs::write(dir.join(".idea/caches/content.dat"), "x".repeat(40)).unwrap();
+    fs::create_dir_all(dir.join(".vs/app/v17")).unwrap();
+    fs::write(dir.join(".vs/app/v17/.suo"), "x".repeat(8)).unwrap();
+    fs::create_dir_all(dir.join(".vscode")).unwrap();
+    fs::write(dir.join(".vscode/settings.json"), "{}").unwrap();
+
+    let default = analyze_project(&dir, ProjectKind::Java).unwrap();
+    assert!(default.clean_targets.is_empty());
+
+    let config = DevSweepConfig {
+        ide_caches: true,
+        ..Default::default()
+    };
+    let project = analyze_project_with(&dir, ProjectKind::Java, &config).unwrap();
+    let mut names: Vec<_> = project
+        .clean_targets
+        .iter()
+        .map(|t| (t.name.as_str(), t.category, t.size_bytes))
+        .collect();
+    names.sort_by_key(|(name, _, _)| *name);
+    assert_eq!(
+        names,
+        [
+            (".idea", TargetCategory::ToolCache, 40),
+            (".vs", TargetCategory::ToolCache, 8),
+        ]
+    );
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn pdm_pypackages_found_only_in_poetry_or_pdm_projects() {
+    let pdm = test_dir("analyze_pdm_pypackages");
+    fs::write(pdm.join("pyproject.toml"), "[project]\nname = \"app\"\n\n[tool.pdm]\n").unwrap();
+    fs::create_dir_all(pdm.join("__pypackages__/3.12/lib/requests")).unwrap();
+    fs::write(pdm.join("__pypackages__/3.12/lib/requests/api.py"), "x".repeat(30)).unwrap();
+    fs::create_dir_all(pdm.join("dist")).unwrap();
+    fs::write(pdm.join("dist/app-0.1.tar.gz"), "x".repeat(10)).unwrap();
+
+    let script = test_dir("analyze_script_pypackages");
+    fs::write(script.join("requirements.txt"), "requests\n").unwrap();
+    fs::create_dir_all(script.join("__pypackages__/3.12/lib")).unwrap();
+    fs::write(script.join("__pypackages__/3.12/lib/mod.py"), "x").unwrap();
+    fs::create_dir_all(script.join("dist")).unwrap();
+    fs::write(script.join("dist/report.html"), "x").unwrap();
+
+    let project = analyze_project(&pdm, ProjectKind::Python).unwrap();
+    let plain = analyze_project(&script, ProjectKind::Python).unwrap();
+    fs::remove_dir_all(&pdm).unwrap();
+    fs::remove_dir_all(&script).unwrap();
+
+    let packages = project.clean_targets.iter().find(|t| t.name == "__pypackages__").unwrap();
+    assert_eq!(packages.size_bytes, 30);
+    assert_eq!(packages.category, TargetCategory::DependencyCache);
+    assert!(project.clean_targets.iter().any(|t| t.name == "dist"));
+    assert!(plain.clean_targets.is_empty());
+}
+
+#[test]
+fn project_local_compiler_caches_are_targets() {
+    let dir = test_dir("analyze_compiler_caches");
+    fs::write(dir.join("Cargo.toml"), "[package]").unwrap();
+    fs::create_dir_all(dir.join("target")).unwrap();
+    fs::write(dir.join("target/bin"), "x".repeat(10)).unwrap();
+    fs::create_dir_all(dir.join(".sccache/a/b")).unwrap();
+    fs::write(dir.join(".sccache/a/b/entry"), "x".repeat(30)).unwrap();
+
+    let project = analyze_project(&dir, ProjectKind::Rust).unwrap();
+    let sccache = project.clean_targets.iter().find(|t| t.name == ".sccache").unwrap();
+    assert_eq!(sccache.category, TargetCategory::CompilerCache);
+    assert_eq!(sccache.size_bytes, 30);
+    assert_eq!(project.total_cleanable_bytes, 40);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn network_restore_flag_only_for_pure_dependency_caches() {
+    let base = test_dir("analyze_network_restore");
+
+    let web = base.join("web");
+    fs::create_dir_all(web.join("node_modules/react")).unwrap();
+    fs::write(web.join("package.json"), "{}").unwrap();
+    fs::write(web.join("node_modules/react/index.js"), "code").unwrap();
+    let node = analyze_project(&web, ProjectKind::Node).unwrap();
+    assert!(node.needs_network_restore());
+
+    // Once a build output sits next to node_modules, the flag goes away
+    fs::create_dir_all(web.join("dist")).unwrap();
+    fs::write(web.join("dist/app.js"), "bundle").unwrap();
+    let node = analyze_project(&web, ProjectKind::Node).unwrap();
+    assert!(!node.needs_network_restore());
+
+    let rs = base.join("rs");
+    fs::create_dir_all(rs.join("target")).unwrap();
+    fs::write(rs.join("Cargo.toml"), "[package]").unwrap();
+    fs::write(rs.join("target/bin"), "binary").unwrap();
+    let rust = analyze_project(&rs, ProjectKind::Rust).unwrap();
+    assert!(!rust.needs_network_restore());
+
+    fs::remove_dir_all(&base).unwrap();
+}
+
 // ── find_pycache_recursive ──────────────────────────────────────────────────
 
 #[test]
@@ -236,6 +1050,33 @@ fn pycache_skips_empty_cache_dirs() {
     fs::remove_dir_all(&dir).unwrap();
 }
 
+#[test]
+fn nested_caches_of_every_name_are_sized_like_other_targets() {
+    let dir = test_dir("nested_caches_fast");
+    fs::write(dir.join("pyproject.toml"), "[project]").unwrap();
+    for cache in ["pkg/__pycache__", "pkg/.mypy_cache", "svc/.pytest_cache"] {
+        fs::create_dir_all(dir.join(cache)).unwrap();
+        fs::write(dir.join(cache).join("data"), "bytes").unwrap();
+    }
+
+    let config = DevSweepConfig {
+        fast: true,
+        ..Default::default()
+    };
+    let project = analyze_project_with(&dir, ProjectKind::Python, &config).unwrap();
+    let mut nested: Vec<&str> = project
+        .clean_targets
+        .iter()
+        .filter(|t| t.name.contains('/'))
+        .map(|t| t.name.as_str())
+        .collect();
+    nested.sort();
+
+    assert_eq!(nested, ["pkg/.mypy_cache", "pkg/__pycache__", "svc/.pytest_cache"]);
+    assert!(project.clean_targets.iter().all(|t| t.estimated));
+    fs::remove_dir_all(&dir).unwrap();
+}
+
 // ── scan_directory (integration) ────────────────────────────────────────────
 
 #[test]
@@ -272,6 +1113,135 @@ fn scan_finds_multiple_project_types() {
     fs::remove_dir_all(&root).unwrap();
 }
 
+#[test]
+fn scan_no_spinner_writes_nothing_to_progress() {
+    let root = test_dir("scan_no_spinner");
+    let proj = root.join("app");
+    fs::create_dir_all(proj.join("target")).unwrap();
+    fs::write(proj.join("Cargo.toml"), "[package]").unwrap();
+    fs::write(proj.join("target/bin"), "data").unwrap();
+
+    let mut drawn = Vec::new();
+    scan_directory_to(&root, None, &DevSweepConfig::default(), &mut drawn).unwrap();
+    assert!(String::from_utf8_lossy(&drawn).contains('\x1b'));
+
+    let config = DevSweepConfig {
+        no_spinner: true,
+        ..Default::default()
+    };
+    let mut quiet = Vec::new();
+    let projects = scan_directory_to(&root, None, &config, &mut quiet).unwrap();
+    assert!(quiet.is_empty());
+    assert_eq!(projects.len(), 1);
+
+    fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+fn scan_each_reports_every_project_with_artifacts_once() {
+    let root = test_dir("scan_each_callback");
+    for name in ["one", "two", "three"] {
+        let proj = root.join(name);
+        fs::create_dir_all(proj.join("target")).unwrap();
+        fs::write(proj.join("Cargo.toml"), "[package]").unwrap();
+        fs::write(proj.join("target/bin"), "data").unwrap();
+    }
+    // Detected, but nothing to clean: must not be reported.
+    fs::create_dir_all(root.join("clean")).unwrap();
+    fs::write(root.join("clean/Cargo.toml"), "[package]").unwrap();
+
+    let config = DevSweepConfig {
+        no_spinner: true,
+        ..Default::default()
+    };
+    let seen = Mutex::new(Vec::new());
+    let (projects, _) = scan_directory_each(&root, None, &config, &mut Vec::new(), &|p| {
+        seen.lock().unwrap().push(p.name.clone())
+    })
+    .unwrap();
+
+    let mut seen = seen.into_inner().unwrap();
+    seen.sort();
+    assert_eq!(seen, vec!["one", "three", "two"]);
+    assert_eq!(projects.len(), 3);
+
+    fs::remove_dir_all(&root).unwrap();
+}
+
+#[cfg(unix)]
+#[test]
+fn scan_reports_unreadable_dirs_from_walk_and_sizing() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let root = test_dir("scan_unreadable");
+    let proj = root.join("app");
+    fs::create_dir_all(proj.join("target/debug")).unwrap();
+    fs::write(proj.join("Cargo.toml"), "[package]").unwrap();
+    fs::write(proj.join("target/bin"), "data").unwrap();
+    let hidden_from_walk = root.join("locked");
+    let hidden_from_sizing = proj.join("target/debug");
+    fs::create_dir_all(&hidden_from_walk).unwrap();
+
+    for dir in [&hidden_from_walk, &hidden_from_sizing] {
+        fs::set_permissions(dir, fs::Permissions::from_mode(0o000)).unwrap();
+    }
+    // Permissions don't restrict root, so there is nothing to observe.
+    let enforced = fs::read_dir(&hidden_from_walk).is_err();
+
+    let config = DevSweepConfig {
+        no_spinner: true,
+        ..Default::default()
+    };
+    let result = scan_directory_each(&root, None, &config, &mut Vec::new(), &|_| {});
+
+    for dir in [&hidden_from_walk, &hidden_from_sizing] {
+        fs::set_permissions(dir, fs::Permissions::from_mode(0o755)).unwrap();
+    }
+    let (projects, unreadable) = result.unwrap();
+    fs::remove_dir_all(&root).unwrap();
+
+    assert_eq!(projects.len(), 1);
+    if !enforced {
+        return;
+    }
+    let paths: Vec<&PathBuf> = unreadable.iter().map(|(p, _)| p).collect();
+    assert!(paths.contains(&&hidden_from_walk));
+    assert!(paths.contains(&&hidden_from_sizing));
+    assert_eq!(projects[0].unreadable.len(), 1);
+    assert!(unreadable.iter().all(|(_, reason)| !reason.is_empty()));
+}
+
+#[test]
+fn name_filter_keeps_only_matching_projects() {
+    let root = test_dir("name_filter");
+    for name in ["app", "My-App", "library"] {
+        let proj = root.join(name);
+        fs::create_dir_all(proj.join("target")).unwrap();
+        fs::write(proj.join("Cargo.toml"), "[package]").unwrap();
+        fs::write(proj.join("target/bin"), "data").unwrap();
+    }
+
+    let projects = scan_directory(&root, None, &DevSweepConfig::default()).unwrap();
+    assert_eq!(projects.len(), 3);
+    let matching = |filter: &str| {
+        let mut names: Vec<&str> = projects
+            .iter()
+            .filter(|p| p.matches_name(filter))
+            .map(|p| p.name.as_str())
+            .collect();
+        names.sort();
+        names
+    };
+
+    assert_eq!(matching("app"), vec!["My-App", "app"]);
+    assert_eq!(matching("APP"), vec!["My-App", "app"]);
+    assert_eq!(matching("app*"), vec!["app"]);
+    // Paths match too, not just names.
+    assert_eq!(matching("name_filter").len(), 3);
+
+    fs::remove_dir_all(&root).unwrap();
+}
+
 #[test]
 fn scan_respects_max_depth() {
     let root = test_dir("scan_depth");
@@ -323,6 +1293,49 @@ fn scan_excludes_projects_with_no_artifacts() {
     fs::remove_dir_all(&root).unwrap();
 }
 
+// ── analyze_paths (explicit project list) ───────────────────────────────────
+
+#[test]
+fn analyze_paths_from_list_skips_walk_and_non_projects() {
+    let base = test_dir("analyze_from_list");
+    let api = base.join("api");
+    let web = base.join("web");
+    let notes = base.join("notes");
+    fs::create_dir_all(api.join("target")).unwrap();
+    fs::write(api.join("Cargo.toml"), "[package]").unwrap();
+    fs::write(api.join("target/app"), "binary").unwrap();
+    fs::create_dir_all(web.join("node_modules/x")).unwrap();
+    fs::write(web.join("package.json"), "{}").unwrap();
+    fs::write(web.join("node_modules/x/index.js"), "code").unwrap();
+    fs::create_dir_all(&notes).unwrap();
+    // An unlisted project next to the listed ones must not be picked up.
+    fs::create_dir_all(base.join("unlisted/target")).unwrap();
+    fs::write(base.join("unlisted/Cargo.toml"), "[package]").unwrap();
+    fs::write(base.join("unlisted/target/app"), "binary").unwrap();
+
+    let list = format!(
+        "# projects to clean\n{}\n\n  {}  \n{}\n",
+        api.display(),
+        web.display(),
+        notes.display(),
+    );
+    let paths = read_path_list(list.as_bytes()).unwrap();
+    assert_eq!(paths, vec![api.clone(), web.clone(), notes.clone()]);
+
+    let (projects, skipped) = analyze_paths(&paths, &DevSweepConfig::default());
+    assert_eq!(projects.len(), 2);
+    assert_eq!(projects[0].path, api);
+    assert_eq!(projects[0].kind, ProjectKind::Rust);
+    assert_eq!(projects[1].path, web);
+    assert_eq!(projects[1].kind, ProjectKind::Node);
+    assert!(projects.iter().all(|p| p.total_cleanable_bytes > 0));
+
+    assert_eq!(skipped.len(), 1);
+    assert_eq!(skipped[0].0, notes);
+
+    fs::remove_dir_all(&base).unwrap();
+}
+
 // ── Config-aware scanning ──────────────────────────────────────────────────
 
 #[test]
@@ -386,6 +1399,36 @@ fn scan_respects_config_ignore_paths() {
     fs::remove_dir_all(&root).unwrap();
 }
 
+#[test]
+fn devcleanignore_prunes_scan_and_excludes_targets() {
+    let root = test_dir("scan_devcleanignore");
+    fs::write(root.join(".devcleanignore"), "vendored/\n").unwrap();
+
+    // A whole project under an ignored directory is never scanned
+    let vendored = root.join("vendored/lib");
+    fs::create_dir_all(vendored.join("target")).unwrap();
+    fs::write(vendored.join("Cargo.toml"), "[package]").unwrap();
+    fs::write(vendored.join("target/bin"), "data").unwrap();
+
+    // A project can protect one of its own would-be targets
+    let web = root.join("web");
+    for dir in ["node_modules", "dist"] {
+        fs::create_dir_all(web.join(dir)).unwrap();
+        fs::write(web.join(dir).join("index.js"), "code").unwrap();
+    }
+    fs::write(web.join("package.json"), "{}").unwrap();
+    fs::write(web.join(".devcleanignore"), "# keep the shipped bundle\n/dist\n").unwrap();
+
+    let projects = scan_directory(&root, None, &DevSweepConfig::default()).unwrap();
+
+    assert_eq!(projects.len(), 1);
+    assert_eq!(projects[0].name, "web");
+    let names: Vec<&str> = projects[0].clean_targets.iter().map(|t| t.name.as_str()).collect();
+    assert_eq!(names, vec!["node_modules"]);
+
+    fs::remove_dir_all(&root).unwrap();
+}
+
 #[test]
 fn scan_config_exclude_multiple_kinds() {
     let root = test_dir("scan_cfg_multi_exclude");
@@ -442,3 +1485,238 @@ fn scan_config_empty_has_no_effect() {
 
     fs::remove_dir_all(&root).unwrap();
 }
+
+// ── custom kinds ────────────────────────────────────────────────────────────
+
+fn mine_config() -> DevSweepConfig {
+    DevSweepConfig {
+        custom_kinds: vec![CustomKind {
+            name: "MyBuild".to_string(),
+            marker_files: vec!["BUILD.mine".to_string()],
+            cleanable_dirs: vec![".mycache".to_string()],
+        }],
+        ..Default::default()
+    }
+}
+
+#[test]
+fn custom_kind_is_detected_during_scan() {
+    let root = test_dir("custom_kind_scan");
+    let proj = root.join("inhouse");
+    fs::create_dir_all(proj.join(".mycache")).unwrap();
+    fs::write(proj.join("BUILD.mine"), "rule()").unwrap();
+    fs::write(proj.join(".mycache/blob"), "cached").unwrap(); // 6
+
+    // Unknown without the config entry
+    assert!(scan_directory(&root, None, &DevSweepConfig::default()).unwrap().is_empty());
+
+    let projects = scan_directory(&root, None, &mine_config()).unwrap();
+    assert_eq!(projects.len(), 1);
+    assert_eq!(projects[0].kind, ProjectKind::Custom);
+    assert_eq!(projects[0].custom_kind.as_deref(), Some("MyBuild"));
+    assert_eq!(projects[0].kind_label(), "MyBuild");
+    assert_eq!(projects[0].clean_targets.len(), 1);
+    assert_eq!(projects[0].clean_targets[0].name, ".mycache");
+    assert_eq!(projects[0].total_cleanable_bytes, 6);
+
+    fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+fn builtin_kind_wins_over_custom_kind() {
+    let dir = test_dir("custom_kind_builtin_wins");
+    fs::write(dir.join("BUILD.mine"), "rule()").unwrap();
+    fs::write(dir.join("Cargo.toml"), "[package]").unwrap();
+    fs::create_dir_all(dir.join("target")).unwrap();
+    fs::write(dir.join("target/bin"), "data").unwrap();
+
+    let projects = scan_directory(&dir, Some(0), &mine_config()).unwrap();
+    assert_eq!(projects.len(), 1);
+    assert_eq!(projects[0].kind, ProjectKind::Rust);
+    assert!(projects[0].custom_kind.is_none());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn custom_kind_cache_is_cleaned() {
+    let dir = test_dir("custom_kind_clean");
+    fs::create_dir_all(dir.join(".mycache/nested")).unwrap();
+    fs::write(dir.join("BUILD.mine"), "rule()").unwrap();
+    fs::write(dir.join(".mycache/nested/blob"), "cached").unwrap();
+
+    let project = analyze_path(&dir, &mine_config()).unwrap();
+    assert_eq!(project.clean_targets[0].category, TargetCategory::Unclassified);
+    let result = clean_project_with(&project, &CleanOptions::default()).unwrap();
+    assert_eq!(result.targets_cleaned, 1);
+    assert_eq!(result.bytes_freed, 6);
+    assert!(!dir.join(".mycache").exists());
+    assert!(dir.join("BUILD.mine").exists());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+// ── git context ─────────────────────────────────────────────────────────────
+
+/// Helper: run a git command in `dir`, panicking on failure.
+fn git(dir: &std::path::Path, args: &[&str]) {
+    let status = std::process::Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+        .args(args)
+        .output()
+        .unwrap()
+        .status;
+    assert!(status.success(), "git {args:?} failed");
+}
+
+#[test]
+fn git_context_reflects_uncommitted_changes() {
+    let dir = test_dir("git_dirty");
+    fs::write(dir.join("Cargo.toml"), "[package]").unwrap();
+    fs::write(dir.join(".gitignore"), "target/\n").unwrap();
+    fs::create_dir_all(dir.join("target")).unwrap();
+    fs::write(dir.join("target/bin"), "data").unwrap();
+
+    git(&dir, &["init", "-q", "-b", "main"]);
+    git(&dir, &["remote", "add", "origin", "https://example.com/app.git"]);
+    git(&dir, &["add", "-A"]);
+    git(&dir, &["commit", "-q", "-m", "init"]);
+
+    let config = DevSweepConfig {
+        git: true,
+        ..Default::default()
+    };
+
+    let clean = analyze_project_with(&dir, ProjectKind::Rust, &config).unwrap();
+    assert_eq!(clean.git_dirty, Some(false));
+    assert_eq!(clean.git_branch.as_deref(), Some("main"));
+    assert_eq!(
+        clean.git_remote.as_deref(),
+        Some("https://example.com/app.git")
+    );
+
+    fs::write(dir.join("Cargo.toml"), "[package]\nname = \"app\"").unwrap();
+    let dirty = analyze_project_with(&dir, ProjectKind::Rust, &config).unwrap();
+    assert_eq!(dirty.git_dirty, Some(true));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn git_context_not_collected_by_default() {
+    let dir = test_dir("git_default");
+    fs::write(dir.join("Cargo.toml"), "[package]").unwrap();
+    git(&dir, &["init", "-q"]);
+
+    let project = analyze_project(&dir, ProjectKind::Rust).unwrap();
+    assert!(project.git_dirty.is_none());
+    assert!(project.git_remote.is_none());
+
+    let json = serde_json::to_string(&project).unwrap();
+    assert!(!json.contains("git_dirty"));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn committed_build_dir_is_ambiguous_but_generated_one_is_not() {
+    let base = test_dir("ambiguous_build");
+
+    // Gradle output: binary blobs nobody commits
+    let generated = base.join("generated");
+    fs::create_dir_all(generated.join("build/classes")).unwrap();
+    fs::write(generated.join("build.gradle"), "plugins {}").unwrap();
+    fs::write(generated.join("build/classes/Main.class"), [0xca, 0xfe, 0xba, 0xbe]).unwrap();
+    fs::write(generated.join("build/app.jar"), [0x50, 0x4b, 0x03, 0x04]).unwrap();
+
+    // A repo whose build/ holds tracked build scripts
+    let committed = base.join("committed");
+    fs::create_dir_all(committed.join("build")).unwrap();
+    fs::write(committed.join("build.gradle"), "plugins {}").unwrap();
+    fs::write(committed.join("build/release.gradle"), "task release {}").unwrap();
+    git(&committed, &["init", "-q"]);
+    git(&committed, &["add", "-A"]);
+    git(&committed, &["commit", "-q", "-m", "init"]);
+
+    // Only a .gitkeep, outside any repo
+    let kept = base.join("kept");
+    fs::create_dir_all(kept.join("build")).unwrap();
+    fs::write(kept.join("build.gradle"), "plugins {}").unwrap();
+    fs::write(kept.join("build/.gitkeep"), "\n").unwrap();
+
+    let mut projects: Vec<_> = [&generated, &committed, &kept]
+        .iter()
+        .map(|dir| analyze_project(dir, ProjectKind::Java).unwrap())
+        .collect();
+    // Scanning alone never asks git
+    let flagged = projects.iter().flat_map(|p| &p.clean_targets).any(|t| t.ambiguous);
+    assert!(!flagged);
+
+    mark_ambiguous_builds(&mut projects);
+    let build = |i: usize| {
+        projects[i]
+            .clean_targets
+            .iter()
+            .find(|t| t.name == "build")
+            .map(|t| t.ambiguous)
+    };
+    assert_eq!(build(0), Some(false));
+    assert_eq!(build(1), Some(true));
+    assert_eq!(build(2), Some(true));
+
+    let skipped = drop_ambiguous_builds(&mut projects);
+    assert_eq!(skipped, vec![committed.join("build"), kept.join("build")]);
+    assert_eq!(projects.len(), 1);
+    assert_eq!(projects[0].path, generated);
+
+    fs::remove_dir_all(&base).unwrap();
+}
+
+#[test]
+fn gradle_modules_are_reported_under_the_settings_root() {
+    let root = test_dir("scan_gradle_multi_module");
+    let build = root.join("shop");
+    fs::create_dir_all(build.join(".gradle/8.5")).unwrap();
+    fs::write(build.join("settings.gradle"), "include 'api', 'web'").unwrap();
+    fs::write(build.join(".gradle/8.5/fileHashes.bin"), "x".repeat(10)).unwrap();
+    for module in ["api", "web"] {
+        let dir = build.join(module);
+        fs::create_dir_all(dir.join("build/libs")).unwrap();
+        fs::write(dir.join("build.gradle"), "plugins {}").unwrap();
+        fs::write(dir.join("build/libs/app.jar"), "x".repeat(100)).unwrap();
+    }
+    // A Maven project inside the Gradle tree is its own project
+    let maven = build.join("tools/legacy");
+    fs::create_dir_all(maven.join("target")).unwrap();
+    fs::write(maven.join("pom.xml"), "<project/>").unwrap();
+    fs::write(maven.join("target/legacy.jar"), "x".repeat(50)).unwrap();
+
+    let config = DevSweepConfig {
+        no_spinner: true,
+        ..Default::default()
+    };
+    let streamed = Mutex::new(Vec::new());
+    let (mut projects, _) = scan_directory_each(&root, None, &config, &mut Vec::new(), &|p| {
+        streamed.lock().unwrap().push(p.path.clone())
+    })
+    .unwrap();
+    projects.sort_by(|a, b| a.path.cmp(&b.path));
+    fs::remove_dir_all(&root).unwrap();
+
+    assert_eq!(projects.len(), 2);
+    let shop = &projects[0];
+    assert_eq!(shop.path, build);
+    assert_eq!(shop.kind, ProjectKind::Java);
+    assert_eq!(shop.total_cleanable_bytes, 210);
+    let mut names: Vec<_> = shop.clean_targets.iter().map(|t| t.name.as_str()).collect();
+    names.sort();
+    assert_eq!(names, [".gradle", "api/build", "web/build"]);
+    assert_eq!(projects[1].path, maven);
+
+    // Modules are only reported once merged, never on their own
+    let mut streamed = streamed.into_inner().unwrap();
+    streamed.sort();
+    assert_eq!(streamed, [build, maven]);
+}
@@ -1,4 +1,4 @@
-//! Tests for project kind detection across all 17 supported project types.
+//! Tests for project kind detection across all supported project types.
 
 use std::fs;
 use std::path::PathBuf;
@@ -32,6 +32,32 @@ fn detect_node() {
     fs::remove_dir_all(&dir).unwrap();
 }
 
+#[test]
+fn detect_bun_lockb() {
+    let dir = test_dir("detect_bun_lockb");
+    fs::write(dir.join("bun.lockb"), "").unwrap();
+    assert_eq!(detect_project_kind(&dir), Some(ProjectKind::Bun));
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn detect_bun_wins_over_node() {
+    let dir = test_dir("detect_bun_over_node");
+    fs::write(dir.join("package.json"), "{}").unwrap();
+    fs::write(dir.join("bun.lockb"), "").unwrap();
+    assert_eq!(detect_project_kind(&dir), Some(ProjectKind::Bun));
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn detect_bunfig_with_package_json() {
+    let dir = test_dir("detect_bunfig");
+    fs::write(dir.join("package.json"), "{}").unwrap();
+    fs::write(dir.join("bunfig.toml"), "").unwrap();
+    assert_eq!(detect_project_kind(&dir), Some(ProjectKind::Bun));
+    fs::remove_dir_all(&dir).unwrap();
+}
+
 #[test]
 fn detect_python_pyproject() {
     let dir = test_dir("detect_py_pyproject");
@@ -104,6 +130,39 @@ fn detect_cmake() {
     fs::remove_dir_all(&dir).unwrap();
 }
 
+#[test]
+fn detect_conan_txt() {
+    let dir = test_dir("detect_conan_txt");
+    fs::write(dir.join("conanfile.txt"), "").unwrap();
+    assert_eq!(detect_project_kind(&dir), Some(ProjectKind::Conan));
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn detect_conan_py() {
+    let dir = test_dir("detect_conan_py");
+    fs::write(dir.join("conanfile.py"), "").unwrap();
+    assert_eq!(detect_project_kind(&dir), Some(ProjectKind::Conan));
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn detect_conan_wins_over_cmake() {
+    let dir = test_dir("detect_conan_over_cmake");
+    fs::write(dir.join("CMakeLists.txt"), "").unwrap();
+    fs::write(dir.join("conanfile.txt"), "").unwrap();
+    assert_eq!(detect_project_kind(&dir), Some(ProjectKind::Conan));
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn detect_qt_pro_file() {
+    let dir = test_dir("detect_qt");
+    fs::write(dir.join("MyApp.pro"), "QT += widgets").unwrap();
+    assert_eq!(detect_project_kind(&dir), Some(ProjectKind::Qt));
+    fs::remove_dir_all(&dir).unwrap();
+}
+
 #[test]
 fn detect_swift() {
     let dir = test_dir("detect_swift");
@@ -152,6 +211,15 @@ fn detect_godot() {
     fs::remove_dir_all(&dir).unwrap();
 }
 
+#[test]
+fn detect_godot_csharp_over_dotnet() {
+    let dir = test_dir("detect_godot_cs");
+    fs::write(dir.join("project.godot"), "").unwrap();
+    fs::write(dir.join("Game.csproj"), "<Project Sdk=\"Godot.NET.Sdk\">").unwrap();
+    assert_eq!(detect_project_kind(&dir), Some(ProjectKind::Godot));
+    fs::remove_dir_all(&dir).unwrap();
+}
+
 // ── Glob-based marker files ─────────────────────────────────────────────────
 
 #[test]
@@ -210,6 +278,71 @@ fn detect_terraform_glob() {
     fs::remove_dir_all(&dir).unwrap();
 }
 
+#[test]
+fn detect_latex_tex_glob() {
+    let dir = test_dir("detect_latex_tex");
+    fs::write(dir.join("thesis.tex"), "\\documentclass{article}").unwrap();
+    assert_eq!(detect_project_kind(&dir), Some(ProjectKind::Latex));
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn detect_latex_latexmkrc() {
+    let dir = test_dir("detect_latex_rc");
+    fs::write(dir.join("latexmkrc"), "$pdf_mode = 1;").unwrap();
+    assert_eq!(detect_project_kind(&dir), Some(ProjectKind::Latex));
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn detect_latex_hidden_latexmkrc() {
+    let dir = test_dir("detect_latex_hidden_rc");
+    fs::write(dir.join(".latexmkrc"), "$pdf_mode = 1;").unwrap();
+    assert_eq!(detect_project_kind(&dir), Some(ProjectKind::Latex));
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn detect_docker_dockerfile() {
+    let dir = test_dir("detect_docker_dockerfile");
+    fs::write(dir.join("Dockerfile"), "FROM alpine").unwrap();
+    assert_eq!(detect_project_kind(&dir), Some(ProjectKind::Docker));
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn detect_docker_compose_yml() {
+    let dir = test_dir("detect_docker_compose_yml");
+    fs::write(dir.join("docker-compose.yml"), "services: {}").unwrap();
+    assert_eq!(detect_project_kind(&dir), Some(ProjectKind::Docker));
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn detect_docker_compose_yaml() {
+    let dir = test_dir("detect_docker_compose_yaml");
+    fs::write(dir.join("compose.yaml"), "services: {}").unwrap();
+    assert_eq!(detect_project_kind(&dir), Some(ProjectKind::Docker));
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn detect_language_project_wins_over_dockerfile() {
+    let dir = test_dir("detect_docker_with_rust");
+    fs::write(dir.join("Dockerfile"), "FROM rust").unwrap();
+    fs::write(dir.join("Cargo.toml"), "[package]").unwrap();
+    assert_eq!(detect_project_kind(&dir), Some(ProjectKind::Rust));
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn detect_mkdocs() {
+    let dir = test_dir("detect_mkdocs");
+    fs::write(dir.join("mkdocs.yml"), "site_name: x").unwrap();
+    assert_eq!(detect_project_kind(&dir), Some(ProjectKind::Docs));
+    fs::remove_dir_all(&dir).unwrap();
+}
+
 // ── Subdirectory-based marker files ─────────────────────────────────────────
 
 #[test]
@@ -221,6 +354,25 @@ fn detect_unity() {
     fs::remove_dir_all(&dir).unwrap();
 }
 
+#[test]
+fn detect_sphinx_docs_conf() {
+    let dir = test_dir("detect_sphinx");
+    fs::create_dir_all(dir.join("docs")).unwrap();
+    fs::write(dir.join("docs/conf.py"), "project = 'x'").unwrap();
+    assert_eq!(detect_project_kind(&dir), Some(ProjectKind::Docs));
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn detect_python_wins_over_sphinx_docs() {
+    let dir = test_dir("detect_python_over_sphinx");
+    fs::create_dir_all(dir.join("docs")).unwrap();
+    fs::write(dir.join("docs/conf.py"), "").unwrap();
+    fs::write(dir.join("pyproject.toml"), "[project]").unwrap();
+    assert_eq!(detect_project_kind(&dir), Some(ProjectKind::Python));
+    fs::remove_dir_all(&dir).unwrap();
+}
+
 // ── Edge cases ──────────────────────────────────────────────────────────────
 
 #[test]
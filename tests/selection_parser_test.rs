@@ -1,6 +1,14 @@
-//! Tests for the multi-select input parser (e.g. "1,3,5-8").
+//! Tests for prompt input parsing: multi-select (e.g. "1,3,5-8") and its
+//! paging, typed confirmation, and prompt timeouts.
 
-use dev_sweep::tui::display::parse_selection;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, mpsc};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use dev_sweep::tui::display::{
+    PromptReader, SELECT_PAGE_SIZE, multi_select, parse_selection, typed_word_matches,
+};
 
 // ── valid inputs ────────────────────────────────────────────────────────────
 
@@ -95,3 +103,128 @@ fn parse_non_number_is_error() {
 fn parse_range_with_non_number_is_error() {
     assert!(parse_selection("a-5", 10).is_err());
 }
+
+// ── typed confirmation ──────────────────────────────────────────────────────
+
+#[test]
+fn typed_word_accepts_exact_word() {
+    assert!(typed_word_matches("clean\n", "clean"));
+    assert!(typed_word_matches("  clean  ", "clean"));
+}
+
+#[test]
+fn typed_word_rejects_yes_and_near_misses() {
+    assert!(!typed_word_matches("y\n", "clean"));
+    assert!(!typed_word_matches("yes\n", "clean"));
+    assert!(!typed_word_matches("\n", "clean"));
+    assert!(!typed_word_matches("CLEAN\n", "clean"));
+    assert!(!typed_word_matches("clea\n", "clean"));
+    assert!(!typed_word_matches("clean it\n", "clean"));
+}
+
+// ── --prompt-timeout ────────────────────────────────────────────────────────
+
+#[test]
+fn prompt_gives_up_when_input_never_arrives() {
+    let never = || -> std::io::Result<String> {
+        loop {
+            thread::sleep(Duration::from_secs(3600));
+        }
+    };
+    let reader = PromptReader::new(never);
+    let started = Instant::now();
+    let line = reader.read_line(Some(Duration::from_millis(50))).unwrap();
+    assert_eq!(line, None);
+    assert!(started.elapsed() < Duration::from_secs(5));
+}
+
+#[test]
+fn prompt_returns_input_that_arrives_in_time() {
+    let reader = PromptReader::new(|| Ok("y\n".to_string()));
+    let line = reader.read_line(Some(Duration::from_secs(5))).unwrap();
+    assert_eq!(line.as_deref(), Some("y\n"));
+    let line = reader.read_line(None).unwrap();
+    assert_eq!(line.as_deref(), Some("y\n"));
+
+    let failed = PromptReader::new(|| Err(std::io::Error::other("stdin closed")));
+    assert!(failed.read_line(Some(Duration::from_secs(5))).is_err());
+}
+
+#[test]
+fn prompt_after_a_timeout_gets_the_next_line() {
+    let (typed, input) = mpsc::channel::<String>();
+    let reads = Arc::new(AtomicUsize::new(0));
+    let counted = Arc::clone(&reads);
+    let reader = PromptReader::new(move || {
+        counted.fetch_add(1, Ordering::SeqCst);
+        Ok(input.recv().unwrap())
+    });
+
+    assert_eq!(reader.read_line(Some(Duration::from_millis(20))).unwrap(), None);
+    // The next prompt waits on the read the first one left behind, so the
+    // line typed for it isn't swallowed by a second reader
+    let typing = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(50));
+        typed.send("2\n".to_string()).unwrap();
+        typed
+    });
+    let line = reader.read_line(Some(Duration::from_secs(5))).unwrap();
+    assert_eq!(line.as_deref(), Some("2\n"));
+    assert_eq!(reads.load(Ordering::SeqCst), 1);
+
+    // A late answer to a timed-out prompt isn't taken as the next one's
+    let typed = typing.join().unwrap();
+    assert_eq!(reader.read_line(Some(Duration::from_millis(20))).unwrap(), None);
+    typed.send("y\n".to_string()).unwrap();
+    thread::sleep(Duration::from_millis(50));
+    typed.send("n\n".to_string()).unwrap();
+    let line = reader.read_line(Some(Duration::from_secs(5))).unwrap();
+    assert_eq!(line.as_deref(), Some("n\n"));
+}
+
+// ── multi_select paging ─────────────────────────────────────────────────────
+
+/// Run `multi_select` over `items`, answering with `answers` in order.
+/// Returns the selection and everything printed.
+fn select(items: &[String], answers: &[&str]) -> (Vec<usize>, String) {
+    let mut answers = answers.iter().map(|a| format!("{a}\n"));
+    let mut read = || Ok(answers.next().expect("prompt asked too many times"));
+    let mut out = Vec::new();
+    let selected = multi_select("Pick:", items, &mut read, &mut out).unwrap();
+    (selected, String::from_utf8(out).unwrap())
+}
+
+#[test]
+fn paging_keeps_item_numbers_stable() {
+    let items: Vec<String> = (1..=300).map(|i| format!("project-{i}")).collect();
+
+    // Paging around first selects the same indices as answering right away
+    let (direct, _) = select(&items, &["45,250-252"]);
+    let (paged, out) = select(&items, &["n", "n", "p", "n", "45,250-252"]);
+    assert_eq!(direct, vec![44, 249, 250, 251]);
+    assert_eq!(paged, direct);
+
+    // Each page lists SELECT_PAGE_SIZE items under their global numbers
+    assert!(out.contains("Page 1 of 15 (300 items)"));
+    assert!(out.contains("Page 3 of 15"));
+    assert!(out.contains("project-41\n"));
+    assert!(!out.contains("project-61\n"));
+    assert_eq!(out.matches("project-").count(), 5 * SELECT_PAGE_SIZE);
+
+    // Paging stops at either end; "all" still means every item
+    let (all, out) = select(&items, &["p", "all"]);
+    assert_eq!(all.len(), 300);
+    assert_eq!(out.matches("Page 1 of 15").count(), 2);
+}
+
+#[test]
+fn short_lists_are_not_paged() {
+    let items: Vec<String> = (1..=3).map(|i| format!("project-{i}")).collect();
+    // "n" isn't a page command here, so it's parsed (and rejected) as a selection
+    let mut answers = ["n\n".to_string()].into_iter();
+    let mut read = || Ok(answers.next().unwrap());
+    let mut out = Vec::new();
+    assert!(multi_select("Pick:", &items, &mut read, &mut out).is_err());
+    assert!(!String::from_utf8(out).unwrap().contains("Page"));
+    assert_eq!(select(&items, &["2-3"]).0, vec![1, 2]);
+}
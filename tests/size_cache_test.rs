@@ -0,0 +1,84 @@
+//! Tests for the `--cache-mode` target size cache.
+
+use std::fs::{self, File};
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+use dev_sweep::config::CacheMode;
+use dev_sweep::scanner::cache::SizeCache;
+
+/// Helper: create a fresh temp dir for a test.
+fn test_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("dev_sweep_test_{name}"));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[test]
+fn rewriting_a_file_in_place_invalidates_signature_entries_only() {
+    let dir = test_dir("size_cache_signature");
+    let target = dir.join("target");
+    fs::create_dir_all(&target).unwrap();
+    fs::write(target.join("lib.rlib"), "x".repeat(10)).unwrap();
+    let dir_mtime = fs::metadata(&target).unwrap().modified().unwrap();
+
+    let mut by_mtime = SizeCache::default();
+    by_mtime.insert(&target, CacheMode::Mtime, 10, None);
+    let mut by_signature = SizeCache::default();
+    by_signature.insert(&target, CacheMode::Signature, 10, None);
+    assert_eq!(by_signature.get(&target, CacheMode::Signature), Some((10, None)));
+
+    // Rewriting an existing file leaves the directory's mtime alone
+    fs::write(target.join("lib.rlib"), "x".repeat(20)).unwrap();
+    let later = SystemTime::now() + Duration::from_secs(60);
+    File::options()
+        .write(true)
+        .open(target.join("lib.rlib"))
+        .unwrap()
+        .set_modified(later)
+        .unwrap();
+    assert_eq!(fs::metadata(&target).unwrap().modified().unwrap(), dir_mtime);
+
+    assert_eq!(by_signature.get(&target, CacheMode::Signature), None);
+    // Mtime mode can't tell, and keeps serving the stale size
+    assert_eq!(by_mtime.get(&target, CacheMode::Mtime), Some((10, None)));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn size_cache_round_trips_and_tolerates_garbage() {
+    let dir = test_dir("size_cache_round_trip");
+    let target = dir.join("node_modules");
+    fs::create_dir_all(&target).unwrap();
+    fs::write(target.join("index.js"), "x".repeat(5)).unwrap();
+
+    let mut cache = SizeCache::default();
+    cache.insert(&target, CacheMode::Signature, 5, None);
+    let path = dir.join("cache.json");
+    cache.save(&path).unwrap();
+    let loaded = SizeCache::load(&path);
+    assert_eq!(loaded.get(&target, CacheMode::Signature), Some((5, None)));
+
+    // Adding an entry changes the signature (and the dir mtime)
+    fs::write(target.join("extra.js"), "y").unwrap();
+    assert_eq!(loaded.get(&target, CacheMode::Signature), None);
+
+    fs::write(&path, "not json").unwrap();
+    assert_eq!(SizeCache::load(&path).get(&target, CacheMode::Mtime), None);
+    let missing = SizeCache::load(&dir.join("missing.json"));
+    assert_eq!(missing.get(&target, CacheMode::Mtime), None);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn off_mode_never_caches() {
+    let dir = test_dir("size_cache_off");
+    let mut cache = SizeCache::default();
+    cache.insert(&dir, CacheMode::Off, 42, None);
+    assert_eq!(cache.get(&dir, CacheMode::Off), None);
+    assert_eq!(cache.get(&dir, CacheMode::Mtime), None);
+    fs::remove_dir_all(&dir).unwrap();
+}
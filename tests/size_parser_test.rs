@@ -0,0 +1,33 @@
+//! Tests for the size string parser (e.g. "500", "10MB", "1.5G").
+
+use dev_sweep::util::parse_size;
+
+#[test]
+fn parse_plain_bytes() {
+    assert_eq!(parse_size("0").unwrap(), 0);
+    assert_eq!(parse_size("500").unwrap(), 500);
+    assert_eq!(parse_size("500B").unwrap(), 500);
+}
+
+#[test]
+fn parse_binary_units() {
+    assert_eq!(parse_size("1K").unwrap(), 1024);
+    assert_eq!(parse_size("10MB").unwrap(), 10 * 1024 * 1024);
+    assert_eq!(parse_size("2GiB").unwrap(), 2 * 1024 * 1024 * 1024);
+    assert_eq!(parse_size("1t").unwrap(), 1 << 40);
+}
+
+#[test]
+fn parse_fractional_and_spaced() {
+    assert_eq!(parse_size("1.5G").unwrap(), 3 * 512 * 1024 * 1024);
+    assert_eq!(parse_size(" 2 gb ").unwrap(), 2 * 1024 * 1024 * 1024);
+}
+
+#[test]
+fn parse_rejects_bad_input() {
+    assert!(parse_size("").is_err());
+    assert!(parse_size("GB").is_err());
+    assert!(parse_size("10XB").is_err());
+    assert!(parse_size("-5M").is_err());
+    assert!(parse_size("99999999999T").is_err());
+}
@@ -0,0 +1,380 @@
+//! Tests for summary computations: grouping and subtotals.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use dev_sweep::cli::commands::{ScanContext, cmd_scan, cmd_summary};
+use dev_sweep::cli::{OutputFormat, SummaryArgs};
+use dev_sweep::config::DevSweepConfig;
+use dev_sweep::scanner::{ProjectKind, scan_directories};
+use dev_sweep::summary::{
+    GroupBy, SummarySnapshot, depth_histogram, group_totals, median, size_stats, stale_split, target_totals,
+    threshold_crossed,
+};
+use dev_sweep::tui::display::{Column, DisplayOptions};
+use dev_sweep::util::parse_age;
+
+/// Helper: create a fresh temp dir for a test.
+fn test_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("dev_sweep_test_{name}"));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// Helper: create a Rust project with `size` bytes of build output.
+fn create_rust_project(root: &Path, size: usize) {
+    fs::create_dir_all(root.join("target")).unwrap();
+    fs::write(root.join("Cargo.toml"), "[package]").unwrap();
+    fs::write(root.join("target/bin"), "x".repeat(size)).unwrap();
+}
+
+// ── group_totals ────────────────────────────────────────────────────────────
+
+#[test]
+fn group_by_root_subtotals_sum_to_grand_total() {
+    let base = test_dir("summary_roots");
+    let work = base.join("work");
+    let home = base.join("home");
+
+    create_rust_project(&work.join("api"), 100);
+    create_rust_project(&work.join("cli"), 50);
+    create_rust_project(&home.join("toy"), 25);
+
+    let roots = vec![work.clone(), home.clone()];
+    let projects = scan_directories(&roots, None, &DevSweepConfig::default()).unwrap();
+    assert_eq!(projects.len(), 3);
+
+    let groups = group_totals(&projects, GroupBy::Root);
+    assert_eq!(groups.len(), 2);
+
+    assert_eq!(groups[0].key, work.display().to_string());
+    assert_eq!(groups[0].projects, 2);
+    assert_eq!(groups[0].reclaimable_bytes, 150);
+    assert_eq!(groups[1].key, home.display().to_string());
+    assert_eq!(groups[1].projects, 1);
+    assert_eq!(groups[1].reclaimable_bytes, 25);
+
+    let grand_total: u64 = projects.iter().map(|p| p.total_cleanable_bytes).sum();
+    let subtotal_sum: u64 = groups.iter().map(|g| g.reclaimable_bytes).sum();
+    assert_eq!(subtotal_sum, grand_total);
+
+    fs::remove_dir_all(&base).unwrap();
+}
+
+#[test]
+fn overlapping_roots_report_each_project_once() {
+    let base = test_dir("summary_overlap");
+    create_rust_project(&base.join("nested/app"), 10);
+
+    let roots = vec![base.clone(), base.join("nested")];
+    let projects = scan_directories(&roots, None, &DevSweepConfig::default()).unwrap();
+    assert_eq!(projects.len(), 1);
+    assert_eq!(projects[0].scan_root, base);
+
+    fs::remove_dir_all(&base).unwrap();
+}
+
+#[test]
+fn group_by_prefix_subtotals_per_top_level_directory() {
+    let base = test_dir("summary_prefix");
+    create_rust_project(&base.join("client-a/api"), 100);
+    create_rust_project(&base.join("client-a/web/app"), 50);
+    create_rust_project(&base.join("client-b/cli"), 25);
+
+    let projects =
+        scan_directories(std::slice::from_ref(&base), None, &DevSweepConfig::default()).unwrap();
+    assert_eq!(projects.len(), 3);
+
+    let groups = group_totals(&projects, "prefix=1".parse().unwrap());
+    assert_eq!(groups.len(), 2);
+    assert_eq!(groups[0].key, "client-a");
+    assert_eq!(groups[0].projects, 2);
+    assert_eq!(groups[0].reclaimable_bytes, 150);
+    assert_eq!(groups[1].key, "client-b");
+    assert_eq!(groups[1].projects, 1);
+    assert_eq!(groups[1].reclaimable_bytes, 25);
+
+    // Projects shallower than the requested level land under "."
+    let groups = group_totals(&projects, GroupBy::Prefix(3));
+    assert_eq!(groups[0].key, ".");
+    assert_eq!(groups[0].projects, 2);
+    assert_eq!(groups[1].key, "app");
+
+    fs::remove_dir_all(&base).unwrap();
+}
+
+#[test]
+fn group_by_parses_and_round_trips() {
+    for group_by in [GroupBy::Kind, GroupBy::Root, GroupBy::Prefix(2)] {
+        assert_eq!(group_by.to_string().parse::<GroupBy>().unwrap(), group_by);
+    }
+    for bad in ["prefix=0", "prefix=", "prefix", "language"] {
+        assert!(bad.parse::<GroupBy>().is_err(), "{bad}");
+    }
+}
+
+#[test]
+fn group_by_kind_counts_projects() {
+    let base = test_dir("summary_kinds");
+    create_rust_project(&base.join("a"), 10);
+    create_rust_project(&base.join("b"), 20);
+
+    let node = base.join("web");
+    fs::create_dir_all(node.join("node_modules")).unwrap();
+    fs::write(node.join("package.json"), "{}").unwrap();
+    fs::write(node.join("node_modules/m.js"), "x").unwrap();
+
+    let projects = scan_directories(std::slice::from_ref(&base), None, &DevSweepConfig::default()).unwrap();
+    let groups = group_totals(&projects, GroupBy::Kind);
+
+    assert_eq!(groups.len(), 2);
+    assert_eq!(groups[0].key, "Rust");
+    assert_eq!(groups[0].projects, 2);
+    assert_eq!(groups[0].reclaimable_bytes, 30);
+    assert_eq!(groups[1].key, "Node.js");
+    assert_eq!(groups[1].reclaimable_bytes, 1);
+
+    fs::remove_dir_all(&base).unwrap();
+}
+
+// ── depth_histogram ─────────────────────────────────────────────────────────
+
+#[test]
+fn depth_histogram_counts_projects_by_walk_depth() {
+    let base = test_dir("summary_depths");
+    create_rust_project(&base.join("a"), 10);
+    create_rust_project(&base.join("b"), 10);
+    create_rust_project(&base.join("group/nested/c"), 10);
+
+    let roots = vec![base.clone()];
+    let projects = scan_directories(&roots, None, &DevSweepConfig::default()).unwrap();
+
+    assert_eq!(depth_histogram(&projects), vec![(1, 2), (3, 1)]);
+
+    fs::remove_dir_all(&base).unwrap();
+}
+
+// ── stale_split ─────────────────────────────────────────────────────────────
+
+#[test]
+fn stale_split_buckets_projects_around_threshold() {
+    let base = test_dir("summary_stale");
+    create_rust_project(&base.join("fresh"), 100);
+    create_rust_project(&base.join("old"), 40);
+    create_rust_project(&base.join("ancient"), 2);
+
+    let roots = vec![base.clone()];
+    let mut projects = scan_directories(&roots, None, &DevSweepConfig::default()).unwrap();
+    let now = chrono::Local::now();
+    for p in &mut projects {
+        p.last_modified = match p.name.as_str() {
+            "old" => now - parse_age("45d").unwrap(),
+            "ancient" => now - parse_age("2y").unwrap(),
+            _ => now - parse_age("3d").unwrap(),
+        };
+    }
+
+    let split = stale_split(&projects, now - parse_age("30d").unwrap());
+    assert_eq!(split.active_projects, 1);
+    assert_eq!(split.active_bytes, 100);
+    assert_eq!(split.stale_projects, 2);
+    assert_eq!(split.stale_bytes, 42);
+    assert_eq!(split.active_bytes + split.stale_bytes, 142);
+
+    fs::remove_dir_all(&base).unwrap();
+}
+
+// ── size_stats ──────────────────────────────────────────────────────────────
+
+#[test]
+fn median_of_odd_and_even_counts() {
+    assert_eq!(median(&[]), 0);
+    assert_eq!(median(&[7]), 7);
+    assert_eq!(median(&[900, 10, 50]), 50);
+    assert_eq!(median(&[40, 10, 30, 20]), 25);
+    assert_eq!(median(&[1, 2]), 1); // rounds down
+    assert_eq!(median(&[u64::MAX, u64::MAX - 2]), u64::MAX - 1);
+}
+
+#[test]
+fn size_stats_average_median_and_largest() {
+    let base = test_dir("summary_size_stats");
+    for (name, size) in [("small", 10), ("mid", 30), ("big", 200)] {
+        create_rust_project(&base.join(name), size);
+    }
+
+    let roots = vec![base.clone()];
+    let projects = scan_directories(&roots, None, &DevSweepConfig::default()).unwrap();
+    let stats = size_stats(&projects);
+
+    assert_eq!(stats.average_bytes, 80);
+    assert_eq!(stats.median_bytes, 30);
+    assert_eq!(stats.largest, Some(("big".to_string(), 200)));
+    assert_eq!(size_stats(&[]).largest, None);
+
+    fs::remove_dir_all(&base).unwrap();
+}
+
+// ── target_totals ───────────────────────────────────────────────────────────
+
+#[test]
+fn node_modules_totals_sum_across_node_projects() {
+    let base = test_dir("summary_node_modules");
+    for (name, size) in [("web", 100), ("api", 40), ("docs", 7)] {
+        let dir = base.join(name);
+        fs::create_dir_all(dir.join("node_modules/pkg")).unwrap();
+        fs::write(dir.join("package.json"), "{}").unwrap();
+        fs::write(dir.join("node_modules/pkg/index.js"), "x".repeat(size)).unwrap();
+    }
+    // Other Node outputs and other kinds don't count
+    fs::create_dir_all(base.join("web/dist")).unwrap();
+    fs::write(base.join("web/dist/app.js"), "x".repeat(1000)).unwrap();
+    create_rust_project(&base.join("rs"), 500);
+
+    let roots = vec![base.clone()];
+    let projects = scan_directories(&roots, None, &DevSweepConfig::default()).unwrap();
+
+    let totals = target_totals(&projects, ProjectKind::Node, "node_modules");
+    assert_eq!(totals.count, 3);
+    assert_eq!(totals.bytes, 147);
+
+    fs::remove_dir_all(&base).unwrap();
+}
+
+// ── threshold_crossed ───────────────────────────────────────────────────────
+
+#[test]
+fn threshold_alerts_only_when_rising_above() {
+    let threshold = 1000;
+    // First tick: alert only if already above
+    assert!(!threshold_crossed(None, 500, threshold));
+    assert!(threshold_crossed(None, 1500, threshold));
+    // Exactly at the threshold doesn't count as exceeding it
+    assert!(!threshold_crossed(Some(500), 1000, threshold));
+
+    let totals = [400, 900, 1200, 1800, 700, 1001];
+    let alerts: Vec<bool> = totals
+        .iter()
+        .scan(None, |prev, &t| {
+            let alert = threshold_crossed(*prev, t, threshold);
+            *prev = Some(t);
+            Some(alert)
+        })
+        .collect();
+    assert_eq!(alerts, vec![false, false, true, false, false, true]);
+}
+
+// ── --fail-on-found ─────────────────────────────────────────────────────────
+
+#[test]
+fn fail_on_found_exit_status_follows_total_vs_limit() {
+    let base = test_dir("summary_fail_on_found");
+    create_rust_project(&base.join("a"), 600);
+    create_rust_project(&base.join("b"), 400);
+
+    let roots = [base.clone()];
+    let config = DevSweepConfig {
+        no_spinner: true,
+        ..Default::default()
+    };
+    // (limit, should fail): the total is exactly 1000 bytes
+    let cases = [
+        (None, false),
+        (Some(1000), false),
+        (Some(999), true),
+        (Some(0), true),
+    ];
+    for (limit, fails) in cases {
+        let ctx = ScanContext {
+            roots: &roots,
+            max_depth: None,
+            older_than: None,
+            name: None,
+            modified_since: None,
+            json: true,
+            compact: true,
+            format: OutputFormat::Table,
+            columns: &Column::ALL,
+            display: DisplayOptions::default(),
+            print0: false,
+            count_only: false,
+            split_output: None,
+            from: None,
+            fail_on_found: limit,
+            allow_sweeping_root: false,
+            depth_report: false,
+            stream: false,
+            report_unreadable: false,
+            stamp: None,
+            config: &config,
+        };
+        let result = cmd_summary(&ctx, &SummaryArgs::default());
+        assert_eq!(result.is_err(), fails, "limit {limit:?}");
+        let streamed = cmd_scan(&ScanContext { stream: true, ..ctx });
+        assert_eq!(streamed.is_err(), fails, "--stream, limit {limit:?}");
+    }
+
+    fs::remove_dir_all(&base).unwrap();
+}
+
+// ── --append-log ────────────────────────────────────────────────────────────
+
+#[test]
+fn append_log_adds_one_snapshot_line_per_run() {
+    let base = test_dir("summary_append_log");
+    let scan = base.join("projects");
+    create_rust_project(&scan.join("a"), 600);
+    let log = base.join("history.ndjson");
+
+    let roots = [scan.clone()];
+    let config = DevSweepConfig {
+        no_spinner: true,
+        ..Default::default()
+    };
+    let ctx = ScanContext {
+        roots: &roots,
+        max_depth: None,
+        older_than: None,
+        name: None,
+        modified_since: None,
+        json: true,
+        compact: true,
+        format: OutputFormat::Table,
+        columns: &Column::ALL,
+        display: DisplayOptions::default(),
+        print0: false,
+        count_only: false,
+        split_output: None,
+        from: None,
+        fail_on_found: None,
+        allow_sweeping_root: false,
+        depth_report: false,
+        stream: false,
+        report_unreadable: false,
+        stamp: None,
+        config: &config,
+    };
+    let args = SummaryArgs {
+        append_log: Some(log.clone()),
+        ..Default::default()
+    };
+    cmd_summary(&ctx, &args).unwrap();
+    create_rust_project(&scan.join("b"), 400);
+    cmd_summary(&ctx, &args).unwrap();
+
+    let contents = fs::read_to_string(&log).unwrap();
+    let snapshots: Vec<SummarySnapshot> = contents
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    fs::remove_dir_all(&base).unwrap();
+
+    assert_eq!(snapshots.len(), 2);
+    assert_eq!(snapshots[0].total_projects, 1);
+    assert_eq!(snapshots[0].total_reclaimable_bytes, 600);
+    assert_eq!(snapshots[1].total_projects, 2);
+    assert_eq!(snapshots[1].total_reclaimable_bytes, 1000);
+    assert_eq!(snapshots[1].by_kind["Rust"], 1000);
+    assert!(snapshots[0].timestamp <= snapshots[1].timestamp);
+}